@@ -140,9 +140,7 @@ impl<U : AstNodeUnion> AbstractAst<U> {
 
     pub fn try_promote<T : AstNodeKind<U>>(&self, wid: WeakIndex) -> Option<StrongIndex<T>> {
         if let Some(node) = self.get_weak(wid) {
-            let result: Result<T, _> = TryInto::<T>::try_into(node.value.clone());
-            
-            if result.is_ok() {
+            if T::is_kind_of(&node.value) {
                 Some(wid.promote())
             } else {
                 None
@@ -1,16 +1,16 @@
-use super::{index::WeakIndex, traits::{AstNodeUnion, GetChildren}, AbstractAst};
+use super::{index::WeakIndex, traits::AstNodeUnion, AbstractAst};
 
 
 impl<U : AstNodeUnion> AbstractAst<U> {
 
-    pub fn sequential_iter(&self) -> AstSequentialIter<U> {
+    pub fn sequential_iter(&self) -> AstSequentialIter<'_, U> {
         AstSequentialIter { 
             ast: self, 
             index: 1 
         }
     }
     
-    pub fn rec_iter_start_from<T : Into<WeakIndex> + Clone>(&self, index: T)  -> AstRecursiveIter<U> {
+    pub fn rec_iter_start_from<T : Into<WeakIndex> + Clone>(&self, index: T)  -> AstRecursiveIter<'_, U> {
         AstRecursiveIter {
             ast: self,
             stack: vec![(index.into(), 0)],
@@ -40,7 +40,7 @@ impl<'a, U: AstNodeUnion> Iterator for AstSequentialIter<'a, U> {
 }
 
 
-pub struct AstRecursiveIter<'a, U> where U: GetChildren {
+pub struct AstRecursiveIter<'a, U> where U: AstNodeUnion {
     ast: &'a AbstractAst<U>,
     stack: Vec<(WeakIndex, usize)>
 }
@@ -71,7 +71,11 @@ macro_rules! impl_as_mut {
 macro_rules! derive_implementations {
     ($union:ty, $p:path, $child:ty) => {
 
-        impl $crate::ast::traits::AstNodeKind<$union> for $child { }
+        impl $crate::ast::traits::AstNodeKind<$union> for $child {
+            fn is_kind_of(value: &$union) -> bool {
+                matches!(value, $p(_))
+            }
+        }
 
         impl From<$child> for $union {
             fn from(value: $child) -> Self {
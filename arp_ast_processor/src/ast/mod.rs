@@ -18,9 +18,11 @@ pub mod macros;
 pub mod tests;
 
 #[derive(Debug)]
-pub struct AbstractAst<T: GetChildren> {
+pub struct AbstractAst<T: AstNodeUnion> {
     nodes: Vec<AbstractNode<T>>,
     root_index: WeakIndex,
+    /// Types of nodes by their index, filled once they are resolved.
+    types: Vec<Option<T::Type>>,
 }
 
 #[derive(Clone, Copy)]
@@ -38,6 +40,7 @@ impl<U: AstNodeUnion> Default for AbstractAst<U> {
         AbstractAst {
             nodes: vec![AbstractNode {value:U::default(),parent_index:zero_index,self_index:zero_index,no_emit:false, span: Span::default() }],
             root_index: zero_index,
+            types: vec![],
         }
     }
 }
@@ -47,7 +50,22 @@ impl<U: AstNodeUnion> AbstractAst<U> {
     pub fn get_root_index(&self) -> WeakIndex {
         self.root_index
     }
-    
+
+    #[inline]
+    pub fn get_cached_type<T : Into<WeakIndex>>(&self, index: T) -> Option<&U::Type> {
+        self.types.get(index.into().index).and_then(Option::as_ref)
+    }
+
+    pub fn set_cached_type<T : Into<WeakIndex>>(&mut self, index: T, ty: Option<U::Type>) {
+        let index = index.into().index;
+
+        if self.types.len() <= index {
+            self.types.resize(self.nodes.len().max(index + 1), None);
+        }
+
+        self.types[index] = ty;
+    }
+
 }
 
 impl<U: GetChildren> AbstractNode<U>  {
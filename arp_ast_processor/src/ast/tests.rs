@@ -1,6 +1,6 @@
 #![cfg(test)]
 
-use crate::{ast::AbstractAst, derive_implementations};
+use crate::ast::AbstractAst;
 
 use super::{index::{StrongIndex, WeakIndex}, traits::{AstNodeUnion, GetChildren, PushRemoveRootChildren}};
 
@@ -38,7 +38,9 @@ impl PushRemoveRootChildren for TestAstNodeValue {
     }
 }
 
-impl AstNodeUnion for TestAstNodeValue { }
+impl AstNodeUnion for TestAstNodeValue {
+    type Type = ();
+}
 
 impl GetChildren for TestAstNodeValue {
     fn get_children(&self) -> Vec<WeakIndex> {
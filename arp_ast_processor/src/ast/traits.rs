@@ -2,8 +2,14 @@ use std::fmt::Debug;
 
 use super::WeakIndex;
 
-pub trait AstNodeUnion: GetChildren + Clone + Debug + Default + PartialEq + PushRemoveRootChildren {}
-pub trait AstNodeKind<U : AstNodeUnion>: TryFrom<U> + Into<U> + GetChildren + Debug + PartialEq { }
+pub trait AstNodeUnion: GetChildren + Clone + Debug + Default + PartialEq + PushRemoveRootChildren {
+    /// Type of a node, cached in the side table of the tree once it's known.
+    type Type: Clone + Debug + PartialEq;
+}
+pub trait AstNodeKind<U : AstNodeUnion>: TryFrom<U> + Into<U> + GetChildren + Debug + PartialEq {
+    /// Whether `value` holds this kind, without taking it out of the union.
+    fn is_kind_of(value: &U) -> bool;
+}
 
 pub trait GetChildren {
     fn get_children(&self) -> Vec<WeakIndex>;
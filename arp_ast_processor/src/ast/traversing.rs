@@ -6,9 +6,7 @@ impl<U : AstNodeUnion> AbstractAst<U> {
 
     pub fn get_child_of_kind<T : AstNodeKind<U>, I : Into<WeakIndex> + Clone>(&self, index: I) -> Option<StrongIndex<T>> {
         for node in self.rec_iter_start_from(index).flat_map(|(index, _)| self.get_weak(index)) {
-            let result: Result<T, _> = TryInto::<T>::try_into(node.value.clone());
-
-            if result.is_ok() { return Some(node.self_index.promote()) }
+            if T::is_kind_of(&node.value) { return Some(node.self_index.promote()) }
         }
 
         None
@@ -18,9 +16,7 @@ impl<U : AstNodeUnion> AbstractAst<U> {
         where U : TryInto<T> { 
         let mut indices = Vec::new();
         for node in self.rec_iter_start_from(index).flat_map(|(index, _)| self.get_weak(index)) {            
-            let result: Result<T, _> = TryInto::<T>::try_into(node.value.clone());
-    
-            if result.is_ok() { indices.push(node.self_index.promote()); }
+            if T::is_kind_of(&node.value) { indices.push(node.self_index.promote()); }
         }
 
         indices
@@ -31,9 +27,11 @@ impl<U : AstNodeUnion> AbstractAst<U> {
         let mut indices = Vec::new();
         
         for node in self.rec_iter_start_from(index).flat_map(|(index, _)| self.get_weak(index)) {
-            let result: Result<T, _> = TryInto::<T>::try_into(node.value.clone());
-            
-            match result {
+            if !T::is_kind_of(&node.value) {
+                continue;
+            }
+
+            match TryInto::<T>::try_into(node.value.clone()) {
                 Ok(ok) => {
                     if !filter(&ok) {
                         continue;
@@ -51,16 +49,15 @@ impl<U : AstNodeUnion> AbstractAst<U> {
 
     pub fn get_parent_of_kind<T : AstNodeKind<U>, I : Into<WeakIndex> + Clone>(&self, index: I) -> Option<StrongIndex<T>> {
         if let Some(node) = self.get_weak(index.clone()) {
-            let result: Result<T, _> = TryInto::<T>::try_into(node.value.clone());
-
             if node.parent_index == node.self_index {
                 return None;
             }
 
-            match result {
-                Ok(_) => return Some(node.self_index.promote()),
-                Err(_) => return self.get_parent_of_kind(node.parent_index),
+            if T::is_kind_of(&node.value) {
+                return Some(node.self_index.promote());
             }
+
+            return self.get_parent_of_kind(node.parent_index);
         }
 
         None
@@ -72,12 +69,11 @@ impl<U : AstNodeUnion> AbstractAst<U> {
                 return self.get_parent_of_kind_with_filter(node.parent_index, filter);
             }
 
-            let result: Result<T, _> = TryInto::<T>::try_into(node.value.clone());
-
-            match result {
-                Ok(_) => return Some(node.self_index.promote()),
-                Err(_) => return self.get_parent_of_kind_with_filter(node.parent_index, filter),
+            if T::is_kind_of(&node.value) {
+                return Some(node.self_index.promote());
             }
+
+            return self.get_parent_of_kind_with_filter(node.parent_index, filter);
         }
 
         None
@@ -88,9 +84,7 @@ impl<U : AstNodeUnion> AbstractAst<U> {
         let mut indices = vec![];
         
         for node in self.sequential_iter().flat_map(|index| self.get_weak(index)) {
-            let result: Result<T, _> = TryInto::<T>::try_into(node.value.clone());
-
-            if result.is_ok() { indices.push(node.self_index.promote()); }
+            if T::is_kind_of(&node.value) { indices.push(node.self_index.promote()); }
         }
 
         indices
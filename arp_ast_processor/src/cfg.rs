@@ -0,0 +1,190 @@
+use crate::types::{ast_node_value::{Ast, Id}, block_scope::BlockScope, expression::{Expression, Literal}, function::Function, statement::{IfKind, Statement}};
+
+/// Straight run of statements, control only enters at the top and leaves at the bottom.
+/// Compound statements are recorded where their condition is evaluated.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct BasicBlock {
+    pub statements: Vec<Id<Statement>>,
+    pub successors: Vec<usize>,
+}
+
+/// `while` or `for` statement, with the block evaluating its condition and the block following it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Loop {
+    pub statement: Id<Statement>,
+    pub header: usize,
+    pub after: usize,
+}
+
+/// Control flow graph of a function body. Flow starts in [`ControlFlowGraph::ENTRY`], `return`
+/// jumps to [`ControlFlowGraph::EXIT`], falling off the end of the body goes through `end`.
+/// A trailing expression of the body is folded into a `return` already.
+/// Code after `return` or `break` lands in blocks without predecessors.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ControlFlowGraph {
+    pub blocks: Vec<BasicBlock>,
+    pub loops: Vec<Loop>,
+    pub end: usize,
+}
+
+impl ControlFlowGraph {
+    pub const ENTRY: usize = 0;
+    pub const EXIT: usize = 1;
+
+    pub fn build(ast: &Ast, func: &Id<Function>) -> Self {
+        let mut builder = Builder { ast, blocks: vec![BasicBlock::default(), BasicBlock::default()], loops: vec![], breaks: vec![] };
+
+        let end = builder.block(&ast.get(func).block, Self::ENTRY);
+        builder.edge(end, Self::EXIT);
+
+        Self { blocks: builder.blocks, loops: builder.loops, end }
+    }
+
+    /// Blocks reachable from the entry, by index.
+    pub fn reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.blocks.len()];
+        let mut stack = vec![Self::ENTRY];
+
+        while let Some(block) = stack.pop() {
+            if !std::mem::replace(&mut reachable[block], true) {
+                stack.extend(&self.blocks[block].successors);
+            }
+        }
+
+        reachable
+    }
+
+    /// Whether flow can leave the loop, by breaking out of it, returning from inside of it or by its condition.
+    pub fn exits(&self, lp: &Loop, reachable: &[bool]) -> bool {
+        reachable[lp.after] || (lp.header..lp.after).any(|block| reachable[block] && self.blocks[block].successors.contains(&Self::EXIT))
+    }
+}
+
+struct Builder<'a> {
+    ast: &'a Ast,
+    blocks: Vec<BasicBlock>,
+    loops: Vec<Loop>,
+    /// Blocks ending in `break`, for every loop being built.
+    breaks: Vec<Vec<usize>>,
+}
+
+impl Builder<'_> {
+    fn new_block(&mut self) -> usize {
+        self.blocks.push(BasicBlock::default());
+        self.blocks.len() - 1
+    }
+
+    fn edge(&mut self, from: usize, to: usize) {
+        self.blocks[from].successors.push(to);
+    }
+
+    /// Adds the statements of `bs` starting in `current`, returns the block flow continues in.
+    fn block(&mut self, bs: &Id<BlockScope>, mut current: usize) -> usize {
+        for stmt in &self.ast.get(bs).statements {
+            current = self.statement(stmt, current);
+        }
+
+        current
+    }
+
+    fn statement(&mut self, stmt: &Id<Statement>, current: usize) -> usize {
+        match self.ast.get(stmt) {
+            Statement::Return(_) => {
+                self.blocks[current].statements.push(*stmt);
+                self.edge(current, ControlFlowGraph::EXIT);
+                self.new_block()
+            },
+            Statement::Break => {
+                self.blocks[current].statements.push(*stmt);
+
+                if let Some(breaks) = self.breaks.last_mut() {
+                    breaks.push(current);
+                }
+
+                self.new_block()
+            },
+            Statement::Block(bs) => {
+                self.blocks[current].statements.push(*stmt);
+                self.block(bs, current)
+            },
+            Statement::IfStmt(if_kinds) => {
+                self.blocks[current].statements.push(*stmt);
+
+                let mut condition = current;
+                let mut ends = vec![];
+                let mut has_else = false;
+
+                for if_kind in if_kinds {
+                    let block = match if_kind {
+                        IfKind::If(_, block) => block,
+                        IfKind::ElseIf(_, block) => {
+                            let next = self.new_block();
+                            self.edge(condition, next);
+                            condition = next;
+                            block
+                        },
+                        IfKind::Else(block) => {
+                            has_else = true;
+                            block
+                        },
+                    };
+
+                    let branch = self.new_block();
+                    self.edge(condition, branch);
+                    ends.push(self.block(block, branch));
+                }
+
+                let after = self.new_block();
+
+                if !has_else {
+                    self.edge(condition, after);
+                }
+
+                for end in ends {
+                    self.edge(end, after);
+                }
+
+                after
+            },
+            Statement::WhileStmt { expr, block } => self.looped(stmt, block, current, self.is_true(expr)),
+            Statement::ForStmt { block, .. } => self.looped(stmt, block, current, false),
+            _ => {
+                self.blocks[current].statements.push(*stmt);
+                current
+            },
+        }
+    }
+
+    /// Loop whose condition is evaluated in a header block of its own, the body jumps back to it.
+    /// Blocks of the body lie between the header and the block after the loop.
+    fn looped(&mut self, stmt: &Id<Statement>, block: &Id<BlockScope>, current: usize, is_infinite: bool) -> usize {
+        let header = self.new_block();
+        self.edge(current, header);
+        self.blocks[header].statements.push(*stmt);
+
+        let body = self.new_block();
+        self.edge(header, body);
+
+        self.breaks.push(vec![]);
+        let body_end = self.block(block, body);
+        self.edge(body_end, header);
+        let breaks = self.breaks.pop().unwrap_or_default();
+
+        let after = self.new_block();
+
+        if !is_infinite {
+            self.edge(header, after);
+        }
+
+        for brk in breaks {
+            self.edge(brk, after);
+        }
+
+        self.loops.push(Loop { statement: *stmt, header, after });
+        after
+    }
+
+    fn is_true(&self, expr: &Id<Expression>) -> bool {
+        matches!(self.ast.get(expr), Expression::Literal(Literal::Bool(true)))
+    }
+}
@@ -20,7 +20,7 @@ impl Folder<Spanned<ChumskyNode>, BlockScope> for ChumskyNodeVisitor {
                 }).collect::<Result<Vec<_>, _>>()?;
 
                 let return_expression = match return_expression {
-                    Some(some) => self.fold(some.as_ref(), next).ok(),
+                    Some(some) => Some(self.fold(some.as_ref(), next)?),
                     None => None,
                 };
 
@@ -16,7 +16,11 @@ impl Folder<Spanned<ChumskyNode>, ArpFile> for ChumskyNodeVisitor {
         let arp_file_index = self.ast.push(ArpFile::default(), parent);
         let mut tmp_file = ArpFile::default();
 
-        for node in declarations {
+        // Types go first, so signatures and implementations can refer to generic classes declared further down.
+        let (types, rest): (Vec<_>, Vec<_>) = declarations.iter()
+            .partition(|node| matches!(node.get_value(), ChumskyNode::Structure(..) | ChumskyNode::StructDecl(..) | ChumskyNode::UnionDecl(..) | ChumskyNode::EnumDecl(..) | ChumskyNode::TraitDecl(..)));
+
+        for node in types.into_iter().chain(rest) {
             match node.get_value() {
                 ChumskyNode::ImportDecl(is_extern, path, types) => {
             
@@ -45,8 +49,13 @@ impl Folder<Spanned<ChumskyNode>, ArpFile> for ChumskyNodeVisitor {
                     };
                     tmp_file.imports.push(import_declaration);
                 },
-                ChumskyNode::ImplementationDecl(..) => tmp_file.implementations.push(self.fold(node, arp_file_index.as_weak())?),
-                ChumskyNode::Structure(..) => tmp_file.structures.push(self.fold(node, arp_file_index.as_weak())?),
+                ChumskyNode::ImplementationDecl(..) | ChumskyNode::TraitImplementationDecl(..) => tmp_file.implementations.push(self.fold(node, arp_file_index.as_weak())?),
+                // The trait itself is a type, its default methods form an implementation of it.
+                ChumskyNode::TraitDecl(..) => {
+                    tmp_file.structures.push(self.fold(node, arp_file_index.as_weak())?);
+                    tmp_file.implementations.push(self.fold(node, arp_file_index.as_weak())?);
+                },
+                ChumskyNode::Structure(..) | ChumskyNode::StructDecl(..) | ChumskyNode::UnionDecl(..) | ChumskyNode::EnumDecl(..) => tmp_file.structures.push(self.fold(node, arp_file_index.as_weak())?),
                 ChumskyNode::FuncDecl(..) => tmp_file.functions.push(self.fold(node, arp_file_index.as_weak())?),
                 _ => return Err(ChumskyFoldError::UnexpectedChumsky(item.clone(), "file".into()))
             };
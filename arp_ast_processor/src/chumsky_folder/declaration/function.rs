@@ -1,7 +1,7 @@
 use arp_parser::types::ChumskyNode;
 use arp_types::Spanned;
 
-use crate::{chumsky_folder::{utils::{parse_ident, parse_type}, ChumskyFoldError, ChumskyNodeVisitor, Folder}, types::{ast_node_value::{Id, WId}, block_scope::{BlockScope, BlockScopeKind}, function::{Function, FunctionKind}, type_collection::TypeId}};
+use crate::{chumsky_folder::{utils::{parse_default, parse_generic_decl, parse_ident, parse_tuple_pattern, parse_type}, ChumskyFoldError, ChumskyNodeVisitor, Folder}, types::{ast_node_value::{Id, WId}, block_scope::{BlockScope, BlockScopeKind}, expression::Expression, function::{Function, FunctionKind}, implementation::Implementation, simple::Identifier, statement::Statement, type_collection::{GenericOwner, TypeId}}};
 
 
 
@@ -13,14 +13,44 @@ impl Folder<Spanned<ChumskyNode>, Function> for ChumskyNodeVisitor {
 
         let func = match item.get_value() {
             ChumskyNode::FuncDecl(identifier, parameters, return_type, block) => {
+                let (ident, generic_params) = parse_generic_decl(identifier)?;
+
+                // Method type parameters are named after the implemented type, e.g. `Box.map.U`.
+                let owner = self.ast.get_parent_of_kind::<Implementation, _>(parent)
+                    .map(|im| self.ast.get(&im).impl_type.clone())
+                    .and_then(|ty| self.ast.get_arp_file_in_parent(parent).and_then(|file| file.type_collection.get_source_name(&ty)).map(str::to_string))
+                    .map(|ty| format!("{ty}.{}", ident.as_ref()))
+                    .unwrap_or_else(|| ident.0.to_string());
+
+                let generics = self.declare_generics(&owner, GenericOwner::Method, &generic_params, parent)?;
+
                 let mut parsed_parameters = vec![];
+                let mut defaults = vec![];
+                let mut destructured = vec![];
                 let mut kind = FunctionKind::Static;
 
                 for parameter in parameters {
+                    let (parameter, default) = match parameter.get_value() {
+                        ChumskyNode::DefaultParam(parameter, default) => (parameter.as_ref(), Some(parse_default(default)?)),
+                        _ => (parameter, None),
+                    };
+
+                    if !matches!(parameter.get_value(), ChumskyNode::MutThis(_)) {
+                        defaults.push(default);
+                    }
+
                     match parameter.get_value() {
+                        // `fn swap((a, b): (int32, string))` takes a hidden parameter which is destructured first thing in the body.
+                        ChumskyNode::VarAndType(pattern, t) if matches!(pattern.get_value(), ChumskyNode::TuplePattern(_)) => {
+                            let ident = Identifier::destructured(parsed_parameters.len());
+                            let ident_type = parse_type(t, parent, &mut self.ast, &self.generics)?;
+
+                            destructured.push((ident.clone(), parse_tuple_pattern(pattern)?, ident_type.clone()));
+                            parsed_parameters.push((ident, ident_type));
+                        },
                         ChumskyNode::VarAndType(ident, t) => {
                             let ident = parse_ident(ident);
-                            let ident_type = parse_type(t, parent, &mut self.ast)?;
+                            let ident_type = parse_type(t, parent, &mut self.ast, &self.generics)?;
 
                             parsed_parameters.push(
                                 (ident?, ident_type)
@@ -33,11 +63,6 @@ impl Folder<Spanned<ChumskyNode>, Function> for ChumskyNodeVisitor {
                     };
                 }
 
-                let ident = match identifier.get_value() {
-                    ChumskyNode::Identifier(name) => Ok(name.as_ref().into()),
-                    _ => Err(ChumskyFoldError::UnexpectedChumsky(*identifier.clone(), "identifier".into()))
-                };
-
 
                 let void = match self.ast.get_arp_file_in_parent(parent) {
                     Some(file) => {
@@ -46,21 +71,51 @@ impl Folder<Spanned<ChumskyNode>, Function> for ChumskyNodeVisitor {
                     None => TypeId::None,
                 };
 
-                let return_type = return_type.as_ref().map(|ty| parse_type(ty, parent, &mut self.ast)).unwrap_or(Ok(void))?;
-                
+                let return_type = return_type.as_ref().map(|ty| parse_type(ty, parent, &mut self.ast, &self.generics)).unwrap_or_else(|| Ok(void.clone()))?;
+
                 let block = self.fold(block.as_ref(), next)?;
 
                 self.ast.mutate_value(&block, |block: &mut BlockScope| {
                     block.kind = BlockScopeKind::Function(next);
                 });
 
+                // A trailing expression of the body is returned, `fn answer() -> int32 { 42 }`.
+                if let Some(tail) = self.ast.get(&block).return_expression {
+                    let stmt = self.ast.next_index(block.as_weak());
+                    self.ast.mutate_node(&tail, |node| node.__unsafe_set_parent(stmt));
+
+                    let span = self.ast.get_node(&tail).span;
+                    let stmt = match return_type.is_same(&void) {
+                        true => self.ast.place_spanned(stmt, Statement::Expression(tail), span),
+                        false => self.ast.place_spanned(stmt, Statement::Return(tail), span),
+                    };
+
+                    self.ast.mutate_value(&block, |block: &mut BlockScope| {
+                        block.statements.push(stmt);
+                        block.return_expression = None;
+                    });
+                }
+
+                for (ident, bindings, ty) in destructured.into_iter().rev() {
+                    let stmt = self.ast.next_index(block.as_weak());
+                    let expr = self.ast.push(Expression::Variable(ident), stmt);
+                    let stmt = self.ast.place(stmt, Statement::Destructure { is_mutable: false, bindings, ty, expr });
+
+                    self.ast.mutate_value(&block, |block: &mut BlockScope| block.statements.insert(0, stmt));
+                }
+
+                self.leave_generics(generic_params.len());
+
                 Ok(Function {
-                    name: ident?,
+                    name: ident,
                     parameters: parsed_parameters,
+                    defaults,
                     return_type,
                     block,
                     kind,
-                    registers : vec![]
+                    generics,
+                    registers : vec![],
+                    locals: vec![],
                 })
             },
             _ => Err(ChumskyFoldError::UnexpectedChumsky(item.clone(), "function".into()))
@@ -69,4 +124,3 @@ impl Folder<Spanned<ChumskyNode>, Function> for ChumskyNodeVisitor {
         Ok(self.ast.place_spanned(next, func?, item.get_span()))
     }
 }
-
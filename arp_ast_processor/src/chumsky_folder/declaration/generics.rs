@@ -0,0 +1,31 @@
+use crate::{chumsky_folder::{utils::{parse_type, GenericParamDecl}, ChumskyFoldError, ChumskyNodeVisitor}, types::{ast_node_value::WId, type_collection::{GenericOwner, TypeId}}};
+
+
+impl ChumskyNodeVisitor {
+    /// Allocates type parameters as `{owner}.{T}` and brings them into scope until `leave_generics`.
+    pub(crate) fn declare_generics(&mut self, owner: &str, kind: GenericOwner, params: &[GenericParamDecl], parent: WId) -> Result<Vec<TypeId>, ChumskyFoldError> {
+        let mut generics = vec![];
+
+        for (index, (ident, bounds)) in params.iter().enumerate() {
+            let bounds = bounds.iter()
+                .map(|bound| parse_type(bound, parent, &mut self.ast, &self.generics))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let full_name = format!("{owner}.{}", ident.as_ref());
+
+            let ty = match self.ast.get_mut_arp_file_in_parent(parent) {
+                Some(file) => file.type_collection.allocate_parameter(&full_name, kind, index, bounds),
+                None => TypeId::None,
+            };
+
+            self.generics.push((ident.clone(), full_name));
+            generics.push(ty);
+        }
+
+        Ok(generics)
+    }
+
+    pub(crate) fn leave_generics(&mut self, count: usize) {
+        self.generics.truncate(self.generics.len() - count);
+    }
+}
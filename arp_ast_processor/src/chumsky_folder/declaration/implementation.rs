@@ -1,7 +1,7 @@
 use arp_parser::types::ChumskyNode;
 use arp_types::Spanned;
 
-use crate::{chumsky_folder::{utils::parse_type, ChumskyFoldError, ChumskyNodeVisitor, Folder}, types::{ast_node_value::{Id, WId}, function::Function, implementation::Implementation}};
+use crate::{chumsky_folder::{utils::{parse_ident, parse_type}, ChumskyFoldError, ChumskyNodeVisitor, Folder}, types::{ast_node_value::{Id, WId}, function::Function, implementation::Implementation, type_collection::{TypeId, TypeSourceKind}}};
 
 
 
@@ -10,38 +10,104 @@ impl Folder<Spanned<ChumskyNode>, Implementation> for ChumskyNodeVisitor {
     type Error = ChumskyFoldError;
 
     fn fold(&mut self, item: &Spanned<ChumskyNode>, parent: WId) -> Result<Id<Implementation>, Self::Error> {
-        let impl_type = if let ChumskyNode::ImplementationDecl(impl_type, _functions) = item.get_value() {
-            parse_type(impl_type, parent, &mut self.ast)?
-        } else {
-            return Err(ChumskyFoldError::UnexpectedChumsky(item.clone(), "implementation".into()));
+        let (impl_type, trait_type, generics_count, functions) = match item.get_value() {
+            ChumskyNode::ImplementationDecl(impl_type, functions) => {
+                let (impl_type, generics_count) = self.bind_impl_generics(impl_type, parent)?;
+                (impl_type, None, generics_count, functions)
+            },
+            ChumskyNode::TraitImplementationDecl(trait_type, impl_type, functions) => {
+                let (impl_type, generics_count) = self.bind_impl_generics(impl_type, parent)?;
+                let trait_type = parse_type(trait_type, parent, &mut self.ast, &self.generics)?;
+                (impl_type, Some(trait_type), generics_count, functions)
+            },
+            // Default methods of a trait, signatures are folded with the trait itself.
+            ChumskyNode::TraitDecl(trait_type, items) => {
+                (parse_type(trait_type, parent, &mut self.ast, &self.generics)?, None, 0, items)
+            },
+            _ => return Err(ChumskyFoldError::UnexpectedChumsky(item.clone(), "implementation".into())),
         };
 
+        // Types of this file are folded already, so anything unresolved or not local is declared elsewhere.
+        let is_extension = trait_type.is_none() && generics_count == 0 && self.ast.get_arp_file_in_parent(parent).is_some_and(|file| {
+            !file.type_collection.try_get_strong(&impl_type).is_some_and(|info| info.source == TypeSourceKind::LocalArp)
+        });
+
         let im = self.ast.push(Implementation {
             impl_type: impl_type.clone(),
+            trait_type: trait_type.clone(),
             functions: vec![],
+            is_extension,
         }, parent);
 
-        let functions = match item.get_value() {
-            ChumskyNode::ImplementationDecl(_impl_type, functions) => {
-                Ok(functions.iter().flat_map(|node| {
-                    match node.get_value() {
-                        ChumskyNode::FuncDecl(..) => Ok(self.fold(node, im.as_weak())),
-                        _ => Err(ChumskyFoldError::UnexpectedChumsky(item.clone(), "function".into()))
-                    }
-                }).collect::<Result<Vec<Id<Function>>, _>>()?)
-            },
-            _ => Err(ChumskyFoldError::UnexpectedChumsky(item.clone(), "implementation".into()))
-        }?;
+        let functions = functions.iter().filter_map(|node| {
+            match node.get_value() {
+                ChumskyNode::FuncDecl(..) => Some(self.fold(node, im.as_weak())),
+                ChumskyNode::FuncSignatureDecl(..) => None,
+                _ => Some(Err(ChumskyFoldError::UnexpectedChumsky(item.clone(), "function".into())))
+            }
+        }).collect::<Result<Vec<Id<Function>>, _>>()?;
+
+        self.leave_generics(generics_count);
 
         self.ast.get_mut(&im).functions.extend(functions);
 
         let funcs = self.ast.get(&im).functions.iter().map(|id| (*id, self.ast.get(id).clone())).collect::<Vec<_>>();
 
-        if let Some(file) = self.ast.get_mut_arp_file_in_parent(im.as_weak()) { 
-            file.type_collection.extend_type_methods(impl_type, funcs)
+        if let Some(file) = self.ast.get_mut_arp_file_in_parent(im.as_weak()) {
+            if let Some(trait_type) = trait_type {
+                file.type_collection.add_trait(&impl_type, trait_type);
+            }
+
+            match is_extension {
+                true => file.type_collection.add_extension_methods(impl_type, funcs),
+                false => file.type_collection.extend_type_methods(impl_type, funcs),
+            }
         }
-        
+
 
         Ok(im)
     }
 }
+
+impl ChumskyNodeVisitor {
+    /// `impl Box<U>` names the type parameters of `class Box<T>` positionally, so `U` is brought
+    /// into scope as `Box.T` and the methods are added to the generic definition.
+    fn bind_impl_generics(&mut self, impl_type: &Spanned<ChumskyNode>, parent: WId) -> Result<(TypeId, usize), ChumskyFoldError> {
+        let generic = match impl_type.get_value() {
+            ChumskyNode::Type(path) => match path.split_last().map(|(last, prefix)| (last.get_value(), prefix)) {
+                Some((ChumskyNode::GenericIdentifier(ident, args), prefix)) => Some((prefix, ident, args)),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let (Some((prefix, ident, args)), Some(file)) = (generic, self.ast.get_arp_file_in_parent(parent)) else {
+            return Ok((parse_type(impl_type, parent, &mut self.ast, &self.generics)?, 0));
+        };
+
+        let name = prefix.iter()
+            .chain(std::iter::once(ident.as_ref()))
+            .map(|segment| parse_ident(segment).map(|ident| ident.0.to_string()))
+            .collect::<Result<Vec<_>, _>>()?
+            .join(".");
+
+        let collection = &file.type_collection;
+        let definition = collection.resolve_name(&name);
+
+        // Implementations for a particular instantiation, like `impl Box<int32>`, aren't supported.
+        let params = match collection.get_generic_params(&definition) {
+            Some(params) if params.len() == args.len() => params,
+            _ => return Err(ChumskyFoldError::Unimplemented(impl_type.clone())),
+        };
+
+        let bindings = params.iter().zip(args).map(|(param, arg)| match arg.get_value() {
+            ChumskyNode::Type(path) if path.len() == 1 => Ok((parse_ident(&path[0])?, collection.get_name(param).unwrap_or_default().to_string())),
+            _ => Err(ChumskyFoldError::Unimplemented(impl_type.clone())),
+        }).collect::<Result<Vec<_>, _>>()?;
+
+        let count = bindings.len();
+        self.generics.extend(bindings);
+
+        Ok((definition, count))
+    }
+}
@@ -2,6 +2,7 @@ pub mod function;
 pub mod structure;
 pub mod implementation;
 pub mod arp_file;
+pub mod generics;
 
 use arp_parser::types::ChumskyNode;
 use arp_types::Spanned;
@@ -17,8 +18,8 @@ impl Folder<Spanned<ChumskyNode>, Declaration> for ChumskyNodeVisitor {
 
         let decl = match item.get_value() {
             ChumskyNode::File(..) => Ok(Declaration::File(self.fold(item, next)?)),
-            ChumskyNode::ImplementationDecl(..) => Ok(Declaration::Implementation(self.fold(item, next)?)),
-            ChumskyNode::Structure(..) => Ok(Declaration::Structure(self.fold(item, next)?)),
+            ChumskyNode::ImplementationDecl(..) | ChumskyNode::TraitImplementationDecl(..) => Ok(Declaration::Implementation(self.fold(item, next)?)),
+            ChumskyNode::Structure(..) | ChumskyNode::StructDecl(..) | ChumskyNode::UnionDecl(..) | ChumskyNode::EnumDecl(..) => Ok(Declaration::Structure(self.fold(item, next)?)),
             ChumskyNode::FuncDecl(..) => {
                 Ok(Declaration::Function(self.fold(item, next)?))
             }
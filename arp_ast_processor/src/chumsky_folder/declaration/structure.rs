@@ -1,7 +1,7 @@
 use arp_parser::types::ChumskyNode;
 use arp_types::Spanned;
 
-use crate::{chumsky_folder::{utils::{parse_ident, parse_type}, ChumskyFoldError, ChumskyNodeVisitor, Folder}, types::{ast_node_value::{Id, WId}, structure::{Structure, StructureKind}, type_collection::TypeId}};
+use crate::{chumsky_folder::{utils::{parse_default, parse_generic_decl, parse_ident, parse_type, parse_type_as_ident, GenericParamDecl}, ChumskyFoldError, ChumskyNodeVisitor, Folder}, types::{ast_node_value::{Id, WId}, simple::Identifier, structure::{Structure, StructureKind}, type_collection::{GenericOwner, MethodInfo, TypeId, TypeKind}}};
 
 
 impl Folder<Spanned<ChumskyNode>, Structure> for ChumskyNodeVisitor {
@@ -11,33 +11,130 @@ impl Folder<Spanned<ChumskyNode>, Structure> for ChumskyNodeVisitor {
         let next = self.ast.next_index(parent);
 
         let structure = match item.get_value() {
-            ChumskyNode::Structure(structure_type, _inherits_from, fields) => {
-                let mut parsed_fields = vec![];
+            ChumskyNode::Structure(structure_type, inherits_from, fields) => {
+                let (name, generic_params) = parse_generic_decl(structure_type)?;
 
-                for parameter in fields {
-                    match parameter.get_value() {
-                        ChumskyNode::VarAndType(ident, t) => {
-                            let ident = parse_ident(ident);
-                            let ty = parse_type(t, parent, &mut self.ast)?;
+                let self_type = if generic_params.is_empty() {
+                    let parsed_fields = self.fold_fields(fields, parent)?;
+                    let self_type = self.allocate_type(structure_type, parsed_fields, parent)?;
+                    self.fold_base(&self_type, inherits_from, parent)?;
+                    self_type
+                } else {
+                    self.allocate_generic_type(name, &generic_params, inherits_from, fields, parent)?
+                };
+
+                Ok(Structure {
+                    kind: StructureKind::Class,
+                    self_type,
+                })
+            }
+            ChumskyNode::StructDecl(structure_type, fields) => {
+                let (name, generic_params) = parse_generic_decl(structure_type)?;
+
+                let self_type = if generic_params.is_empty() {
+                    let parsed_fields = self.fold_fields(fields, parent)?;
+                    self.allocate_type(structure_type, parsed_fields, parent)?
+                } else {
+                    self.allocate_generic_type(name, &generic_params, &[], fields, parent)?
+                };
+
+                if let Some(file) = self.ast.get_mut_arp_file_in_parent(parent) {
+                    file.type_collection.set_value_type(&self_type);
+                }
+
+                Ok(Structure {
+                    kind: StructureKind::Struct,
+                    self_type,
+                })
+            }
+            ChumskyNode::EnumDecl(enum_type, variants) => {
+                // Members without a value continue counting from the previous one.
+                let mut next_value = 0;
+                let mut members = vec![];
 
-                            parsed_fields.push((ident?, ty));
+                for variant in variants {
+                    match variant.get_value() {
+                        ChumskyNode::EnumVariant(ident, value) => {
+                            let value = value.unwrap_or(next_value);
+                            members.push((parse_ident(ident)?, value));
+                            next_value = value + 1;
                         },
-                        _ => return Err(ChumskyFoldError::UnexpectedChumsky(parameter.clone(), "identifier".into()))
-                    };
+                        _ => return Err(ChumskyFoldError::UnexpectedChumsky(variant.clone(), "enum member".into()))
+                    }
                 }
 
-                let self_type = match parse_type(structure_type, parent, &mut self.ast) {
-                    Ok(TypeId::Weak(id)) => match self.ast.get_mut_arp_file_in_parent(parent) {
-                        Some(file) => {
-                            file.type_collection.try_allocate(id,  parsed_fields.clone())
+                let int = self.ast.get_arp_file_in_parent(parent).map(|file| file.type_collection.get_int()).unwrap_or_default();
+                let self_type = self.allocate_type(enum_type, vec![(Identifier::from("value"), int)], parent)?;
+
+                if let Some(file) = self.ast.get_mut_arp_file_in_parent(parent) {
+                    file.type_collection.set_kind(&self_type, TypeKind::Enum(members));
+                    file.type_collection.set_value_type(&self_type);
+                }
+
+                Ok(Structure {
+                    kind: StructureKind::Enum,
+                    self_type,
+                })
+            }
+            ChumskyNode::UnionDecl(union_type, variants) => {
+                let union_name = parse_type_as_ident(union_type)?;
+                let self_type = self.allocate_type(union_type, vec![], parent)?;
+
+                let mut variant_types = vec![];
+                for variant in variants {
+                    match variant.get_value() {
+                        ChumskyNode::UnionVariant(ident, fields) => {
+                            let variant_name = format!("{}.{}", union_name.as_ref(), parse_ident(ident)?.as_ref());
+                            let parsed_fields = self.fold_fields(fields, parent)?;
+
+                            let variant_type = match self.ast.get_mut_arp_file_in_parent(parent) {
+                                Some(file) => match file.type_collection.get_or_allocate(&variant_name) {
+                                    TypeId::Weak(id) => file.type_collection.try_allocate(id, parsed_fields),
+                                    _ => TypeId::None,
+                                },
+                                None => TypeId::None,
+                            };
+
+                            variant_types.push(variant_type);
                         },
-                        None => TypeId::None,
-                    },
-                    _ => TypeId::None,
-                };
+                        _ => return Err(ChumskyFoldError::UnexpectedChumsky(variant.clone(), "union variant".into()))
+                    }
+                }
+
+                if let Some(file) = self.ast.get_mut_arp_file_in_parent(parent) {
+                    for variant_type in variant_types.iter() {
+                        file.type_collection.set_kind(variant_type, TypeKind::UnionVariant(self_type.clone()));
+                    }
+                    file.type_collection.set_kind(&self_type, TypeKind::Union(variant_types));
+                }
 
                 Ok(Structure {
-                    kind: StructureKind::Class,
+                    kind: StructureKind::Union,
+                    self_type,
+                })
+            }
+            ChumskyNode::TraitDecl(trait_type, items) => {
+                let (_, generic_params) = parse_generic_decl(trait_type)?;
+
+                // Generic traits aren't supported yet.
+                if !generic_params.is_empty() {
+                    return Err(ChumskyFoldError::Unimplemented(*trait_type.clone()));
+                }
+
+                let self_type = self.allocate_type(trait_type, vec![], parent)?;
+
+                let signatures = items.iter()
+                    .filter(|node| matches!(node.get_value(), ChumskyNode::FuncSignatureDecl(..)))
+                    .map(|node| self.fold_signature(node, parent))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if let Some(file) = self.ast.get_mut_arp_file_in_parent(parent) {
+                    file.type_collection.set_kind(&self_type, TypeKind::Trait);
+                    file.type_collection.extend_type_signatures(&self_type, signatures);
+                }
+
+                Ok(Structure {
+                    kind: StructureKind::Trait,
                     self_type,
                 })
             }
@@ -54,3 +151,121 @@ impl Folder<Spanned<ChumskyNode>, Structure> for ChumskyNodeVisitor {
         Ok(index)
     }
 }
+
+impl ChumskyNodeVisitor {
+    fn fold_fields(&mut self, fields: &[Spanned<ChumskyNode>], parent: WId) -> Result<Vec<(Identifier, TypeId)>, ChumskyFoldError> {
+        let mut parsed_fields = vec![];
+
+        for parameter in fields {
+            match parameter.get_value() {
+                ChumskyNode::VarAndType(ident, t) => {
+                    let ident = parse_ident(ident);
+                    let ty = parse_type(t, parent, &mut self.ast, &self.generics)?;
+
+                    parsed_fields.push((ident?, ty));
+                },
+                _ => return Err(ChumskyFoldError::UnexpectedChumsky(parameter.clone(), "identifier".into()))
+            };
+        }
+
+        Ok(parsed_fields)
+    }
+
+    /// Method of a trait without a default body. Trait methods always take `this`.
+    fn fold_signature(&mut self, signature: &Spanned<ChumskyNode>, parent: WId) -> Result<MethodInfo, ChumskyFoldError> {
+        let ChumskyNode::FuncSignatureDecl(identifier, parameters, return_type) = signature.get_value() else {
+            return Err(ChumskyFoldError::UnexpectedChumsky(signature.clone(), "function signature".into()));
+        };
+
+        let (name, generic_params) = parse_generic_decl(identifier)?;
+
+        if !generic_params.is_empty() || !parameters.iter().any(|p| matches!(p.get_value(), ChumskyNode::MutThis(..))) {
+            return Err(ChumskyFoldError::Unimplemented(signature.clone()));
+        }
+
+        let (args, defaults): (Vec<_>, Vec<_>) = parameters.iter().filter_map(|parameter| {
+            let (parameter, default) = match parameter.get_value() {
+                ChumskyNode::DefaultParam(parameter, default) => (parameter.as_ref(), Some(default)),
+                _ => (parameter, None),
+            };
+
+            match parameter.get_value() {
+                ChumskyNode::VarAndType(ident, t) => Some(parse_ident(ident).and_then(|ident| {
+                    Ok(((ident, parse_type(t, parent, &mut self.ast, &self.generics)?), default.map(|default| parse_default(default)).transpose()?))
+                })),
+                _ => None,
+            }
+        }).collect::<Result<Vec<_>, _>>()?.into_iter().unzip();
+
+        let return_type = match return_type {
+            Some(ty) => parse_type(ty, parent, &mut self.ast, &self.generics)?,
+            None => self.ast.get_arp_file_in_parent(parent).map(|file| file.type_collection.get_void()).unwrap_or_default(),
+        };
+
+        Ok(MethodInfo {
+            name,
+            args,
+            return_type,
+            generics: vec![],
+            defaults,
+            is_params: false,
+            definition: None,
+        })
+    }
+
+    /// Only single inheritance is supported, the base may be declared later or imported.
+    fn fold_base(&mut self, self_type: &TypeId, inherits_from: &[Spanned<ChumskyNode>], parent: WId) -> Result<(), ChumskyFoldError> {
+        let base = match inherits_from {
+            [] => return Ok(()),
+            [base] => parse_type(base, parent, &mut self.ast, &self.generics)?,
+            [_, rest @ ..] => return Err(ChumskyFoldError::Unimplemented(rest[0].clone())),
+        };
+
+        if let Some(file) = self.ast.get_mut_arp_file_in_parent(parent) {
+            file.type_collection.set_base(self_type, base);
+        }
+
+        Ok(())
+    }
+
+    /// Generic classes are allocated before their fields are folded, so `Box<T>` inside of them
+    /// resolves to the definition itself.
+    fn allocate_generic_type(&mut self, name: Identifier, generic_params: &[GenericParamDecl], inherits_from: &[Spanned<ChumskyNode>], fields: &[Spanned<ChumskyNode>], parent: WId) -> Result<TypeId, ChumskyFoldError> {
+        let self_type = match self.ast.get_mut_arp_file_in_parent(parent) {
+            Some(file) => match file.type_collection.get_or_allocate(&name) {
+                TypeId::Weak(id) => file.type_collection.try_allocate(id, vec![]),
+                _ => TypeId::None,
+            },
+            None => TypeId::None,
+        };
+
+        let generics = self.declare_generics(name.as_ref(), GenericOwner::Type, generic_params, parent)?;
+
+        if let Some(file) = self.ast.get_mut_arp_file_in_parent(parent) {
+            file.type_collection.set_kind(&self_type, TypeKind::Generic(generics));
+        }
+
+        let parsed_fields = self.fold_fields(fields, parent)?;
+        self.fold_base(&self_type, inherits_from, parent)?;
+        self.leave_generics(generic_params.len());
+
+        if let Some(file) = self.ast.get_mut_arp_file_in_parent(parent) {
+            file.type_collection.set_fields(&self_type, parsed_fields);
+            file.type_collection.instantiate_pending(&self_type);
+        }
+
+        Ok(self_type)
+    }
+
+    fn allocate_type(&mut self, structure_type: &Spanned<ChumskyNode>, fields: Vec<(Identifier, TypeId)>, parent: WId) -> Result<TypeId, ChumskyFoldError> {
+        Ok(match parse_type(structure_type, parent, &mut self.ast, &self.generics) {
+            Ok(TypeId::Weak(id)) => match self.ast.get_mut_arp_file_in_parent(parent) {
+                Some(file) => {
+                    file.type_collection.try_allocate(id, fields)
+                },
+                None => TypeId::None,
+            },
+            _ => TypeId::None,
+        })
+    }
+}
@@ -1,8 +1,8 @@
 use arp_parser::types::ChumskyNode;
 use arp_types::Spanned;
-use crate::{ast::index::StrongIndex, types::{ast_node_value::WId, expression::{Expression, Literal}, implementation::Implementation, simple::Identifier, type_collection::TypeId}};
+use crate::{ast::index::StrongIndex, types::{ast_node_value::WId, block_scope::{BlockScope, BlockScopeKind}, expression::{Expression, Literal, MatchArm}, function::{Function, FunctionKind}, implementation::Implementation, simple::Identifier, statement::Statement, type_collection::TypeId}};
 
-use super::{utils::parse_ident, ChumskyFoldError, ChumskyNodeVisitor, Folder};
+use super::{utils::{parse_ident, parse_pattern, parse_type, parse_type_as_ident}, ChumskyFoldError, ChumskyNodeVisitor, Folder};
 
 
 impl Folder<Spanned<ChumskyNode>, Expression> for ChumskyNodeVisitor {
@@ -42,7 +42,7 @@ impl Folder<Spanned<ChumskyNode>, Expression> for ChumskyNodeVisitor {
                 Ok(self.ast.place_spanned(next, bin_expr, item.get_span()))
             },
             ChumskyNode::Identifier(ident) => {
-                Ok(self.ast.push(Expression::Variable(ident.clone().into()), parent))
+                Ok(self.ast.push_spanned(Expression::Variable(ident.clone().into()), item.get_span(), parent))
             },
 
             ChumskyNode::GetExpr(lhs, rhs) => {
@@ -51,15 +51,17 @@ impl Folder<Spanned<ChumskyNode>, Expression> for ChumskyNodeVisitor {
 
                 match rhs.get_value() {
                     ChumskyNode::CallExpr(ident, args) => {
-                        let args = args.iter().map(|item| self.fold(item, next)).collect::<Result<Vec<_>, _>>()?;
+                        let (args, names) = self.fold_arguments(args, next)?;
                         let method = match ident.get_value() {
                             ChumskyNode::Identifier(ident) => Ok(Identifier::from(ident.as_ref())),
                             _ => Err(ChumskyFoldError::UnexpectedChumsky(*rhs.clone(), "call or identifier".into())),
                         }?;
 
-                        Ok(self.ast.place_spanned(next, Expression::Call { on: Some(on), method, args }, item.get_span()))
+                        Ok(self.ast.place_spanned(next, Expression::Call { on: Some(on), method, args, names, spilled: vec![] }, item.get_span()))
                     },
                     ChumskyNode::Identifier(ident) => Ok(self.ast.place_spanned(next, Expression::GetField { on, ident: ident.clone().into() }, item.get_span())),
+                    // Tuple fields are named by position, `pair.0`.
+                    ChumskyNode::LiteralInteger(position) => Ok(self.ast.place_spanned(next, Expression::GetField { on, ident: position.to_string().into() }, item.get_span())),
                     _ => Err(ChumskyFoldError::UnexpectedChumsky(*rhs.clone(), "call or identifier".into()))
                 }
             },
@@ -67,36 +69,123 @@ impl Folder<Spanned<ChumskyNode>, Expression> for ChumskyNodeVisitor {
             ChumskyNode::ConstructExpr(ident, args) => {
                 let next = self.ast.next_index(parent);
 
-                let args = args.iter().map(|(ident, expr)| {
-                    let ident = match ident {
-                        Some(id) => parse_ident(id),
-                        None => parse_ident(expr),
+                let mut named = vec![];
+                let mut positional = vec![];
+
+                for (ident, expr) in args {
+                    // `Point { x, y }` is short for `Point { x: x, y: y }`, other values without a name are positional.
+                    let ident = match (ident, expr.get_value()) {
+                        (Some(id), _) => Some(parse_ident(id)?),
+                        (None, ChumskyNode::Identifier(_)) => Some(parse_ident(expr)?),
+                        (None, _) => None,
                     };
 
-                    match self.fold(expr, next) {
-                        Ok(expr_id) => Ok((ident?, expr_id)),
-                        Err(err) => Err(err),
+                    let expr = self.fold(expr, next)?;
+
+                    match ident {
+                        Some(ident) => named.push((ident, expr)),
+                        None => positional.push(expr),
                     }
-                }).collect::<Result<Vec<_>, _>>()?;
+                }
 
-                
-                let ident = match ident.get_value() {
-                    ChumskyNode::Type(long_type) => {
-                        let type_name = long_type.iter().map(|node| match node.get_value() {
-                            ChumskyNode::Identifier(s) => Ok(s.clone()),
-                            _ => Err(ChumskyFoldError::CantUnfoldTypeName(long_type.clone())),
-                        }).collect::<Result<Vec<_>, _>>()?.join(".");
-                        
-                        Ok(type_name)
-                    },
-                    _ => Err(ChumskyFoldError::UnexpectedChumsky(*ident.clone(), "type".into()))
+                let ty = parse_type(ident, next, &mut self.ast, &self.generics)?;
+                let ident = match self.ast.get_arp_file_in_parent(next).and_then(|file| file.type_collection.get_source_name(&ty)) {
+                    Some(name) => Identifier::from(name),
+                    None => parse_type_as_ident(ident)?,
                 };
-                
-                Ok(self.ast.place_spanned(next, Expression::Construct { ident: ident?.into(), args }, item.get_span()))
+
+                Ok(self.ast.place_spanned(next, Expression::Construct { ident, args: named, positional }, item.get_span()))
+            },
+            ChumskyNode::MatchExpr(on, arms) => {
+                let next = self.ast.next_index(parent);
+                let on = self.fold(on.as_ref(), next)?;
+
+                let arms = arms.iter().map(|(pattern, expr)| {
+                    let pattern = parse_pattern(pattern, next, &mut self.ast, &self.generics)?;
+
+                    Ok(MatchArm { pattern, expr: self.fold(expr, next)? })
+                }).collect::<Result<Vec<_>, ChumskyFoldError>>()?;
+
+                Ok(self.ast.place_spanned(next, Expression::Match { on, arms }, item.get_span()))
+            },
+            ChumskyNode::TryExpr(expr) => {
+                let next = self.ast.next_index(parent);
+                let expr = self.fold(expr.as_ref(), next)?;
+
+                Ok(self.ast.place_spanned(next, Expression::Try { expr }, item.get_span()))
+            },
+            ChumskyNode::CastExpr(expr, ty) => {
+                let next = self.ast.next_index(parent);
+                let expr = self.fold(expr.as_ref(), next)?;
+                let ty = parse_type(ty, next, &mut self.ast, &self.generics)?;
+
+                Ok(self.ast.place_spanned(next, Expression::Cast { expr, ty }, item.get_span()))
+            },
+            ChumskyNode::NewExpr(ty, args) => {
+                let next = self.ast.next_index(parent);
+                let (args, names) = self.fold_arguments(args, next)?;
+                let ty = parse_type(ty, next, &mut self.ast, &self.generics)?;
+
+                Ok(self.ast.place_spanned(next, Expression::New { ty, args, names, spilled: vec![] }, item.get_span()))
+            },
+            ChumskyNode::ArrayExpr(elements) => {
+                let next = self.ast.next_index(parent);
+                let elements = elements.iter().map(|item| self.fold(item, next)).collect::<Result<Vec<_>, _>>()?;
+
+                Ok(self.ast.place_spanned(next, Expression::Array(elements), item.get_span()))
+            },
+            ChumskyNode::TupleExpr(elements) => {
+                let next = self.ast.next_index(parent);
+                let elements = elements.iter().map(|item| self.fold(item, next)).collect::<Result<Vec<_>, _>>()?;
+
+                Ok(self.ast.place_spanned(next, Expression::Tuple(elements), item.get_span()))
+            },
+            ChumskyNode::IndexExpr(on, index) => {
+                let next = self.ast.next_index(parent);
+                let on = self.fold(on.as_ref(), next)?;
+                let index = self.fold(index.as_ref(), next)?;
+
+                Ok(self.ast.place_spanned(next, Expression::Index { on, index }, item.get_span()))
+            },
+            // `apply(x)` calls a function value or a top level function.
+            ChumskyNode::CallExpr(ident, args) => {
+                let next = self.ast.next_index(parent);
+                let method = parse_ident(ident)?;
+                let (args, names) = self.fold_arguments(args, next)?;
+
+                Ok(self.ast.place_spanned(next, Expression::Call { on: None, method, args, names, spilled: vec![] }, item.get_span()))
+            },
+            ChumskyNode::LambdaExpr(params, body) => {
+                let next = self.ast.next_index(parent);
+                let func = self.ast.next_index(next);
+
+                let parameters = params.iter().map(|param| match param.get_value() {
+                    ChumskyNode::VarAndType(ident, ty) => Ok((parse_ident(ident)?, parse_type(ty, next, &mut self.ast, &self.generics)?)),
+                    _ => Err(ChumskyFoldError::UnexpectedChumsky(param.clone(), "parameter".into())),
+                }).collect::<Result<Vec<_>, _>>()?;
+
+                // The body is returned right away, its type becomes the return type once it's resolved.
+                let block = self.ast.next_index(func);
+                let stmt = self.ast.next_index(block);
+                let expr = self.fold(body.as_ref(), stmt)?;
+                let stmt = self.ast.place(stmt, Statement::Return(expr));
+                let block = self.ast.place(block, BlockScope::new(BlockScopeKind::Function(func), vec![stmt], None));
+
+                // Closure classes are instantiated for every lambda, so the body is always an instance method.
+                let func = self.ast.place_spanned(func, Function {
+                    kind: FunctionKind::Method { is_this_mutable: false },
+                    name: "Invoke".into(),
+                    parameters,
+                    defaults: vec![],
+                    return_type: TypeId::None,
+                    block,
+                    generics: vec![],
+                    registers: vec![],
+                    locals: vec![],
+                }, item.get_span());
+
+                Ok(self.ast.place_spanned(next, Expression::Lambda { func, captures: vec![] }, item.get_span()))
             },
-            ChumskyNode::CallExpr(_, _) => {
-                unimplemented!("Call expression without target is not yet supported");
-            }
             ChumskyNode::This => {
                 if let Some(im) = self.ast.get_parent_of_kind::<Implementation, _>(parent) {
                     Ok(self.ast.push_spanned(Expression::This(self.ast.get(&im).impl_type.clone()), item.get_span(), parent))
@@ -104,7 +193,44 @@ impl Folder<Spanned<ChumskyNode>, Expression> for ChumskyNodeVisitor {
                     Ok(self.ast.push_spanned(Expression::This(TypeId::None), item.get_span(), parent))
                 }
             }
+            ChumskyNode::Base => {
+                if let Some(im) = self.ast.get_parent_of_kind::<Implementation, _>(parent) {
+                    Ok(self.ast.push_spanned(Expression::Base(self.ast.get(&im).impl_type.clone()), item.get_span(), parent))
+                } else {
+                    Ok(self.ast.push_spanned(Expression::Base(TypeId::None), item.get_span(), parent))
+                }
+            }
             _ => Err(ChumskyFoldError::UnexpectedChumsky(item.clone(), "expression".into()))
         }
     }
-}
\ No newline at end of file
+}
+
+/// Folded arguments of a call and the names they were passed with.
+type FoldedArguments = (Vec<StrongIndex<Expression>>, Vec<Option<Identifier>>);
+
+impl ChumskyNodeVisitor {
+    /// Arguments of a call and their names, which are left empty if every argument is positional.
+    fn fold_arguments(&mut self, args: &[Spanned<ChumskyNode>], parent: WId) -> Result<FoldedArguments, ChumskyFoldError> {
+        let mut folded = vec![];
+        let mut names = vec![];
+
+        for arg in args {
+            match arg.get_value() {
+                ChumskyNode::NamedArgument(name, value) => {
+                    folded.push(self.fold(value.as_ref(), parent)?);
+                    names.push(Some(parse_ident(name)?));
+                },
+                _ => {
+                    folded.push(self.fold(arg, parent)?);
+                    names.push(None);
+                },
+            }
+        }
+
+        if names.iter().all(Option::is_none) {
+            names.clear();
+        }
+
+        Ok((folded, names))
+    }
+}
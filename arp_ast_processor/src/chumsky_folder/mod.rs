@@ -7,7 +7,7 @@ pub mod utils;
 use arp_parser::types::ChumskyNode;
 use arp_types::Spanned;
 use thiserror::Error;
-use crate::{ast::{index::StrongIndex, traits::AstNodeKind}, types::{ast_node_value::{Ast, AstNodeValue, WId}, file::ArpFile, statement::Statement}};
+use crate::{ast::{index::StrongIndex, traits::AstNodeKind}, types::{ast_node_value::{Ast, AstNodeValue, WId}, file::ArpFile, simple::Identifier, statement::Statement}};
 
 
 #[derive(Debug, Error)]
@@ -35,7 +35,10 @@ pub trait Folder<T, Res : AstNodeKind<AstNodeValue>> {
 
 #[derive(Default)]
 pub struct ChumskyNodeVisitor {
-    pub(crate) ast: Ast
+    pub(crate) ast: Ast,
+
+    /// Type parameters in scope, mapped to their names in the type collection.
+    pub(crate) generics: Vec<(Identifier, String)>,
 }
 
 impl ChumskyNodeVisitor {
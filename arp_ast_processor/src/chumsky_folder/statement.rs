@@ -3,7 +3,7 @@ use arp_types::Spanned;
 
 use crate::{ast::index::StrongIndex, types::{ast_node_value::WId, simple::Identifier, statement::{IfKind, Statement}, type_collection::TypeId}};
 
-use super::{utils::{parse_ident, parse_type}, ChumskyFoldError, ChumskyNodeVisitor, Folder};
+use super::{utils::{parse_ident, parse_tuple_pattern, parse_type}, ChumskyFoldError, ChumskyNodeVisitor, Folder};
 
 impl Folder<Spanned<ChumskyNode>, Statement> for ChumskyNodeVisitor {
     type Error = ChumskyFoldError;
@@ -19,6 +19,7 @@ impl Folder<Spanned<ChumskyNode>, Statement> for ChumskyNodeVisitor {
         let stmt = match item.get_value() {
             ChumskyNode::ExpressionStmt(expr) => Ok(Statement::Expression(self.fold(expr.as_ref(), next)?)),
             ChumskyNode::ReturnStmt(expr) => Ok(Statement::Return(self.fold(expr.as_ref(), next)?)),
+            ChumskyNode::Break => Ok(Statement::Break),
 
             ChumskyNode::AssignmentStmt(lhs, rhs) => {
                 let expr = self.fold(rhs.as_ref(), next)?;
@@ -34,7 +35,13 @@ impl Folder<Spanned<ChumskyNode>, Statement> for ChumskyNodeVisitor {
                         }
                     },
                     ChumskyNode::Identifier(ident) => Ok(Statement::Assignment { on: None, field: Identifier::from(ident.as_ref()), expr }),
-                    _ => Err(ChumskyFoldError::UnexpectedChumsky(item.clone(), "identifier or field getter".into()))
+                    ChumskyNode::IndexExpr(on, index) => {
+                        let on = self.fold(on.as_ref(), next)?;
+                        let index = self.fold(index.as_ref(), next)?;
+
+                        Ok(Statement::ElementAssignment { on, index, expr })
+                    },
+                    _ => Err(ChumskyFoldError::UnexpectedChumsky(item.clone(), "identifier, field getter or index".into()))
                 }
             }
 
@@ -70,11 +77,12 @@ impl Folder<Spanned<ChumskyNode>, Statement> for ChumskyNodeVisitor {
             ChumskyNode::VariableDecl(is_mutable, ident, declared_type, expr) => {
                 let expr = self.fold(expr.as_ref(), next)?;
 
-                let ident = parse_ident(ident)?;
+                let ty = declared_type.as_ref().map(|ty| parse_type(ty, parent, &mut self.ast, &self.generics)).unwrap_or(Ok(TypeId::None))?;    
 
-                let ty = declared_type.as_ref().map(|ty| parse_type(ty, parent, &mut self.ast)).unwrap_or(Ok(TypeId::None))?;    
-            
-                Ok(Statement::LocalVariableDeclaration { is_mutable: *is_mutable, ident, ty, expr })
+                match ident.get_value() {
+                    ChumskyNode::TuplePattern(_) => Ok(Statement::Destructure { is_mutable: *is_mutable, bindings: parse_tuple_pattern(ident)?, ty, expr }),
+                    _ => Ok(Statement::LocalVariableDeclaration { is_mutable: *is_mutable, ident: parse_ident(ident)?, ty, expr })
+                }
             },
 
             ChumskyNode::BlockStmt( .. ) => {
@@ -1,7 +1,7 @@
-use arp_parser::types::ChumskyNode;
+use arp_parser::types::{ChumskyNode, UnaryOp};
 use arp_types::Spanned;
 
-use crate::types::{ast_node_value::{Ast, WId}, simple::Identifier, type_collection::TypeId};
+use crate::types::{ast_node_value::{Ast, WId}, expression::{Literal, Pattern, MAX_TUPLE_LEN}, simple::Identifier, type_collection::TypeId};
 
 use super::ChumskyFoldError;
 
@@ -15,35 +15,149 @@ pub fn parse_ident(node: &Spanned<ChumskyNode>)  -> Result<Identifier, ChumskyFo
 
 pub fn parse_type_as_ident(node: &Spanned<ChumskyNode>)  -> Result<Identifier, ChumskyFoldError> {
     match node.get_value() {
-        ChumskyNode::Type(long_type) => {
-            let type_name = long_type.iter().map(|node| match node.get_value() {
-                ChumskyNode::Identifier(s) => Ok(s.clone()),
-                _ => Err(ChumskyFoldError::CantUnfoldTypeName(long_type.clone())),
-            }).collect::<Result<Vec<_>, _>>()?.join(".");
-            Ok(type_name.into())
-        },
+        ChumskyNode::Type(long_type) => Ok(unfold_type_name(long_type, &[])?.into()),
         _ => Err(ChumskyFoldError::UnexpectedChumsky(node.clone(), "type".into()))
     }
 }
 
-pub fn parse_type(node: &Spanned<ChumskyNode>, parent: WId, ast: &mut Ast)  -> Result<TypeId, ChumskyFoldError> {
+/// Joins path segments with `.`, generic arguments are written as `Result<int32, string>`.
+/// Type parameters in scope are replaced by their names in the type collection.
+fn unfold_type_name(long_type: &[Spanned<ChumskyNode>], generics: &[(Identifier, String)]) -> Result<String, ChumskyFoldError> {
+    if let [segment] = long_type {
+        if let ChumskyNode::Identifier(name) = segment.get_value() {
+            if let Some((_, full_name)) = generics.iter().rev().find(|(ident, _)| ident.as_ref() == name.as_ref()) {
+                return Ok(full_name.clone());
+            }
+        }
+    }
+
+    Ok(long_type.iter().map(|node| match node.get_value() {
+        ChumskyNode::Identifier(s) => Ok(s.to_string()),
+        ChumskyNode::GenericIdentifier(ident, args) => {
+            let args = args.iter().map(|arg| unfold_type(arg, generics).map_err(|_| ChumskyFoldError::CantUnfoldTypeName(long_type.to_vec())))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(format!("{}<{}>", parse_ident(ident)?.as_ref(), args.join(", ")))
+        },
+        _ => Err(ChumskyFoldError::CantUnfoldTypeName(long_type.to_vec())),
+    }).collect::<Result<Vec<_>, _>>()?.join("."))
+}
+
+/// Name of a type node, array types are written as `[int32]`, tuple types as `(int32, string)`
+/// and function types as `fn(int32) -> bool`.
+fn unfold_type(node: &Spanned<ChumskyNode>, generics: &[(Identifier, String)]) -> Result<String, ChumskyFoldError> {
     match node.get_value() {
-        ChumskyNode::Type(long_type) => {
-            let type_name = long_type.iter().map(|node| match node.get_value() {
-                ChumskyNode::Identifier(s) => Ok(s.clone()),
-                _ => Err(ChumskyFoldError::CantUnfoldTypeName(long_type.clone())),
-            }).collect::<Result<Vec<_>, _>>()?.join(".");
-            
-            match ast.get_mut_arp_file_in_parent(parent) {
-                Some(arp_file) => {
-                    Ok(arp_file.type_collection.get_or_allocate(&type_name))
-                },
-                // this is only for testing purposes. For other scenarios arp_file guaranteed to exist
-                None => {
-                    Ok(TypeId::None)
-                }, 
+        ChumskyNode::Type(long_type) => unfold_type_name(long_type, generics),
+        ChumskyNode::ArrayType(element) => Ok(format!("[{}]", unfold_type(element, generics)?)),
+        // `System.ValueTuple` nests anything past the seventh element, which isn't supported yet.
+        ChumskyNode::TupleType(elements) if elements.len() > MAX_TUPLE_LEN => Err(ChumskyFoldError::Unimplemented(node.clone())),
+        ChumskyNode::TupleType(elements) => {
+            let elements = elements.iter().map(|element| unfold_type(element, generics)).collect::<Result<Vec<_>, _>>()?;
+            Ok(format!("({})", elements.join(", ")))
+        },
+        ChumskyNode::FunctionType(params, return_type) => {
+            let params = params.iter().map(|param| unfold_type(param, generics)).collect::<Result<Vec<_>, _>>()?;
+
+            match return_type {
+                Some(return_type) => Ok(format!("fn({}) -> {}", params.join(", "), unfold_type(return_type, generics)?)),
+                None => Ok(format!("fn({})", params.join(", "))),
             }
         },
         _ => Err(ChumskyFoldError::UnexpectedChumsky(node.clone(), "type".into()))
     }
-}
\ No newline at end of file
+}
+
+pub fn parse_type(node: &Spanned<ChumskyNode>, parent: WId, ast: &mut Ast, generics: &[(Identifier, String)])  -> Result<TypeId, ChumskyFoldError> {
+    let type_name = unfold_type(node, generics)?;
+
+    match ast.get_mut_arp_file_in_parent(parent) {
+        Some(arp_file) => {
+            Ok(arp_file.type_collection.get_or_allocate(&type_name))
+        },
+        // this is only for testing purposes. For other scenarios arp_file guaranteed to exist
+        None => {
+            Ok(TypeId::None)
+        }, 
+    }
+}
+
+/// Names bound by `let (a, b) = pair;`, `_` skips an element.
+pub fn parse_tuple_pattern(node: &Spanned<ChumskyNode>) -> Result<Vec<Identifier>, ChumskyFoldError> {
+    match node.get_value() {
+        ChumskyNode::TuplePattern(names) => names.iter().map(parse_ident).collect(),
+        _ => Err(ChumskyFoldError::UnexpectedChumsky(node.clone(), "tuple pattern".into()))
+    }
+}
+
+/// Default value of a parameter, a literal which may be negated, `-1`.
+pub fn parse_default(node: &Spanned<ChumskyNode>) -> Result<Literal, ChumskyFoldError> {
+    match node.get_value() {
+        ChumskyNode::LiteralInteger(value) => Ok(Literal::Integer(*value)),
+        ChumskyNode::LiteralFloat(value) => Ok(Literal::Float(value.0)),
+        ChumskyNode::LiteralString(value) => Ok(Literal::String(value.clone())),
+        ChumskyNode::LiteralBool(value) => Ok(Literal::Bool(*value)),
+        ChumskyNode::UnaryExpr(UnaryOp::Negate, literal) => match parse_default(literal)? {
+            Literal::Integer(value) => Ok(Literal::Integer(-value)),
+            Literal::Float(value) => Ok(Literal::Float(-value)),
+            _ => Err(ChumskyFoldError::UnexpectedChumsky(node.clone(), "number".into())),
+        },
+        _ => Err(ChumskyFoldError::UnexpectedChumsky(node.clone(), "literal".into())),
+    }
+}
+
+pub fn parse_pattern(node: &Spanned<ChumskyNode>, parent: WId, ast: &mut Ast, generics: &[(Identifier, String)])  -> Result<Pattern, ChumskyFoldError> {
+    match node.get_value() {
+        ChumskyNode::Pattern(ty, None) if matches!(ty.get_value(), ChumskyNode::Type(path) if path.len() == 1) => {
+            match parse_type_as_ident(ty)? {
+                ident if ident.as_ref() == "_" => Ok(Pattern::Wildcard),
+                ident => Ok(Pattern::Binding(ident)),
+            }
+        },
+        ChumskyNode::Pattern(ty, fields) => {
+            let fields = fields.iter().flatten().map(|(field, binding)| {
+                let binding = parse_ident(binding)?;
+                let field = field.as_ref().map(parse_ident).unwrap_or_else(|| Ok(binding.clone()))?;
+
+                Ok((field, binding))
+            }).collect::<Result<Vec<_>, _>>()?;
+
+            Ok(Pattern::Variant { ty: parse_type(ty, parent, ast, generics)?, fields })
+        },
+        _ => Err(ChumskyFoldError::UnexpectedChumsky(node.clone(), "pattern".into()))
+    }
+}
+
+/// Type parameter declared as `T: IComparable + IEquatable`.
+pub type GenericParamDecl = (Identifier, Vec<Spanned<ChumskyNode>>);
+
+/// Splits a declared name like `Namespace.Box<T: Show>` or `identity<T>` into the plain name and its type parameters.
+pub fn parse_generic_decl(node: &Spanned<ChumskyNode>) -> Result<(Identifier, Vec<GenericParamDecl>), ChumskyFoldError> {
+    let (prefix, last) = match node.get_value() {
+        ChumskyNode::Type(path) => match path.split_last() {
+            Some((last, prefix)) => (prefix, last),
+            None => return Err(ChumskyFoldError::UnexpectedChumsky(node.clone(), "type".into())),
+        },
+        _ => (&[][..], node),
+    };
+
+    let (ident, params) = match last.get_value() {
+        ChumskyNode::Identifier(_) => (parse_ident(last)?, vec![]),
+        ChumskyNode::GenericIdentifier(ident, params) => {
+            let params = params.iter().map(|param| match param.get_value() {
+                ChumskyNode::GenericParam(ident, bounds) => Ok((parse_ident(ident)?, bounds.clone())),
+                _ => Err(ChumskyFoldError::UnexpectedChumsky(param.clone(), "type parameter".into())),
+            }).collect::<Result<Vec<_>, _>>()?;
+
+            (parse_ident(ident)?, params)
+        },
+        _ => return Err(ChumskyFoldError::UnexpectedChumsky(last.clone(), "identifier".into())),
+    };
+
+    let name = prefix.iter()
+        .map(|segment| parse_ident(segment).map(|ident| ident.0.to_string()))
+        .chain(std::iter::once(Ok(ident.0.to_string())))
+        .collect::<Result<Vec<_>, _>>()?
+        .join(".");
+
+    Ok((name.into(), params))
+}
@@ -7,13 +7,15 @@ pub mod validations;
 pub mod type_resolver;
 mod post;
 pub mod utils;
+pub mod scope;
+pub mod cfg;
 
 use arp_parser::types::ChumskyNode;
 use arp_types::{sources::Source, Spanned};
 use chumsky_folder::ChumskyNodeVisitor;
 use errors::ProcessingError;
 use post::post_process;
-use type_resolver::{imports_graph::resolve_imports, resolve_types_loop};
+use type_resolver::{imports_graph::resolve_imports, resolve_types};
 use types::ast_node_value::Ast;
 
 
@@ -22,7 +24,7 @@ pub fn process_file_ast(root: Box<Spanned<ChumskyNode>>) -> Result<Ast, Processi
     visitor.fold_file(&root);
     let ast = visitor.consume();
     
-    let ast = resolve_types_loop(ast)?;
+    let ast = resolve_types(ast)?;
     validations::validate(&ast)?;
     Ok(ast)
 }
@@ -40,7 +42,7 @@ pub fn build_multiple_sources(sources: &[Source]) -> Result<Ast, ProcessingError
 
     let ast = visitor.consume();
     let ast = resolve_imports(ast)?;
-    let ast = resolve_types_loop(ast)?;
+    let ast = resolve_types(ast)?;
     let ast = post_process(ast)?;
 
     validations::validate(&ast)?;
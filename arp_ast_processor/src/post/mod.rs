@@ -1,30 +1,343 @@
-use crate::{errors::ProcessingError, types::{ast_node_value::{Ast, Id}, function::Function, statement::Statement}};
+use crate::{errors::ProcessingError, scope::Binding, type_resolver::TypeResolverError, types::{ast_node_value::{Ast, Id, WId}, block_scope::BlockScope, expression::{Expression, OperatorOverload}, function::Function, simple::Identifier, statement::{IfKind, Statement}, type_collection::{BoundArgument, MethodInfo, Overload, TypeId}}};
 
 pub fn post_process(mut ast: Ast) -> Result<Ast, ProcessingError> {
+    ast = lower_calls(ast)?;
+    ast = lower_constructs(ast);
 
     let functions = ast.get_children_of_kind::<Function, _>(ast.get_root_index());
     for func in functions {
         ast = build_registers(ast, func)?;
     }
 
+    // Outer lambdas first, a nested lambda may capture what its parent captured.
+    let lambdas = ast.get_children_of_kind::<Expression, _>(ast.get_root_index())
+        .into_iter()
+        .filter(|expr| matches!(ast.get(expr), Expression::Lambda { .. }))
+        .collect::<Vec<_>>();
+
+    for lambda in lambdas {
+        let found = find_captures(&ast, &lambda)?;
+
+        ast.mutate_value(&lambda, |expr| {
+            if let Expression::Lambda { captures, .. } = expr {
+                *captures = found.clone();
+            }
+        });
+    }
 
     Ok(ast)
 }
 
+/// Rewrites calls to pass every parameter by position: named arguments are put in parameter order, defaults fill in
+/// the parameters left out and the trailing arguments of a `params` method are collected into its array. Arguments are
+/// still evaluated in source order, the ones with side effects are spilled when they end up out of it. Calls which don't
+/// bind are left as they are, the validation reports them.
+fn lower_calls(mut ast: Ast) -> Result<Ast, ProcessingError> {
+    let calls = ast.get_children_of_kind::<Expression, _>(ast.get_root_index());
+    let mut is_lowered = false;
 
-fn build_registers(mut ast: Ast, index: Id<Function>) -> Result<Ast, ProcessingError> {
-    let mut registers = vec![];
+    for call in calls {
+        let Some((params, bound)) = bind_call(&ast, &call)? else { continue };
+        let (Expression::Call { args, names, .. } | Expression::New { args, names, .. }) = ast.get(&call).clone() else { continue };
+
+        if names.is_empty() && bound.iter().enumerate().all(|(position, argument)| *argument == BoundArgument::Argument(position)) {
+            continue;
+        }
+
+        // `f(b: g(), a: h())` still calls `g` before `h`.
+        let order = bound.iter().flat_map(|argument| match argument {
+            BoundArgument::Argument(position) => vec![*position],
+            BoundArgument::Params(positions) => positions.clone(),
+            BoundArgument::Default(_) => vec![],
+        }).collect::<Vec<_>>();
+        let spilled = match order.is_sorted() {
+            true => vec![],
+            false => args.iter().copied().filter(|arg| has_side_effects(&ast, arg)).collect::<Vec<_>>(),
+        };
+
+        let mut lowered = vec![];
+        for (param, argument) in params.into_iter().zip(bound) {
+            lowered.push(match argument {
+                BoundArgument::Argument(position) => args[position],
+                BoundArgument::Default(literal) => ast.push(Expression::Literal(literal), call),
+                BoundArgument::Params(positions) => {
+                    let elements = positions.iter().map(|position| args[*position]).collect::<Vec<_>>();
+                    let array = ast.push(Expression::ParamsArray { ty: param, elements: elements.clone() }, call);
+
+                    for element in elements {
+                        ast.mutate_node(&element, |node| node.__unsafe_set_parent(array));
+                    }
+
+                    array
+                },
+            });
+        }
+
+        ast.mutate_value(&call, |expr| {
+            if let Expression::Call { args, names, spilled: spills, .. } | Expression::New { args, names, spilled: spills, .. } = expr {
+                *args = lowered.clone();
+                *spills = spilled.clone();
+                names.clear();
+            }
+        });
+        is_lowered = true;
+    }
+
+    // The filled in defaults and arrays need their types as well.
+    if is_lowered {
+        ast.cache_types();
+    }
+
+    Ok(ast)
+}
+
+/// Whether evaluating `expr` may be observed by the other arguments of a call. Reading a local or a literal can't be.
+fn has_side_effects(ast: &Ast, expr: &Id<Expression>) -> bool {
+    !matches!(ast.get(expr), Expression::Literal(_) | Expression::Variable(_) | Expression::This(_) | Expression::Type(_) | Expression::Lambda { .. })
+}
+
+/// Names the positional values of `MyClass { 5 }` after the fields they initialize, the fields not given by name
+/// in declaration order. Constructions with more values than fields are left as they are, the validation reports them.
+fn lower_constructs(mut ast: Ast) -> Ast {
+    let constructs = ast.get_children_of_kind::<Expression, _>(ast.get_root_index());
+
+    for construct in constructs {
+        let Expression::Construct { ident, args, positional } = ast.get(&construct) else { continue };
+        let Some(file) = ast.get_arp_file_in_parent(construct.as_weak()) else { continue };
+
+        let named = args.iter().map(|(field, _)| field.clone()).collect::<Vec<_>>();
+        let fields = file.type_collection.unnamed_fields(&file.type_collection.resolve_name(ident), &named);
+
+        if positional.is_empty() || positional.len() > fields.len() {
+            continue;
+        }
+
+        let filled = fields.into_iter().zip(positional.clone()).collect::<Vec<_>>();
+
+        ast.mutate_value(&construct, |expr| {
+            if let Expression::Construct { args, positional, .. } = expr {
+                args.extend(filled.clone());
+                positional.clear();
+            }
+        });
+    }
+
+    ast
+}
+
+type BoundCall = (Vec<TypeId>, Vec<BoundArgument>);
+
+/// Parameter types of the method `call` resolves to, and where each of them gets its value from.
+fn bind_call(ast: &Ast, call: &Id<Expression>) -> Result<Option<BoundCall>, ProcessingError> {
+    let Some(file) = ast.get_arp_file_in_parent(call.as_weak()) else { return Ok(None) };
+    let collection = &file.type_collection;
+    let params = |mi: &MethodInfo| mi.args.iter().map(|(_, ty)| ty.clone()).collect::<Vec<_>>();
+    let arg_types = |args: &[Id<Expression>]| args.iter().map(|arg| ast.get_type(arg)).collect::<Result<Vec<_>, _>>();
+
+    let (overload, names, arg_types) = match ast.get(call) {
+        Expression::Call { on: None, method, args, names, .. } => {
+            let Some(mi) = ast.get_top_level_method(call, method) else { return Ok(None) };
+            return Ok(mi.bind_arguments(names, args.len(), false).ok().map(|bound| (params(&mi), bound)));
+        },
+        Expression::Call { on: Some(on), method, args, names, .. } => {
+            let arg_types = arg_types(args)?;
+            (collection.resolve_overload(&ast.get_type(on)?, method, names, &arg_types), names, arg_types)
+        },
+        Expression::New { args, names, .. } => {
+            let arg_types = arg_types(args)?;
+            (collection.resolve_constructor(&ast.get_type(call)?, names, &arg_types), names, arg_types)
+        },
+        _ => return Ok(None),
+    };
+
+    let Overload::Found(_, mi, _) = overload else { return Ok(None) };
+    Ok(collection.bind_call(mi, names, &arg_types).map(|bound| (params(mi), bound)))
+}
+
+/// Variables read by `lambda` which are declared outside of it. Nested lambdas count as well,
+/// their captures are copied from this closure.
+fn find_captures(ast: &Ast, lambda: &Id<Expression>) -> Result<Vec<(Identifier, TypeId)>, ProcessingError> {
+    let Expression::Lambda { func, .. } = ast.get(lambda) else { return Ok(vec![]) };
+    let mut captures: Vec<(Identifier, TypeId)> = vec![];
+
+    for expr in ast.get_children_of_kind::<Expression, _>(*func) {
+        let ident = match ast.get(&expr) {
+            Expression::Variable(ident) | Expression::Call { on: None, method: ident, .. } => ident,
+            _ => continue,
+        };
+
+        if captures.iter().any(|(captured, _)| captured == ident) || !is_captured_by(ast, &expr, ident, lambda) {
+            continue;
+        }
+
+        captures.push((ident.clone(), ast.get_variable_type(&expr, ident)?));
+    }
+
+    Ok(captures)
+}
+
+/// Whether `ident` at `expr` is a variable declared outside of `lambda`, possibly reached through lambdas nested in it.
+/// Top level functions aren't bound anywhere, they are referenced directly.
+fn is_captured_by(ast: &Ast, expr: &Id<Expression>, ident: &Identifier, lambda: &Id<Expression>) -> bool {
+    let mut binding = ast.lookup_binding(expr.as_weak(), ident);
+
+    while let Some(Binding::Captured(capturing)) = binding {
+        if capturing == *lambda {
+            return true;
+        }
+
+        binding = ast.lookup_binding(capturing.as_weak(), ident);
+    }
 
-    for stmt in ast.get_children_of_kind::<Statement, _>(index).into_iter().map(|index| ast.get(&index)) {
-        if let Statement::LocalVariableDeclaration { ident, ty, .. }  = stmt {
-            registers.push((ident.clone(), ty.clone()));
+    false
+}
+
+/// Registers of a function being allocated. Locals live until the end of their block, hidden locals
+/// until the end of their statement. Registers which aren't live anymore are reused by later locals of the same type.
+#[derive(Default)]
+struct Registers {
+    registers: Vec<(Identifier, TypeId)>,
+    locals: Vec<(WId, Identifier, usize)>,
+    live: Vec<usize>,
+}
+
+impl Registers {
+    fn declare(&mut self, declaration: WId, ident: &Identifier, ty: TypeId) {
+        // Nested fields of one spilled value share its register.
+        if self.locals.iter().any(|(node, local, _)| *node == declaration && local == ident) {
+            return;
         }
+
+        let free = (0..self.registers.len()).find(|register| !self.live.contains(register) && self.registers[*register].1.is_same(&ty));
+
+        let register = free.unwrap_or_else(|| {
+            self.registers.push((ident.clone(), ty));
+            self.registers.len() - 1
+        });
+
+        self.live.push(register);
+        self.locals.push((declaration, ident.clone(), register));
+    }
+
+    /// Frees the registers declared since `mark`, the number of live registers back then.
+    fn release(&mut self, mark: usize) {
+        self.live.truncate(mark);
     }
-    
-    let stmt = ast.get_mut(&index);
-    stmt.registers.extend(registers);
+}
+
+fn build_registers(mut ast: Ast, index: Id<Function>) -> Result<Ast, ProcessingError> {
+    let mut registers = Registers::default();
+    allocate_block(&ast, index, &ast.get(&index).block, &mut registers)?;
+
+    let func = ast.get_mut(&index);
+    func.registers.extend(registers.registers);
+    func.locals.extend(registers.locals);
 
     Ok(ast)
-} 
+}
+
+fn allocate_block(ast: &Ast, func: Id<Function>, block: &Id<BlockScope>, registers: &mut Registers) -> Result<(), ProcessingError> {
+    let mark = registers.live.len();
+
+    for stmt in &ast.get(block).statements {
+        let statement_mark = registers.live.len();
+        allocate_hidden(ast, func, stmt, registers)?;
+        registers.release(statement_mark);
+
+        match ast.get(stmt) {
+            Statement::LocalVariableDeclaration { ident, ty, .. } => registers.declare(stmt.as_weak(), ident, ty.clone()),
+            Statement::Destructure { bindings, ty, .. } => {
+                let elements = ast.get_arp_file_in_parent(stmt.as_weak()).and_then(|file| file.type_collection.get_tuple_elements(ty)).unwrap_or_default();
+
+                for (binding, ty) in bindings.iter().zip(elements).filter(|(binding, _)| binding.as_ref() != "_") {
+                    registers.declare(stmt.as_weak(), binding, ty.clone());
+                }
+            },
+            _ => {},
+        }
+
+        for nested in nested_blocks(ast.get(stmt)) {
+            allocate_block(ast, func, &nested, registers)?;
+        }
+    }
+
+    registers.release(mark);
+    Ok(())
+}
 
+fn nested_blocks(stmt: &Statement) -> Vec<Id<BlockScope>> {
+    match stmt {
+        Statement::Block(block) | Statement::WhileStmt { block, .. } | Statement::ForStmt { block, .. } => vec![*block],
+        Statement::IfStmt(if_kinds) => if_kinds.iter().map(|if_kind| match if_kind {
+            IfKind::If(_, block) | IfKind::ElseIf(_, block) | IfKind::Else(block) => *block,
+        }).collect(),
+        _ => vec![],
+    }
+}
+
+/// Hidden locals of the expressions of `stmt`, along with match arm bindings.
+fn allocate_hidden(ast: &Ast, func: Id<Function>, stmt: &Id<Statement>, registers: &mut Registers) -> Result<(), ProcessingError> {
+    let expressions = ast.get_children_of_kind::<Expression, _>(*stmt)
+        .into_iter()
+        .filter(|expr| ast.get_parent_of_kind::<Statement, _>(*expr) == Some(*stmt) && ast.get_parent_of_kind::<Function, _>(*expr) == Some(func))
+        .collect::<Vec<_>>();
+
+    for receiver in receivers(ast, stmt, &expressions)? {
+        // Enums are plain integers on the stack, they are never accessed through an address.
+        let is_value_type = ast.get_arp_file_in_parent(receiver.as_weak()).is_some_and(|file| {
+            let ty = ast.get_type(&receiver).unwrap_or_default();
+            file.type_collection.is_value_type(&ty) && !file.type_collection.is_enum(&ty)
+        });
+
+        if let Some(spilled) = is_value_type.then(|| ast.address_root_to_spill(&receiver)).transpose()?.flatten() {
+            registers.declare(spilled.as_weak(), &Identifier::spilled(&spilled), ast.get_type(&spilled)?);
+        }
+    }
+
+    for expr in expressions {
+        if let Expression::Call { spilled, .. } | Expression::New { spilled, .. } = ast.get(&expr) {
+            for arg in spilled {
+                registers.declare(arg.as_weak(), &Identifier::temporary(arg), ast.get_type(arg)?);
+            }
+        }
+
+        if let Expression::Try { expr: operand } = ast.get(&expr) {
+            registers.declare(expr.as_weak(), &Identifier::temporary(&expr), ast.get_type(operand)?);
+        }
+
+        if let Expression::Match { on, arms } = ast.get(&expr) {
+            registers.declare(expr.as_weak(), &Identifier::temporary(&expr), ast.get_type(on)?);
 
+            for arm in arms {
+                for binding in arm.pattern.bindings() {
+                    let ty = ast.get_pattern_binding_type(&expr, &arm.pattern, binding)?.unwrap_or_default();
+                    registers.declare(arm.expr.as_weak(), binding, ty);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Expressions of a statement whose fields are read or written, or whose methods are called.
+fn receivers(ast: &Ast, stmt: &Id<Statement>, expressions: &[Id<Expression>]) -> Result<Vec<Id<Expression>>, TypeResolverError> {
+    let mut receivers = vec![];
+
+    for expr in expressions {
+        match ast.get(expr) {
+            Expression::GetField { on, .. } | Expression::Call { on: Some(on), .. } => receivers.push(*on),
+            // Only operators lowered to `lhs.add(rhs)` call a method on the left operand.
+            Expression::Binary { lhs, .. } => if let Some((OperatorOverload::Method(_), _)) = ast.get_operator_overload(expr)? {
+                receivers.push(*lhs);
+            },
+            _ => {},
+        }
+    }
+
+    if let Statement::Assignment { on: Some(on), .. } = ast.get(stmt) {
+        receivers.push(*on);
+    }
+
+    Ok(receivers)
+}
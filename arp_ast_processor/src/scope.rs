@@ -0,0 +1,129 @@
+use crate::{ast::index::WeakIndex, type_resolver::TypeResolverError, types::{ast_node_value::{Ast, Id, WId}, block_scope::BlockScope, expression::Expression, function::Function, simple::Identifier, statement::Statement, type_collection::TypeId}};
+
+/// Declaration a name refers to. Blocks, match arms, functions and lambdas form a tree of scopes,
+/// a name resolves to the nearest declaration before it, walking from the innermost scope outwards.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Binding {
+    /// `let` or destructuring `let` statement.
+    Local(Id<Statement>),
+    /// Pattern of the match arm with this body.
+    MatchArm(Id<Expression>),
+    /// Variable of a `for` loop.
+    ForLoop(Id<Statement>),
+    /// Parameter of the function at this position, not counting `this`.
+    Parameter(Id<Function>, usize),
+    /// Variable of an enclosing function, copied into the closure of this lambda.
+    Captured(Id<Expression>),
+}
+
+impl Binding {
+    /// Node declaring the binding. Locals are keyed by it together with their name, so
+    /// shadowed variables and variables of different blocks get registers of their own.
+    pub fn declaration(&self) -> WId {
+        match self {
+            Binding::Local(stmt) | Binding::ForLoop(stmt) => stmt.as_weak(),
+            Binding::MatchArm(arm) | Binding::Captured(arm) => arm.as_weak(),
+            Binding::Parameter(func, _) => func.as_weak(),
+        }
+    }
+}
+
+impl Ast {
+    /// Binding `ident` refers to at `index`. A declaration is visible in the rest of its block,
+    /// `let x = x + 1;` reads the previous `x` and only the statement itself stores into the new one.
+    pub fn lookup_binding(&self, index: WId, ident: &Identifier) -> Option<Binding> {
+        if let Some(arm) = self.lookup_match_arm(index, ident) {
+            return Some(Binding::MatchArm(arm));
+        }
+
+        let mut stmt = self.get_parent_of_kind::<Statement, _>(index)?;
+        let mut inclusive = stmt.as_weak() == index;
+
+        while let Some(bs) = self.get_parent_of_kind::<BlockScope, _>(stmt.as_weak()) {
+            let statements = &self.get(&bs).statements;
+            let position = statements.iter().position(|id| *id == stmt)?;
+            let visible = if inclusive { &statements[..=position] } else { &statements[..position] };
+
+            if let Some(declaring) = visible.iter().rev().find(|id| self.declares(id, ident)) {
+                return Some(Binding::Local(*declaring));
+            }
+
+            inclusive = false;
+
+            match self.try_promote::<Statement>(self.get_node(&bs).get_parent()) {
+                Some(parent) => match self.get(&parent) {
+                    Statement::ForStmt { ident: variable, .. } if variable == ident => return Some(Binding::ForLoop(parent)),
+                    _ => stmt = parent,
+                },
+                None => break,
+            }
+        }
+
+        let func = self.get_parent_of_kind::<Function, _>(stmt.as_weak())?;
+
+        if let Some(position) = self.get(&func).parameters.iter().position(|(param, _)| param == ident) {
+            return Some(Binding::Parameter(func, position));
+        }
+
+        let lambda = self.get_enclosing_lambda(&func)?;
+        self.lookup_binding(lambda.as_weak(), ident).map(|_| Binding::Captured(lambda))
+    }
+
+    /// Type of `ident` declared by `binding`.
+    pub fn get_binding_type(&self, binding: &Binding, ident: &Identifier) -> Result<TypeId, TypeResolverError> {
+        match binding {
+            Binding::Local(stmt) => match self.get(stmt) {
+                Statement::LocalVariableDeclaration { ty, .. } => Ok(ty.clone()),
+                Statement::Destructure { bindings, ty, .. } => {
+                    let collection = &self.get_arp_file_in_parent(stmt.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+                    let position = bindings.iter().position(|binding| binding == ident);
+
+                    Ok(collection.get_tuple_elements(ty)
+                        .zip(position)
+                        .and_then(|(elements, position)| elements.get(position).cloned())
+                        .unwrap_or_default())
+                },
+                _ => Ok(TypeId::None),
+            },
+            Binding::MatchArm(arm_expr) => {
+                let Some(match_index) = self.try_promote::<Expression>(self.get_node(arm_expr).get_parent()) else { return Ok(TypeId::None) };
+                let Expression::Match { arms, .. } = self.get(&match_index) else { return Ok(TypeId::None) };
+
+                match arms.iter().find(|arm| arm.expr == *arm_expr) {
+                    Some(arm) => Ok(self.get_pattern_binding_type(&match_index, &arm.pattern, ident)?.unwrap_or_default()),
+                    None => Ok(TypeId::None),
+                }
+            },
+            Binding::ForLoop(_) => Ok(TypeId::None),
+            Binding::Parameter(func, position) => Ok(self.get(func).parameters[*position].1.clone()),
+            Binding::Captured(lambda) => self.get_variable_type(lambda, ident),
+        }
+    }
+
+    fn declares(&self, stmt: &Id<Statement>, ident: &Identifier) -> bool {
+        match self.get(stmt) {
+            Statement::LocalVariableDeclaration { ident: declared, .. } => declared == ident,
+            Statement::Destructure { bindings, .. } => bindings.contains(ident),
+            _ => false,
+        }
+    }
+
+    /// Body of the innermost match arm around `index` whose pattern binds `ident`.
+    fn lookup_match_arm(&self, index: WeakIndex, ident: &Identifier) -> Option<Id<Expression>> {
+        let mut child = index;
+
+        while let Some(parent) = self.get_weak(child).map(|node| node.get_parent()) {
+            let Some(match_index) = self.try_promote::<Expression>(parent) else { break };
+
+            if let Expression::Match { arms, .. } = self.get(&match_index) {
+                if let Some(arm) = arms.iter().find(|arm| arm.expr.as_weak() == child && arm.pattern.bindings().contains(&ident)) {
+                    return Some(arm.expr);
+                }
+            }
+
+            child = parent;
+        }
+
+        None
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{errors::ProcessingError, types::expression::{Expression, Literal}, validations::ValidationError};
+
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn named_and_default_arguments() {
+    let ast = get_file_ast("
+class Text {
+    value: string
+}
+
+impl Text {
+    fn pad(text: string, width: int32 = 8, fill: string = \" \") -> Text {
+        return Text { value: text };
+    }
+}
+
+fn main() {
+    let short = Text.pad(\"a\");
+    let wide = Text.pad(fill: \"-\", text: \"b\");
+}
+", Some("test/arguments/named")).unwrap();
+
+    let calls = ast.get_nodes_of_kind::<Expression>().into_iter().filter(|expr| matches!(ast.get(expr), Expression::Call { .. })).collect::<Vec<_>>();
+    let literals = |call| match ast.get(call) {
+        Expression::Call { args, names, .. } => {
+            assert!(names.is_empty());
+            args.iter().map(|arg| match ast.get(arg) {
+                Expression::Literal(literal) => literal.clone(),
+                expr => panic!("Expected literal, found {expr:?}"),
+            }).collect::<Vec<_>>()
+        },
+        _ => unreachable!(),
+    };
+
+    // Calls are lowered to every argument in parameter order, the defaults filled in.
+    assert_eq!(literals(&calls[0]), [Literal::String("\"a\"".into()), Literal::Integer(8), Literal::String("\" \"".into())]);
+    assert_eq!(literals(&calls[1]), [Literal::String("\"b\"".into()), Literal::Integer(8), Literal::String("\"-\"".into())]);
+}
+
+#[test]
+fn top_level_defaults() {
+    get_file_ast("
+fn scale(value: float32, factor: float32 = 2.0, offset: int32 = -1) -> float32 {
+    return value * factor;
+}
+
+fn main() {
+    let twice: float32 = scale(3.0);
+    let half = scale(factor: 0.5, value: 4.0) + scale(1.0, 3.0, 2);
+}
+", Some("test/arguments/top_level")).unwrap();
+}
+
+#[test]
+fn argument_errors() {
+    let error_of = |call: &str| {
+        let source = format!("
+fn join(first: string, second: string, sep: string = \", \") -> string {{
+    return first;
+}}
+
+fn main() {{
+    let joined: string = {call};
+}}
+");
+
+        match get_file_ast(&source, Some("test/arguments/errors")).unwrap_err() {
+            ProcessingError::Validation(error) => error,
+            error => panic!("unexpected error {error:?}"),
+        }
+    };
+
+    assert!(matches!(error_of("join(\"a\", second: \"b\", spacer: \"\")"), ValidationError::UnknownArgument { name, .. } if name.as_ref() == "spacer"));
+    assert!(matches!(error_of("join(\"a\", first: \"b\")"), ValidationError::DuplicateArgument { name, .. } if name.as_ref() == "first"));
+    assert!(matches!(error_of("join(sep: \"-\", first: \"a\")"), ValidationError::MissingArgument { name, .. } if name.as_ref() == "second"));
+    assert!(matches!(error_of("join(first: \"a\", \"b\")"), ValidationError::PositionalAfterNamed(method) if method.as_ref() == "join"));
+}
+
+#[test]
+fn default_of_wrong_type() {
+    let error = get_file_ast("
+fn repeat(text: string, times: int32 = \"twice\") { }
+", Some("test/arguments/default_type")).unwrap_err();
+
+    match error {
+        ProcessingError::Validation(ValidationError::TypeMismatch { expected, actual }) => {
+            assert_eq!((expected.as_ref(), actual.as_ref()), ("int32", "string"));
+        },
+        error => panic!("unexpected error {error:?}"),
+    }
+}
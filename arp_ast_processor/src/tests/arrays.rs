@@ -0,0 +1,115 @@
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn literal_and_index() {
+    get_file_ast("
+fn main() {
+    let items = [1, 2, 3];
+    let first = items[0];
+    let last = items[items.len - 1];
+    items[1] = first + last;
+}
+", Some("test/arrays/literal")).unwrap();
+}
+
+#[test]
+fn declared_array() {
+    get_file_ast("
+fn main() {
+    let names: [string] = [\"a\", \"b\"];
+    let empty: [int32] = [];
+    let grid = [[1, 2], [3, 4]];
+    let cell = grid[1][0];
+    let count = empty.len + names.len;
+}
+", Some("test/arrays/declared")).unwrap();
+}
+
+#[test]
+fn array_of_classes() {
+    get_file_ast("
+class Shape {
+    sides: int32
+}
+
+class Square : Shape {
+    size: int32
+}
+
+impl Shapes {
+    fn sides(shapes: [Shape]) -> int32 {
+        return shapes[0].sides + shapes[1].sides;
+    }
+}
+
+class Shapes {
+    count: int32
+}
+
+fn main() {
+    let shapes = [Shape { sides: 3 }, Square { sides: 4, size: 2 }];
+    shapes[0] = Square { sides: 4, size: 1 };
+    let sides = Shapes.sides(shapes);
+}
+", Some("test/arrays/classes")).unwrap();
+}
+
+#[test]
+fn array_of_structs() {
+    get_file_ast("
+struct Point {
+    x: int32,
+    y: int32
+}
+
+fn main() {
+    let points = [Point { x: 1, y: 2 }, Point { x: 3, y: 4 }];
+    points[1].x = 5;
+    let x = points[1].x;
+}
+", Some("test/arrays/structs")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn mixed_elements() {
+    get_file_ast("
+fn main() {
+    let items = [1, \"two\"];
+}
+", Some("test/arrays/mixed")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn store_wrong_element() {
+    get_file_ast("
+fn main() {
+    let items = [1, 2];
+    items[0] = \"one\";
+}
+", Some("test/arrays/store")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn index_with_string() {
+    get_file_ast("
+fn main() {
+    let items = [1, 2];
+    let item = items[\"0\"];
+}
+", Some("test/arrays/index")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn assign_length() {
+    get_file_ast("
+fn main() {
+    let items = [1, 2];
+    items.len = 3;
+}
+", Some("test/arrays/len")).unwrap();
+}
@@ -0,0 +1,102 @@
+use crate::{errors::ProcessingError, types::expression::Expression, validations::{lints::{lint, Lint}, ValidationError}};
+
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn numeric_casts() {
+    let ast = get_file_ast("
+fn main() {
+    let count = 3;
+    let half = count as float32 / 2.0;
+    let precise = half as float64;
+    let rounded = precise as int32 + 1;
+}
+", Some("test/casts/numeric")).unwrap();
+
+    let casts = ast.get_nodes_of_kind::<Expression>().into_iter().filter(|expr| matches!(ast.get(expr), Expression::Cast { .. })).collect::<Vec<_>>();
+    let file = ast.get_nodes_of_kind::<crate::types::file::ArpFile>()[0];
+    let collection = &ast.get(&file).type_collection;
+
+    assert_eq!(casts.len(), 3);
+    assert!(ast.get_type(&casts[0]).unwrap().is_same(&collection.get_float()));
+    assert!(ast.get_type(&casts[1]).unwrap().is_same(&collection.get_double()));
+    assert!(ast.get_type(&casts[2]).unwrap().is_same(&collection.get_int()));
+}
+
+#[test]
+fn reference_and_enum_casts() {
+    get_file_ast("
+trait Printable {
+    fn print(this) -> string;
+}
+
+class Shape {
+    sides: int32
+}
+
+class Square : Shape {
+    size: int32
+}
+
+struct Point {
+    x: int32
+}
+
+impl Printable for Point {
+    fn print(this) -> string {
+        return \"point\";
+    }
+}
+
+enum Color {
+    Red,
+    Green = 5
+}
+
+fn main() {
+    let shape = Square { sides: 4, size: 2 } as Shape;
+    let square = shape as Square;
+    let any = square as object;
+
+    let printable = Point { x: 1 } as Printable;
+    let point = printable as Point;
+    let boxed = 1 as object;
+    let unboxed = boxed as int32;
+
+    let green = Color.Green as int32;
+    let color = green as Color;
+}
+", Some("test/casts/references")).unwrap();
+}
+
+#[test]
+fn invalid_cast() {
+    let error = get_file_ast("
+fn main() {
+    let number = \"text\" as int32;
+}
+", Some("test/casts/invalid")).unwrap_err();
+
+    match error {
+        ProcessingError::Validation(ValidationError::InvalidCast(from, to)) => {
+            assert_eq!((from.as_ref(), to.as_ref()), ("string", "int32"));
+        },
+        error => panic!("unexpected error {error:?}"),
+    }
+}
+
+#[test]
+fn lossy_casts_are_linted() {
+    let ast = get_file_ast("
+fn main() {
+    let exact = 1 as float64;
+    let truncated = 1.5 as int32;
+}
+", Some("test/casts/lossy")).unwrap();
+
+    match lint(&ast).as_slice() {
+        [Lint::LossyCast { from, to, .. }] => assert_eq!((from.as_ref(), to.as_ref()), ("float32", "int32")),
+        lints => panic!("unexpected lints {lints:?}"),
+    }
+}
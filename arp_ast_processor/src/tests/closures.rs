@@ -0,0 +1,120 @@
+use crate::types::{expression::Expression, type_collection::TypeKind};
+
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn lambda_and_function_types() {
+    get_file_ast("
+fn main() {
+    let double = fn(x: int32) => x * 2;
+    let four = double(2);
+    let is_even: fn(int32) -> bool = fn(x: int32) => x > 2;
+    let even = is_even(four);
+}
+", Some("test/closures/lambda")).unwrap();
+}
+
+#[test]
+fn captures() {
+    let ast = get_file_ast("
+fn main() {
+    let offset = 10;
+    let scale = 2;
+    let shift = fn(x: int32) => x + offset;
+    let both = fn(x: int32) => shift(x) * scale;
+    let y = both(1);
+}
+", Some("test/closures/captures")).unwrap();
+
+    let captures = ast.get_nodes_of_kind::<Expression>().into_iter().filter_map(|expr| match ast.get(&expr) {
+        Expression::Lambda { captures, .. } => Some(captures.iter().map(|(ident, _)| ident.as_ref().to_string()).collect::<Vec<_>>()),
+        _ => None,
+    }).collect::<Vec<_>>();
+
+    assert_eq!(captures, vec![vec!["offset".to_string()], vec!["shift".to_string(), "scale".to_string()]]);
+}
+
+#[test]
+fn nested_lambda_captures_through_parent() {
+    let ast = get_file_ast("
+fn main() {
+    let start = 1;
+    let make = fn(x: int32) => fn(y: int32) => x + y + start;
+    let add = make(2);
+    let sum = add(3);
+}
+", Some("test/closures/nested")).unwrap();
+
+    let captures = ast.get_nodes_of_kind::<Expression>().into_iter().filter_map(|expr| match ast.get(&expr) {
+        Expression::Lambda { captures, .. } => Some(captures.len()),
+        _ => None,
+    }).collect::<Vec<_>>();
+
+    // The outer lambda captures `start` for the inner one, which captures `x` and `start`.
+    assert_eq!(captures, vec![1, 2]);
+}
+
+#[test]
+fn named_functions_as_values() {
+    let ast = get_file_ast("
+class Numbers {
+    count: int32
+}
+
+impl Numbers {
+    fn square(x: int32) -> int32 {
+        return x * x;
+    }
+}
+
+fn twice(f: fn(int32) -> int32, x: int32) -> int32 {
+    return f(f(x));
+}
+
+fn increment(x: int32) {
+}
+
+fn main() {
+    let square = Numbers.square;
+    let sixteen = twice(square, 2);
+    let apply = twice;
+    let result = apply(fn(x: int32) => x + 1, 1);
+    let action = increment;
+}
+", Some("test/closures/named")).unwrap();
+
+    let file = ast.get_nodes_of_kind::<crate::types::file::ArpFile>()[0];
+    let collection = &ast.get(&file).type_collection;
+
+    let apply = collection.resolve_name("fn(fn(int32) -> int32, int32) -> int32");
+    assert!(matches!(collection.try_get_strong(&apply).map(|info| &info.kind), Some(TypeKind::Function { params, .. }) if params.len() == 2));
+    assert!(collection.resolve_name("fn(int32)").is_strong());
+}
+
+#[test]
+#[should_panic]
+fn wrong_argument_count() {
+    get_file_ast("
+fn main() {
+    let double = fn(x: int32) => x * 2;
+    let four = double(2, 3);
+}
+", Some("test/closures/arity")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn this_inside_lambda() {
+    get_file_ast("
+class Counter {
+    count: int32
+}
+
+impl Counter {
+    fn getter(this) -> fn() -> int32 {
+        return fn() => this.count;
+    }
+}
+", Some("test/closures/this")).unwrap();
+}
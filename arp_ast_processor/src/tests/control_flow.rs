@@ -0,0 +1,181 @@
+use std::ops::Range;
+
+use crate::{cfg::ControlFlowGraph, errors::ProcessingError, types::{function::Function, statement::Statement}, validations::ValidationError};
+
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn returns_on_every_path() {
+    get_file_ast("
+fn sign(x: int32) -> int32 {
+    if x > 0 {
+        return 1;
+    } else if x < 0 {
+        return -1;
+    } else {
+        return 0;
+    }
+}
+
+fn first_over(limit: int32) -> int32 {
+    let mut i = 0;
+    while true {
+        if i > limit {
+            return i;
+        }
+        i = i + 1;
+    }
+}
+
+fn count() {
+    let mut i = 0;
+    while true {
+        if i > 10 {
+            break;
+        }
+        i = i + 1;
+    }
+}
+", Some("test/control_flow/returns")).unwrap();
+}
+
+#[test]
+fn missing_return() {
+    let error = get_file_ast("
+fn sign(x: int32) -> int32 {
+    if x > 0 {
+        return 1;
+    } else if x < 0 {
+        return -1;
+    }
+}
+", Some("test/control_flow/missing")).unwrap_err();
+
+    match error {
+        ProcessingError::Validation(ValidationError::MissingReturn(name)) => assert_eq!(name.as_ref(), "sign"),
+        error => panic!("unexpected error {error:?}"),
+    }
+}
+
+#[test]
+fn code_after_return() {
+    let source = "
+fn one() -> int32 {
+    return 1;
+    let x = 2;
+}
+";
+    let error = get_file_ast(source, Some("test/control_flow/after_return")).unwrap_err();
+
+    match error {
+        ProcessingError::Validation(ValidationError::UnreachableCode(span)) => assert_eq!(&source[Range::from(span)], "let x = 2;"),
+        error => panic!("unexpected error {error:?}"),
+    }
+}
+
+#[test]
+#[should_panic]
+fn code_after_break() {
+    get_file_ast("
+fn main() {
+    let mut i = 0;
+    while i < 10 {
+        break;
+        i = i + 1;
+    }
+}
+", Some("test/control_flow/after_break")).unwrap();
+}
+
+#[test]
+fn loop_without_exit() {
+    let error = get_file_ast("
+fn main() {
+    let mut i = 0;
+    while true {
+        i = i + 1;
+    }
+}
+", Some("test/control_flow/infinite")).unwrap_err();
+
+    assert!(matches!(error, ProcessingError::Validation(ValidationError::InfiniteLoop(_))));
+}
+
+#[test]
+#[should_panic]
+fn break_outside_loop() {
+    get_file_ast("
+fn main() {
+    break;
+}
+", Some("test/control_flow/break")).unwrap();
+}
+
+#[test]
+fn graph_of_loop() {
+    let ast = get_file_ast("
+fn main() {
+    let mut i = 0;
+    while i < 10 {
+        i = i + 1;
+    }
+}
+", Some("test/control_flow/graph")).unwrap();
+
+    let main = ast.get_nodes_of_kind::<Function>().into_iter().find(|func| ast.get(func).name.as_ref() == "main").unwrap();
+    let cfg = ControlFlowGraph::build(&ast, &main);
+    let reachable = cfg.reachable();
+
+    assert_eq!(cfg.loops.len(), 1);
+
+    let lp = &cfg.loops[0];
+    assert!(cfg.blocks[lp.header].successors.contains(&lp.after));
+    assert!(cfg.blocks[lp.header + 1..lp.after].iter().any(|block| block.successors.contains(&lp.header)));
+    assert!(reachable[cfg.end] && cfg.blocks[cfg.end].successors == [ControlFlowGraph::EXIT]);
+}
+
+#[test]
+fn trailing_expression_returns() {
+    let ast = get_file_ast("
+class MyClass {
+    field: int32
+}
+
+fn answer() -> int32 {
+    42
+}
+
+fn make() -> MyClass {
+    MyClass { 5 }
+}
+
+fn log() {
+    answer()
+}
+", Some("test/control_flow/trailing")).unwrap();
+
+    let last_statement = |name: &str| {
+        let func = ast.get_nodes_of_kind::<Function>().into_iter().find(|func| ast.get(func).name.as_ref() == name).unwrap();
+        let body = ast.get(&ast.get(&func).block);
+
+        assert!(body.return_expression.is_none());
+        ast.get(body.statements.last().unwrap()).clone()
+    };
+
+    assert!(matches!(last_statement("answer"), Statement::Return(_)));
+    assert!(matches!(last_statement("make"), Statement::Return(_)));
+    assert!(matches!(last_statement("log"), Statement::Expression(_)));
+}
+
+#[test]
+fn empty_body_is_a_stub() {
+    let ast = get_file_ast("
+fn parse() -> int32 {}
+
+fn main() {}
+", Some("test/control_flow/stub")).unwrap();
+
+    let stubs = ast.get_nodes_of_kind::<Function>().into_iter().filter(|func| ast.is_stub(func)).map(|func| ast.get(&func).name.clone()).collect::<Vec<_>>();
+    assert_eq!(stubs, ["parse".into()]);
+}
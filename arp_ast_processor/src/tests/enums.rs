@@ -0,0 +1,78 @@
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn enum_members() {
+    get_file_ast("
+enum Color {
+    Red,
+    Green = 5,
+    Blue
+}
+
+fn main() {
+    let color = Color.Green;
+    let is_red = color == Color.Red;
+    let is_not_blue = color != Color.Blue;
+    let before_blue = color < Color.Blue;
+    let value = color.value + 1;
+}
+", Some("test/enums/members")).unwrap();
+}
+
+#[test]
+fn enum_field_and_parameter() {
+    get_file_ast("
+enum Direction {
+    Up,
+    Down
+}
+
+class Player {
+    facing: Direction
+}
+
+impl Player {
+    fn is_facing(this, direction: Direction) -> bool {
+        return this.facing == direction;
+    }
+}
+
+fn main() {
+    let player = Player { facing: Direction.Down };
+    let up = player.is_facing(Direction.Up);
+}
+", Some("test/enums/field")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn unknown_member() {
+    get_file_ast("
+enum Color {
+    Red
+}
+
+fn main() {
+    let color = Color.Purple;
+}
+", Some("test/enums/unknown")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn compare_different_enums() {
+    get_file_ast("
+enum Color {
+    Red
+}
+
+enum Size {
+    Small
+}
+
+fn main() {
+    let same = Color.Red == Size.Small;
+}
+", Some("test/enums/mismatch")).unwrap();
+}
@@ -71,7 +71,7 @@ fn complex_expressions() {
     assert_eq!(nodes.len(), 1);
     let nodes = ast.find_children_of_kind::<Expression, _>(ast.get_root_index(), |node| {
         match node {
-            Expression::Call { on: Some(_), method: _, args } => args.is_empty(),
+            Expression::Call { on: Some(_), args, .. } => args.is_empty(),
             _ => false,
         }
     });
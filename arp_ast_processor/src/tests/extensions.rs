@@ -0,0 +1,105 @@
+use arp_types::sources::Source;
+
+use crate::{build_multiple_sources, types::{file::ArpFile, implementation::Implementation}};
+
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn extend_standard_type() {
+    let ast = get_file_ast("
+impl string {
+    fn shout(this) -> string {
+        return this + \"!\";
+    }
+
+    fn empty() -> string {
+        return \"\";
+    }
+}
+
+fn main() {
+    let greeting = \"hi\";
+    let loud: string = greeting.shout();
+    let nothing: string = string.empty();
+}
+", Some("test/extensions/standard")).unwrap();
+
+    let implementation = ast.get_nodes_of_kind::<Implementation>()[0];
+    assert!(ast.get(&implementation).is_extension);
+}
+
+#[test]
+fn own_types_are_not_extended() {
+    let ast = get_file_ast("
+class Counter {
+    count: int32
+}
+
+impl Counter {
+    fn get(this) -> int32 {
+        return this.count;
+    }
+}
+", Some("test/extensions/own")).unwrap();
+
+    let implementation = ast.get_nodes_of_kind::<Implementation>()[0];
+    assert!(!ast.get(&implementation).is_extension);
+}
+
+#[test]
+fn extend_imported_type() {
+    let sources = [
+        Source::new_inline("Main.arp", "
+        from Shapes import Point
+
+        impl Point {
+            fn sum(this) -> int32 {
+                return this.x + this.y;
+            }
+        }
+
+        fn main() {
+            let point = Point { x: 1, y: 2 };
+            let sum: int32 = point.sum();
+        }"),
+
+        Source::new_inline("Shapes.arp", "
+        class Point {
+            x: int32,
+            y: int32
+        }"),
+    ];
+
+    let ast = build_multiple_sources(&sources).unwrap();
+
+    // The extension belongs to the extending file, `Point` itself stays as declared.
+    let shapes = ast.get_nodes_of_kind::<ArpFile>().into_iter().find(|file| ast.get(file).arp_path.0 == "Shapes").unwrap();
+    let collection = &ast.get(&shapes).type_collection;
+    assert!(collection.try_get_strong(&collection.resolve_name("Point")).unwrap().methods.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn extensions_are_not_imported() {
+    let sources = [
+        Source::new_inline("Main.arp", "
+        from Strings import Empty
+
+        fn main() {
+            let greeting = \"hi\";
+            let loud = greeting.shout();
+        }"),
+
+        Source::new_inline("Strings.arp", "
+        class Empty { }
+
+        impl string {
+            fn shout(this) -> string {
+                return this + \"!\";
+            }
+        }"),
+    ];
+
+    build_multiple_sources(&sources).unwrap();
+}
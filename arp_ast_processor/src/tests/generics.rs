@@ -0,0 +1,202 @@
+use arp_types::sources::Source;
+
+use crate::{build_multiple_sources, types::function::Function};
+
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn generic_class_instance() {
+    get_file_ast("
+class Box<T> {
+    value: T
+}
+
+impl Box<T> {
+    fn get(this) -> T {
+        return this.value;
+    }
+
+    fn copy(this) -> Box<T> {
+        return Box<T> { value: this.value };
+    }
+}
+
+fn main() {
+    let boxed = Box<int32> { value: 1 };
+    let value = boxed.get();
+    let copied = boxed.copy().get();
+    let field = boxed.value;
+    let text = Box<string> { value: \"text\" };
+}
+", Some("test/generics/instance")).unwrap();
+}
+
+#[test]
+fn generic_impl_with_renamed_parameter() {
+    get_file_ast("
+class Pair<A, B> {
+    first: A,
+    second: B
+}
+
+impl Pair<X, Y> {
+    fn first(this) -> X {
+        return this.first;
+    }
+}
+
+fn main() {
+    let pair = Pair<int32, bool> { first: 1, second: true };
+    let first = pair.first();
+}
+", Some("test/generics/renamed")).unwrap();
+}
+
+#[test]
+fn generic_method_inference() {
+    get_file_ast("
+class Box<T> {
+    value: T
+}
+
+class Boxes { }
+
+impl Boxes {
+    fn identity<U>(x: U) -> U {
+        return x;
+    }
+
+    fn wrap<U>(x: U) -> Box<U> {
+        return Box<U> { value: x };
+    }
+}
+
+fn main() {
+    let number = Boxes.identity(1);
+    let boxed = Boxes.wrap(true);
+    let unwrapped = boxed.value;
+}
+", Some("test/generics/inference")).unwrap();
+}
+
+#[test]
+fn generic_function_inference() {
+    let ast = get_file_ast("
+class Box<T> {
+    value: T
+}
+
+fn id<T>(x: T) -> T {
+    return x;
+}
+
+fn wrap<T>(x: T) -> Box<T> {
+    return Box<T> { value: x };
+}
+
+fn main() {
+    let number = id(5);
+    let text = id(\"five\");
+    let boxed = wrap(true);
+}
+", Some("test/generics/function_inference")).unwrap();
+
+    let main = ast.get_nodes_of_kind::<Function>().into_iter().find(|func| ast.get(func).name.as_ref() == "main").unwrap();
+    let collection = &ast.get_arp_file_in_parent(main.as_weak()).unwrap().type_collection;
+    let registers = ast.get(&main).registers.iter().map(|(_, ty)| collection.get_source_name(ty).unwrap_or_default().to_string()).collect::<Vec<_>>();
+
+    assert_eq!(registers, vec!["int32", "string", "Box<bool>"]);
+}
+
+#[test]
+fn generic_bound() {
+    get_file_ast("
+class Shape {
+    sides: int32
+}
+
+impl Shape {
+    fn count(this) -> int32 {
+        return this.sides;
+    }
+}
+
+class Holder<T: Shape> {
+    item: T
+}
+
+impl Holder<T> {
+    fn count(this) -> int32 {
+        return this.item.count();
+    }
+}
+
+fn main() {
+    let holder = Holder<Shape> { item: Shape { sides: 3 } };
+}
+", Some("test/generics/bound")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn generic_unsatisfied_bound() {
+    get_file_ast("
+class Shape {
+    sides: int32
+}
+
+class Holder<T: Shape> {
+    item: T
+}
+
+fn main() {
+    let holder = Holder<int32> { item: 1 };
+}
+", Some("test/generics/unsatisfied_bound")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn generic_field_keeps_argument() {
+    get_file_ast("
+class Box<T> {
+    value: T
+}
+
+fn main() {
+    let boxed = Box<Option<int32>> { value: Option<int32>.None { } };
+    let value: int32 = boxed.value;
+}
+", Some("test/generics/field_argument")).unwrap();
+}
+
+#[test]
+fn generic_across_files() {
+    let sources = [
+        Source::new_inline("Main.arp", "
+        from Container import Box
+
+        class App { }
+
+        impl App {
+            fn run() -> int32 {
+                let boxed = Box<int32> { value: 2 };
+                return boxed.get();
+            }
+        }"),
+
+        Source::new_inline("Container.arp", "
+        class Box<T> {
+            value: T
+        }
+
+        impl Box<T> {
+            fn get(this) -> T {
+                return this.value;
+            }
+        }"),
+    ];
+
+    build_multiple_sources(&sources).unwrap();
+}
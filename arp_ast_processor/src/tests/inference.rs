@@ -0,0 +1,129 @@
+use std::ops::Range;
+
+use crate::{errors::ProcessingError, type_resolver::TypeResolverError, types::{function::Function, statement::Statement}, validations::ValidationError};
+
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn long_chain_of_locals() {
+    let lets = (1..300).map(|i| format!("    let v{i} = v{} + 1;\n", i - 1)).collect::<String>();
+    let source = format!("fn main() {{\n    let v0 = 0;\n{lets}    let last: int32 = v299;\n}}");
+
+    get_file_ast(&source, Some("test/inference/chain")).unwrap();
+}
+
+#[test]
+fn lambdas_and_captured_locals() {
+    let ast = get_file_ast("
+fn main() {
+    let offset = 10;
+    let add = fn(x: int32) => x + offset;
+    let twice = fn(x: int32) => add(add(x));
+    let result = twice(1);
+    let check: int32 = result;
+}
+", Some("test/inference/lambdas")).unwrap();
+
+    let collection = &ast.get_nodes_of_kind::<Function>().first().and_then(|func| ast.get_arp_file_in_parent(func.as_weak())).unwrap().type_collection;
+    let returns = ast.get_nodes_of_kind::<Function>().into_iter()
+        .filter(|func| ast.get(func).name.as_ref() == "Invoke")
+        .map(|func| collection.get_source_name(&ast.get(&func).return_type).unwrap_or_default().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(returns, vec!["int32", "int32"]);
+}
+
+#[test]
+fn typed_by_later_assignment() {
+    let ast = get_file_ast("
+fn main() {
+    let mut items = [];
+    items = [1, 2];
+    let first: int32 = items[0];
+}
+", Some("test/inference/assignment")).unwrap();
+
+    let items = ast.get_nodes_of_kind::<Statement>().into_iter().find_map(|stmt| match ast.get(&stmt) {
+        Statement::LocalVariableDeclaration { ident, ty, .. } if ident.as_ref() == "items" => Some(ty.clone()),
+        _ => None,
+    }).unwrap();
+
+    assert!(items.is_strong());
+}
+
+#[test]
+fn uninferable_variable() {
+    let source = "
+fn main() {
+    let items = [1, 2];
+    for item in items {
+        let copy = item;
+    }
+}
+";
+    let error = get_file_ast(source, Some("test/inference/uninferable")).unwrap_err();
+
+    match error {
+        ProcessingError::TypeResolver(TypeResolverError::CantInferType(name, span)) => {
+            assert_eq!(name.as_ref(), "copy");
+            assert_eq!(&source[Range::from(span)], "let copy = item;");
+        },
+        error => panic!("unexpected error {error:?}"),
+    }
+}
+
+#[test]
+fn undeclared_variable_in_initializer() {
+    let source = "
+fn main() {
+    let x = y;
+    let z = x + 1;
+}
+";
+    let error = get_file_ast(source, Some("test/inference/undeclared")).unwrap_err();
+
+    match error {
+        ProcessingError::TypeResolver(TypeResolverError::UndeclaredVariable(name, span)) => {
+            assert_eq!(name.as_ref(), "y");
+            assert_eq!(&source[Range::from(span)], "y");
+        },
+        error => panic!("unexpected error {error:?}"),
+    }
+}
+
+#[test]
+fn unresolved_call_is_reported() {
+    let declarations = "
+class Point {
+    x: int32
+}
+
+impl Point {
+    fn moved(this, dx: int32) -> Point {
+        return Point { x: this.x + dx };
+    }
+}
+
+fn first<T>(a: T, b: T) -> T {
+    return a;
+}
+";
+
+    // Locals depending on the call aren't reported either.
+    let cases = [
+        ("let moved = Point { x: 1 }.moved(\"two\"); let copy = moved;", "Argument 0 of moved expects int32, found string"),
+        ("let picked = first(1, \"two\");", "first can't be called with (int32, string)"),
+        ("let point = Point { x: 1 }; let scaled = point.scaled(2);", "Point.scaled can't be called with (int32)"),
+        ("let missing = nowhere(1);", "nowhere isn't declared in this scope"),
+    ];
+
+    for (main, message) in cases {
+        let error = get_file_ast(&format!("{declarations}\nfn main() {{\n    {main}\n}}"), Some("test/inference/unresolved_call")).unwrap_err();
+
+        match error {
+            ProcessingError::Validation(error) if !matches!(error, ValidationError::TypeResolverError(_)) => assert_eq!(error.to_string(), message),
+            error => panic!("unexpected error {error:?}"),
+        }
+    }
+}
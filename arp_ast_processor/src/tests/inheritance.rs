@@ -0,0 +1,228 @@
+use arp_types::sources::Source;
+
+use crate::{
+    build_multiple_sources,
+    type_resolver::managed_dll_info::{SharpMethodInfo, SharpTypeInfo, SharpTypedIdent},
+    types::{file::ArpFile, type_collection::CONSTRUCTOR},
+    validations::{Validate, ValidationError},
+};
+
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn inherited_fields_and_methods() {
+    get_file_ast("
+class Shape {
+    sides: int32
+}
+
+impl Shape {
+    fn count(this) -> int32 {
+        return this.sides;
+    }
+}
+
+class Square : Shape {
+    size: int32
+}
+
+impl Square {
+    fn area(this) -> int32 {
+        return this.size * this.size;
+    }
+}
+
+fn main() {
+    let square = Square { sides: 4, size: 2 };
+    let sides = square.sides;
+    let count = square.count();
+    let area = square.area();
+}
+", Some("test/inheritance/fields")).unwrap();
+}
+
+#[test]
+fn base_declared_later() {
+    get_file_ast("
+class Square : Shape {
+    size: int32
+}
+
+class Shape {
+    sides: int32
+}
+
+fn main() {
+    let square = Square { sides: 4, size: 2 };
+    let sides = square.sides;
+}
+", Some("test/inheritance/later")).unwrap();
+}
+
+#[test]
+fn base_call() {
+    get_file_ast("
+class Shape {
+    sides: int32
+}
+
+impl Shape {
+    fn describe(this) -> int32 {
+        return this.sides;
+    }
+}
+
+class Square : Shape {
+    size: int32
+}
+
+impl Square {
+    fn describe(this) -> int32 {
+        return base.describe() + this.size;
+    }
+}
+", Some("test/inheritance/base_call")).unwrap();
+}
+
+#[test]
+fn subclass_as_argument() {
+    get_file_ast("
+class Shape {
+    sides: int32
+}
+
+class Square : Shape {
+    size: int32
+}
+
+class Geometry { }
+
+impl Geometry {
+    fn sides(shape: Shape) -> int32 {
+        return shape.sides;
+    }
+}
+
+fn main() {
+    let sides = Geometry.sides(Square { sides: 4, size: 2 });
+}
+", Some("test/inheritance/argument")).unwrap();
+}
+
+#[test]
+fn generic_base() {
+    get_file_ast("
+class Box<T> {
+    value: T
+}
+
+class Labeled<T> : Box<T> {
+    label: string
+}
+
+fn main() {
+    let labeled = Labeled<int32> { value: 1, label: \"one\" };
+    let value = labeled.value;
+}
+", Some("test/inheritance/generic")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn base_without_base_class() {
+    get_file_ast("
+class Shape {
+    sides: int32
+}
+
+impl Shape {
+    fn describe(this) -> int32 {
+        return base.describe();
+    }
+}
+", Some("test/inheritance/no_base")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn inheritance_cycle() {
+    get_file_ast("
+class A : B { }
+class B : A { }
+", Some("test/inheritance/cycle")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn inherit_from_union() {
+    get_file_ast("
+union Shape { Circle { r: int32 }, Empty }
+class Square : Shape { }
+", Some("test/inheritance/union")).unwrap();
+}
+
+#[test]
+fn inheritance_across_files() {
+    let sources = [
+        Source::new_inline("Main.arp", "
+        from Shapes import Square
+
+        class App { }
+
+        impl App {
+            fn run() -> int32 {
+                let square = Square { sides: 4, size: 2 };
+                return square.count();
+            }
+        }"),
+
+        Source::new_inline("Shapes.arp", "
+        class Shape {
+            sides: int32
+        }
+
+        impl Shape {
+            fn count(this) -> int32 {
+                return this.sides;
+            }
+        }
+
+        class Square : Shape {
+            size: int32
+        }"),
+    ];
+
+    build_multiple_sources(&sources).unwrap();
+}
+
+#[test]
+fn managed_base_constructor() {
+    let ctor = |args: Vec<SharpTypedIdent>| SharpMethodInfo { ident: CONSTRUCTOR.into(), args, return_ty_full_name: "System.Void".into(), ..Default::default() };
+    let interval = SharpTypedIdent { ident: "interval".into(), ty_full_name: "System.Double".into() };
+
+    for (constructors, is_valid) in [(vec![ctor(vec![]), ctor(vec![interval.clone()])], true), (vec![ctor(vec![interval])], false)] {
+        let mut ast = get_file_ast("class Clock { }", Some("test/inheritance/managed_base")).unwrap();
+        let file = ast.get_nodes_of_kind::<ArpFile>()[0];
+
+        // Managed bases come from imported dlls, the base is set like the import would.
+        let collection = &mut ast.get_mut(&file).type_collection;
+        collection.insert_external("System.ComponentModel.TypeConverter.dll", &SharpTypeInfo {
+            full_name: "System.Timers.Timer".into(),
+            short_name: Some("Timer".into()),
+            constructors,
+            ..Default::default()
+        });
+        let (clock, timer) = (collection.resolve_name("Clock"), collection.resolve_name("Timer"));
+        collection.set_base(&clock, timer);
+
+        match ast.get(&file).validate(file, &ast) {
+            Ok(()) => assert!(is_valid),
+            Err(ValidationError::NoBaseConstructor(ty, base)) => {
+                assert!(!is_valid);
+                assert_eq!((ty.as_ref(), base.as_ref()), ("Clock", "System.Timers.Timer"));
+            },
+            Err(error) => panic!("unexpected error {error:?}"),
+        }
+    }
+}
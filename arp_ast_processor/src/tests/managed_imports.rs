@@ -1,5 +1,12 @@
+use std::path::Path;
+
 use arp_types::sources::Source;
-use crate::build_multiple_sources;
+use tempfile::TempDir;
+use crate::{
+    build_multiple_sources,
+    type_resolver::managed_dll_info::{resolve_dll_path, DllImporterError, SharpEnumMember, SharpMethodInfo, SharpPropertyInfo, SharpStaticFieldInfo, SharpTypeInfo, SharpTypedIdent},
+    types::{expression::Literal, type_collection::{BoundArgument, Overload, TypeCollection, TypeId, TypeKind, CONSTRUCTOR}},
+};
 
 #[test]
 fn external_import() {
@@ -16,4 +23,393 @@ fn external_import() {
     let ast = build_multiple_sources(&sources).unwrap();
 
     dbg!(ast);
-}
\ No newline at end of file
+}
+
+#[test]
+fn dll_paths() {
+    let dir = TempDir::new().unwrap();
+    let framework = dir.path().to_path_buf();
+    std::fs::write(framework.join("System.Collections.dll"), []).unwrap();
+
+    // Dlls that aren't next to the project come from the framework.
+    let list = resolve_dll_path(Path::new("System.Collections.dll"), std::slice::from_ref(&framework)).unwrap();
+    assert_eq!(list, framework.join("System.Collections.dll"));
+
+    let own = resolve_dll_path(&framework.join("System.Collections.dll"), &[]).unwrap();
+    assert_eq!(own, framework.join("System.Collections.dll"));
+
+    let missing = resolve_dll_path(Path::new("System.Missing.dll"), &[framework]);
+    assert!(matches!(missing, Err(DllImporterError::DllNotFound(path)) if path == Path::new("System.Missing.dll")));
+}
+
+fn list_type_info() -> SharpTypeInfo {
+    let ident = |ident: &str, ty: &str| SharpTypedIdent { ident: ident.into(), ty_full_name: ty.into() };
+    let method = |name: &str, args: Vec<SharpTypedIdent>, ret: &str| SharpMethodInfo { ident: name.into(), args, return_ty_full_name: ret.into(), ..Default::default() };
+
+    SharpTypeInfo {
+        full_name: "System.Collections.Generic.List`1".into(),
+        short_name: Some("List`1".into()),
+        generic_params: vec!["T".into()],
+        constructors: vec![
+            method(CONSTRUCTOR, vec![], "System.Void"),
+            method(CONSTRUCTOR, vec![ident("capacity", "System.Int32")], "System.Void"),
+        ],
+        properties: vec![
+            SharpPropertyInfo { ident: "Count".into(), ty_full_name: "System.Int32".into(), can_read: true, ..Default::default() },
+            SharpPropertyInfo { ident: "Last".into(), ty_full_name: "!0".into(), can_read: true, can_write: true, ..Default::default() },
+        ],
+        methods: vec![
+            method("Add", vec![ident("item", "!0")], "System.Void"),
+            method("get_Item", vec![ident("index", "System.Int32")], "!0"),
+            method("GetRange", vec![ident("index", "System.Int32"), ident("count", "System.Int32")], "System.Collections.Generic.List<!0>"),
+            method("ConvertAll", vec![ident("converter", "System.Converter<!0, !!0>")], "System.Collections.Generic.List<!!0>"),
+            method("Sort", vec![ident("comparison", "System.Comparison<!0>")], "System.Void"),
+            method("Find", vec![ident("match", "System.Predicate<!0>")], "!0"),
+            method("ForEach", vec![ident("action", "System.Action<!0>")], "System.Void"),
+            method("Select", vec![ident("selector", "System.Func<!0, System.String>")], "System.Void"),
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn managed_generic_instance() {
+    let mut collection = TypeCollection::default();
+
+    // Annotations are folded before extern imports are resolved.
+    let pending = collection.get_or_allocate(&"List<int32>");
+    collection.insert_external("System.Collections.dll", &list_type_info());
+
+    let list = collection.resolve_name("List<int32>");
+    assert!(list.is_strong());
+    assert!(list.is_same(&pending));
+    assert!(list.is_same(&collection.resolve_name("System.Collections.Generic.List<int32>")));
+
+    let int = collection.get_int();
+    let info = collection.try_get_strong(&list).unwrap();
+
+    let add = info.methods.iter().find(|mi| mi.name.as_ref() == "Add").unwrap();
+    assert!(add.args[0].1.is_same(&int));
+
+    let get = info.methods.iter().find(|mi| mi.name.as_ref() == "get_Item").unwrap();
+    assert!(get.return_type.is_same(&int));
+
+    let range = info.methods.iter().find(|mi| mi.name.as_ref() == "GetRange").unwrap();
+    assert!(range.return_type.is_same(&list));
+
+    assert!(!info.methods.iter().any(|mi| mi.name.as_ref() == "ConvertAll"));
+}
+
+#[test]
+fn managed_interface_is_trait() {
+    let mut collection = TypeCollection::default();
+
+    let comparable = SharpTypeInfo {
+        full_name: "System.IComparable".into(),
+        short_name: Some("IComparable".into()),
+        is_interface: true,
+        methods: vec![SharpMethodInfo {
+            ident: "CompareTo".into(),
+            args: vec![SharpTypedIdent { ident: "obj".into(), ty_full_name: "System.Object".into() }],
+            return_ty_full_name: "System.Int32".into(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    collection.insert_external("System.Runtime.dll", &comparable);
+
+    let ty = collection.resolve_name("IComparable");
+    let info = collection.try_get_strong(&ty).unwrap();
+    assert_eq!(info.kind, TypeKind::Trait);
+
+    let compare_to = info.methods.iter().find(|mi| mi.name.as_ref() == "CompareTo").unwrap();
+    assert!(compare_to.args[0].1.is_same(&collection.get_object()));
+    assert!(compare_to.definition.is_none());
+}
+
+#[test]
+fn managed_enum() {
+    let mut collection = TypeCollection::default();
+
+    let color = SharpTypeInfo {
+        full_name: "System.ConsoleColor".into(),
+        short_name: Some("ConsoleColor".into()),
+        is_value_type: true,
+        is_enum: true,
+        enum_members: vec![
+            SharpEnumMember { ident: "Black".into(), value: 0 },
+            SharpEnumMember { ident: "Red".into(), value: 12 },
+        ],
+        fields: vec![
+            SharpTypedIdent { ident: "value__".into(), ty_full_name: "System.Int32".into() },
+            SharpTypedIdent { ident: "Black".into(), ty_full_name: "System.ConsoleColor".into() },
+            SharpTypedIdent { ident: "Red".into(), ty_full_name: "System.ConsoleColor".into() },
+        ],
+        ..Default::default()
+    };
+
+    collection.insert_external("System.Console.dll", &color);
+
+    let ty = collection.resolve_name("ConsoleColor");
+    assert!(collection.is_enum(&ty));
+    assert!(collection.is_value_type(&ty));
+    assert_eq!(collection.get_enum_value(&ty, "Red"), Some(12));
+    assert!(collection.get_field_type(&ty, "value").unwrap().is_same(&collection.get_int()));
+    assert!(collection.get_field_type(&ty, "Red").is_none());
+}
+
+#[test]
+fn managed_array_arguments() {
+    let mut collection = TypeCollection::default();
+
+    let string = SharpTypeInfo {
+        full_name: "System.String".into(),
+        short_name: Some("String".into()),
+        methods: vec![SharpMethodInfo {
+            ident: "Join".into(),
+            args: vec![
+                SharpTypedIdent { ident: "separator".into(), ty_full_name: "System.String".into() },
+                SharpTypedIdent { ident: "value".into(), ty_full_name: "System.String[]".into() },
+            ],
+            return_ty_full_name: "System.String".into(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    // `System.String` is a standard type already, importing it adds its methods.
+    collection.insert_external("System.Runtime.dll", &string);
+
+    let ty = collection.get_string();
+    let names = collection.resolve_name("[string]");
+    assert!(names.is_strong());
+    assert!(collection.get_array_element(&names).unwrap().is_same(&ty));
+
+    let (join, _) = collection.find_method(&ty, &"Join".into(), &[ty.clone(), names]).unwrap();
+    assert!(join.return_type.is_same(&ty));
+}
+
+#[test]
+fn managed_tuple_return() {
+    let mut collection = TypeCollection::default();
+
+    let parser = SharpTypeInfo {
+        full_name: "Numbers.Parser".into(),
+        short_name: Some("Parser".into()),
+        methods: vec![SharpMethodInfo {
+            ident: "TryParse".into(),
+            args: vec![SharpTypedIdent { ident: "text".into(), ty_full_name: "System.String".into() }],
+            return_ty_full_name: "System.ValueTuple<System.Boolean, System.Int32>".into(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    collection.insert_external("Numbers.dll", &parser);
+
+    let ty = collection.resolve_name("Parser");
+    let (try_parse, _) = collection.find_method(&ty, &"TryParse".into(), &[collection.get_string()]).unwrap();
+    let elements = collection.get_tuple_elements(&try_parse.return_type).unwrap();
+
+    assert!(elements[0].is_same(&collection.get_bool()));
+    assert!(elements[1].is_same(&collection.get_int()));
+    assert!(collection.resolve_name("(bool, int32)").is_same(&try_parse.return_type));
+}
+
+#[test]
+fn managed_delegates_take_functions() {
+    let mut collection = TypeCollection::default();
+
+    let comparison = SharpTypeInfo {
+        full_name: "System.Comparison`1".into(),
+        short_name: Some("Comparison`1".into()),
+        generic_params: vec!["T".into()],
+        is_delegate: true,
+        methods: vec![SharpMethodInfo {
+            ident: "Invoke".into(),
+            args: vec![
+                SharpTypedIdent { ident: "x".into(), ty_full_name: "!0".into() },
+                SharpTypedIdent { ident: "y".into(), ty_full_name: "!0".into() },
+            ],
+            return_ty_full_name: "System.Int32".into(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    collection.insert_external("System.Runtime.dll", &comparison);
+    collection.insert_external("System.Collections.dll", &list_type_info());
+
+    let list = collection.get_or_allocate(&"List<int32>");
+    let compare = collection.get_or_allocate(&"fn(int32, int32) -> int32");
+    let predicate = collection.get_or_allocate(&"fn(int32) -> bool");
+
+    let (sort, _) = collection.find_method(&list, &"Sort".into(), std::slice::from_ref(&compare)).unwrap();
+    assert!(collection.is_delegate(&sort.args[0].1));
+    assert!(collection.find_method(&list, &"Sort".into(), &[predicate]).is_none());
+
+    // `Func` and `Action` are function types themselves, `Predicate` wasn't imported.
+    let info = collection.try_get_strong(&list).unwrap();
+    assert!(!info.methods.iter().any(|mi| mi.name.as_ref() == "Find"));
+
+    let for_each = info.methods.iter().find(|mi| mi.name.as_ref() == "ForEach").unwrap();
+    assert!(for_each.args[0].1.is_same(&collection.resolve_name("fn(int32)")));
+
+    let select = info.methods.iter().find(|mi| mi.name.as_ref() == "Select").unwrap();
+    assert!(select.args[0].1.is_same(&collection.resolve_name("fn(int32) -> string")));
+}
+
+#[test]
+fn managed_overloads_widen_and_box() {
+    let mut collection = TypeCollection::default();
+    let write_line = |ty: &str| SharpMethodInfo {
+        ident: "WriteLine".into(),
+        args: vec![SharpTypedIdent { ident: "value".into(), ty_full_name: ty.into() }],
+        return_ty_full_name: "System.Void".into(),
+        ..Default::default()
+    };
+
+    let console = SharpTypeInfo {
+        full_name: "System.Console".into(),
+        short_name: Some("Console".into()),
+        methods: vec![write_line("System.String"), write_line("System.Object"), write_line("System.Double"), write_line("System.Single")],
+        ..Default::default()
+    };
+
+    collection.insert_external("System.Console.dll", &console);
+
+    let ty = collection.resolve_name("Console");
+    let param_of = |arg: TypeId| collection.find_method(&ty, &"WriteLine".into(), &[arg]).unwrap().0.args[0].1.clone();
+
+    assert!(param_of(collection.get_int()).is_same(&collection.get_float()));
+    assert!(param_of(collection.get_double()).is_same(&collection.get_double()));
+    assert!(param_of(collection.get_bool()).is_same(&collection.get_object()));
+    assert!(param_of(collection.get_string()).is_same(&collection.get_string()));
+}
+
+#[test]
+fn managed_constructors() {
+    let mut collection = TypeCollection::default();
+    let ctor = |args: Vec<SharpTypedIdent>| SharpMethodInfo { ident: CONSTRUCTOR.into(), args, return_ty_full_name: "System.Void".into(), ..Default::default() };
+
+    let random = SharpTypeInfo {
+        full_name: "System.Random".into(),
+        short_name: Some("Random".into()),
+        constructors: vec![ctor(vec![]), ctor(vec![SharpTypedIdent { ident: "Seed".into(), ty_full_name: "System.Int32".into() }])],
+        ..Default::default()
+    };
+
+    let pending = collection.get_or_allocate(&"List<int32>");
+    collection.insert_external("System.Runtime.dll", &random);
+    collection.insert_external("System.Collections.dll", &list_type_info());
+
+    let ty = collection.resolve_name("Random");
+    let int = collection.get_int();
+
+    assert!(matches!(collection.resolve_constructor(&ty, &[], &[]), Overload::Found(_, mi, _) if mi.args.is_empty()));
+    assert!(matches!(collection.resolve_constructor(&ty, &[], std::slice::from_ref(&int)), Overload::Found(_, mi, _) if mi.args[0].1.is_same(&int)));
+    assert_eq!(collection.resolve_constructor(&ty, &[], &[collection.get_string()]), Overload::NotFound);
+
+    // Constructors of generic instances are substituted like their methods.
+    let list = collection.strengthen(&pending);
+    assert!(matches!(collection.resolve_constructor(&list, &[], &[int]), Overload::Found(_, mi, _) if mi.args[0].0.as_ref() == "capacity"));
+}
+
+#[test]
+fn managed_static_fields_and_properties() {
+    let mut collection = TypeCollection::default();
+    let static_field = |ident: &str, ty: &str, constant: Option<serde_json::Value>| SharpStaticFieldInfo {
+        ident: ident.into(),
+        ty_full_name: ty.into(),
+        is_read_only: true,
+        constant,
+    };
+
+    let math = SharpTypeInfo {
+        full_name: "System.Math".into(),
+        short_name: Some("Math".into()),
+        static_fields: vec![
+            static_field("PI", "System.Double", Some(serde_json::json!(std::f64::consts::PI))),
+            static_field("Tau", "System.Double", Some(serde_json::json!(6))),
+            static_field("Cache", "System.Double", None),
+            static_field("MaxValue", "System.Int64", Some(serde_json::json!(9))),
+        ],
+        properties: vec![SharpPropertyInfo { ident: "Title".into(), ty_full_name: "System.String".into(), is_static: true, can_read: true, can_write: true }],
+        ..Default::default()
+    };
+
+    let pending = collection.get_or_allocate(&"List<int32>");
+    collection.insert_external("System.Runtime.dll", &math);
+    collection.insert_external("System.Collections.dll", &list_type_info());
+
+    let ty = collection.resolve_name("Math");
+
+    let pi = collection.get_static_field(&ty, "PI").unwrap();
+    assert!(pi.ty.is_same(&collection.get_double()));
+    assert_eq!(pi.constant, Some(Literal::Float(std::f64::consts::PI)));
+
+    // Whole numbers are exported without a fraction.
+    assert_eq!(collection.get_static_field(&ty, "Tau").unwrap().constant, Some(Literal::Float(6.0)));
+    assert_eq!(collection.get_static_field(&ty, "Cache").unwrap().constant, None);
+
+    // `int64` isn't an arp type.
+    assert!(collection.get_static_field(&ty, "MaxValue").is_none());
+
+    let title = collection.find_property(&ty, "Title").unwrap();
+    assert!(title.is_static && title.can_write);
+    assert_eq!(title.setter().as_ref(), "set_Title");
+
+    let list = collection.strengthen(&pending);
+    assert!(collection.find_property(&list, "Count").is_some_and(|count| !count.can_write));
+    assert!(collection.find_property(&list, "Last").unwrap().ty.is_same(&collection.get_int()));
+}
+
+#[test]
+fn managed_params_and_defaults() {
+    let mut collection = TypeCollection::default();
+    let arg = |ident: &str, ty: &str| SharpTypedIdent { ident: ident.into(), ty_full_name: ty.into() };
+    let method = |name: &str, args: Vec<SharpTypedIdent>, defaults: Vec<Option<serde_json::Value>>, is_params: bool| SharpMethodInfo {
+        ident: name.into(),
+        args,
+        return_ty_full_name: "System.Void".into(),
+        defaults,
+        is_params,
+    };
+
+    let console = SharpTypeInfo {
+        full_name: "System.Console".into(),
+        short_name: Some("Console".into()),
+        methods: vec![
+            method("WriteLine", vec![arg("format", "System.String"), arg("arg0", "System.Object")], vec![], false),
+            method("WriteLine", vec![arg("format", "System.String"), arg("arg", "System.Object[]")], vec![], true),
+            // `null` can't be spelled in arp, `prefix` has to be passed.
+            method("Beep", vec![arg("frequency", "System.Int32"), arg("duration", "System.Int32"), arg("prefix", "System.String")],
+                vec![None, Some(200.into()), Some(serde_json::Value::Null)], false),
+        ],
+        ..Default::default()
+    };
+
+    collection.insert_external("System.Console.dll", &console);
+
+    let ty = collection.resolve_name("Console");
+    let (int, string) = (collection.get_int(), collection.get_string());
+    let write_line = |arg_types: &[TypeId]| match collection.resolve_overload(&ty, &"WriteLine".into(), &[], arg_types) {
+        Overload::Found(_, mi, _) => (mi.is_params, collection.bind_call(mi, &[], arg_types).unwrap()),
+        overload => panic!("Expected a single overload, found {overload:?}"),
+    };
+
+    // A single argument is passed as it is, more are collected into the `params` array.
+    assert_eq!(write_line(&[string.clone(), int.clone()]), (false, vec![BoundArgument::Argument(0), BoundArgument::Argument(1)]));
+    assert_eq!(write_line(&[string.clone(), int.clone(), string.clone()]), (true, vec![BoundArgument::Argument(0), BoundArgument::Params(vec![1, 2])]));
+    assert_eq!(write_line(std::slice::from_ref(&string)), (true, vec![BoundArgument::Argument(0), BoundArgument::Params(vec![])]));
+
+    let beep = &collection.try_get_strong(&ty).unwrap().methods[2];
+    let names = [None, Some("prefix".into())];
+    assert_eq!(beep.defaults, [None, Some(Literal::Integer(200)), None]);
+    assert_eq!(
+        collection.bind_call(beep, &names, &[int.clone(), string.clone()]),
+        Some(vec![BoundArgument::Argument(0), BoundArgument::Default(Literal::Integer(200)), BoundArgument::Argument(1)]),
+    );
+    assert_eq!(collection.resolve_overload(&ty, &"Beep".into(), &[], &[int]), Overload::NotFound);
+}
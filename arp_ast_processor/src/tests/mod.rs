@@ -9,4 +9,23 @@ pub mod ty_resolver;
 pub mod validator;
 pub mod imports;
 mod managed_imports;
-mod itegration;
\ No newline at end of file
+mod itegration;
+pub mod unions;
+pub mod prelude;
+pub mod generics;
+pub mod inheritance;
+pub mod traits;
+pub mod operators;
+pub mod structs;
+pub mod enums;
+pub mod arrays;
+pub mod tuples;
+pub mod closures;
+pub mod extensions;
+pub mod mutability;
+pub mod scopes;
+pub mod type_check;
+pub mod control_flow;
+pub mod inference;
+pub mod casts;
+pub mod arguments;
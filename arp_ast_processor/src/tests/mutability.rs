@@ -0,0 +1,149 @@
+use std::ops::Range;
+
+use crate::{errors::ProcessingError, validations::ValidationError};
+
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn mutable_bindings() {
+    get_file_ast("
+class Counter {
+    count: int32
+}
+
+impl Counter {
+    fn increment(mut this) {
+        this.count = this.count + 1;
+    }
+
+    fn twice(mut this) {
+        this.increment();
+        this.increment();
+    }
+}
+
+fn main() {
+    let mut x = 1;
+    x = 2;
+
+    let mut counter = Counter { count: 0 };
+    counter.count = x;
+    counter.twice();
+
+    let mut (a, b) = (1, 2);
+    a = b;
+}
+", Some("test/mutability/mutable")).unwrap();
+}
+
+#[test]
+fn reassign_immutable() {
+    let source = "
+fn main() {
+    let x = 1;
+    x = 2;
+}
+";
+    let error = get_file_ast(source, Some("test/mutability/reassign")).unwrap_err();
+
+    match error {
+        ProcessingError::Validation(ValidationError::AssignToImmutable { name, binding, write }) => {
+            assert_eq!(name.as_ref(), "x");
+            assert_eq!(&source[Range::from(binding)], "let x = 1;");
+            assert_eq!(&source[Range::from(write)], "x = 2;");
+        },
+        error => panic!("unexpected error {error:?}"),
+    }
+}
+
+#[test]
+#[should_panic]
+fn assign_parameter() {
+    get_file_ast("
+fn set(x: int32) {
+    x = 2;
+}
+", Some("test/mutability/parameter")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn assign_field_of_immutable() {
+    get_file_ast("
+class Point {
+    x: int32
+}
+
+fn main() {
+    let point = Point { x: 1 };
+    point.x = 2;
+}
+", Some("test/mutability/field")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn assign_class_field_without_mut_this() {
+    get_file_ast("
+class Counter {
+    count: int32
+}
+
+impl Counter {
+    fn increment(this) {
+        this.count = this.count + 1;
+    }
+}
+", Some("test/mutability/immutable_this")).unwrap();
+}
+
+#[test]
+fn mut_method_on_immutable() {
+    let source = "
+struct Counter {
+    count: int32
+}
+
+impl Counter {
+    fn increment(mut this) {
+        this.count = this.count + 1;
+    }
+}
+
+fn main() {
+    let counter = Counter { count: 0 };
+    counter.increment();
+}
+";
+    let error = get_file_ast(source, Some("test/mutability/mut_method")).unwrap_err();
+
+    match error {
+        ProcessingError::Validation(ValidationError::MutatingCallOnImmutable { method, name, binding, write }) => {
+            assert_eq!((method.as_ref(), name.as_ref()), ("increment", "counter"));
+            assert!(source[Range::from(binding)].starts_with("let counter"));
+            assert_eq!(&source[Range::from(write)], "counter.increment()");
+        },
+        error => panic!("unexpected error {error:?}"),
+    }
+}
+
+#[test]
+#[should_panic]
+fn mut_method_from_immutable_this() {
+    get_file_ast("
+class Counter {
+    count: int32
+}
+
+impl Counter {
+    fn increment(mut this) {
+        this.count = this.count + 1;
+    }
+
+    fn twice(this) {
+        this.increment();
+    }
+}
+", Some("test/mutability/mut_method_this")).unwrap();
+}
@@ -0,0 +1,161 @@
+use crate::types::function::Function;
+
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn add_method() {
+    get_file_ast("
+class Vector {
+    x: int32,
+    y: int32
+}
+
+impl Vector {
+    fn add(this, other: Vector) -> Vector {
+        return Vector { x: this.x + other.x, y: this.y + other.y };
+    }
+
+    fn mul(this, factor: int32) -> Vector {
+        return Vector { x: this.x * factor, y: this.y * factor };
+    }
+}
+
+fn main() {
+    let a = Vector { x: 1, y: 2 };
+    let b = Vector { x: 3, y: 4 };
+    let sum = a + b;
+    let scaled = sum * 2;
+    let x = scaled.x;
+}
+", Some("test/operators/add")).unwrap();
+}
+
+#[test]
+fn operator_trait() {
+    get_file_ast("
+trait Equatable {
+    fn eq(this, other: Point) -> bool;
+}
+
+class Point {
+    x: int32
+}
+
+impl Equatable for Point {
+    fn eq(this, other: Point) -> bool {
+        return this.x == other.x;
+    }
+}
+
+fn main() {
+    let a = Point { x: 1 };
+    let b = Point { x: 1 };
+    let same = a == b;
+    let different = a != b;
+}
+", Some("test/operators/trait")).unwrap();
+}
+
+#[test]
+fn compare_method() {
+    get_file_ast("
+class Version {
+    major: int32
+}
+
+impl Version {
+    fn cmp(this, other: Version) -> int32 {
+        return this.major - other.major;
+    }
+}
+
+fn main() {
+    let a = Version { major: 1 };
+    let b = Version { major: 2 };
+    let older = a < b;
+    let newer = a >= b;
+}
+", Some("test/operators/cmp")).unwrap();
+}
+
+#[test]
+fn string_concat() {
+    get_file_ast("
+fn main() {
+    let greeting = \"Hello, \" + \"world\";
+    let again = greeting + \"!\";
+}
+", Some("test/operators/concat")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn missing_operator_method() {
+    get_file_ast("
+class Vector {
+    x: int32
+}
+
+fn main() {
+    let a = Vector { x: 1 };
+    let b = Vector { x: 2 };
+    let sum = a + b;
+}
+", Some("test/operators/missing")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn cmp_must_return_int() {
+    get_file_ast("
+class Point {
+    x: int32
+}
+
+impl Point {
+    fn cmp(this, other: Point) -> bool {
+        return true;
+    }
+}
+
+fn main() {
+    let a = Point { x: 1 };
+    let b = Point { x: 2 };
+    let less = a < b;
+}
+", Some("test/operators/cmp_bool")).unwrap();
+}
+
+#[test]
+fn primitive_operands_are_not_spilled() {
+    let ast = get_file_ast("
+struct Square {
+    side: int32
+}
+
+impl Square {
+    fn area(this) -> int32 {
+        return this.side * this.side;
+    }
+
+    fn double(this) -> int32 {
+        return this.area() * 2;
+    }
+}
+
+fn main() {
+    let square = Square { side: 2 };
+    let area = square.area() + 1;
+}
+", Some("test/operators/primitive_operands")).unwrap();
+
+    for name in ["double", "main"] {
+        let func = ast.get_nodes_of_kind::<Function>().into_iter().find(|func| ast.get(func).name.as_ref() == name).unwrap();
+        let collection = &ast.get_arp_file_in_parent(func.as_weak()).unwrap().type_collection;
+        let registers = ast.get(&func).registers.iter().map(|(_, ty)| collection.get_source_name(ty).unwrap_or_default().to_string()).collect::<Vec<_>>();
+
+        let expected: &[&str] = if name == "main" { &["Square", "int32"] } else { &[] };
+        assert_eq!(registers, expected);
+    }
+}
@@ -0,0 +1,153 @@
+use arp_types::sources::Source;
+
+use crate::build_multiple_sources;
+
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn option_propagation() {
+    get_file_ast("
+class Parser { }
+
+impl Parser {
+    fn parse(x: int32) -> Option<int32> {
+        return Option<int32>.Some { value: x };
+    }
+
+    fn twice(x: int32) -> Option<int32> {
+        let value = Parser.parse(x)?;
+        return Option<int32>.Some { value: value * 2 };
+    }
+}
+", Some("test/prelude/option")).unwrap();
+}
+
+#[test]
+fn result_propagation() {
+    get_file_ast("
+class Parser { }
+
+impl Parser {
+    fn parse(x: int32) -> Result<int32, string> {
+        return Result<int32, string>.Err { error: \"nope\" };
+    }
+
+    fn twice(x: int32) -> Result<bool, string> {
+        let value = Parser.parse(x)?;
+        return Result<bool, string>.Ok { value: value > 2 };
+    }
+}
+", Some("test/prelude/result")).unwrap();
+}
+
+#[test]
+fn option_match() {
+    get_file_ast("
+fn main() {
+    let opt = Option<int32>.None { };
+    let value = match opt { 
+        Option<int32>.Some { value } => value, 
+        Option<int32>.None => 0,
+    };
+    let nested: Option<Option<string>> = Option<Option<string>>.None { };
+}
+", Some("test/prelude/match")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn propagation_on_plain_value() {
+    get_file_ast("
+class Parser { }
+
+impl Parser {
+    fn twice(x: int32) -> Option<int32> {
+        let value = x?;
+        return Option<int32>.Some { value: value };
+    }
+}
+", Some("test/prelude/plain")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn propagation_into_plain_function() {
+    get_file_ast("
+class Parser { }
+
+impl Parser {
+    fn twice(x: Option<int32>) -> int32 {
+        return x?;
+    }
+}
+", Some("test/prelude/plain_function")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn propagation_with_different_errors() {
+    get_file_ast("
+class Parser { }
+
+impl Parser {
+    fn twice(x: Result<int32, bool>) -> Result<int32, string> {
+        let value = x?;
+        return Result<int32, string>.Ok { value: value };
+    }
+}
+", Some("test/prelude/different_errors")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn option_used_as_value() {
+    get_file_ast("
+fn main() {
+    let opt = Option<int32>.None { };
+    let value: int32 = opt;
+}
+", Some("test/prelude/used_as_value")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn option_returned_as_value() {
+    get_file_ast("
+class Parser { }
+
+impl Parser {
+    fn get(x: Option<int32>) -> int32 {
+        return x;
+    }
+}
+", Some("test/prelude/returned_as_value")).unwrap();
+}
+
+#[test]
+fn option_across_files() {
+    let sources = [
+        Source::new_inline("Main.arp", "
+        from Parser import Parser
+
+        class App { }
+
+        impl App {
+            fn run() -> Option<int32> {
+                let value = Parser.parse(1)?;
+                return Option<int32>.Some { value: value };
+            }
+        }"),
+
+        Source::new_inline("Parser.arp", "
+        class Parser { }
+
+        impl Parser {
+            fn parse(x: int32) -> Option<int32> {
+                return Option<int32>.Some { value: x };
+            }
+        }"),
+    ];
+
+    build_multiple_sources(&sources).unwrap();
+}
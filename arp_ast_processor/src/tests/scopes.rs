@@ -0,0 +1,90 @@
+use crate::types::{ast_node_value::Ast, function::Function};
+
+use super::ty_resolver::get_file_ast;
+
+
+/// Source names of the register types of `main`.
+fn main_registers(ast: &Ast) -> Vec<String> {
+    let main = ast.get_nodes_of_kind::<Function>().into_iter().find(|func| ast.get(func).name.as_ref() == "main").unwrap();
+    let collection = &ast.get_arp_file_in_parent(main.as_weak()).unwrap().type_collection;
+
+    ast.get(&main).registers.iter().map(|(_, ty)| collection.get_source_name(ty).unwrap_or_default().to_string()).collect()
+}
+
+#[test]
+fn shadowing_in_same_block() {
+    let ast = get_file_ast("
+fn main() {
+    let x = 1;
+    let x = x + 1;
+    let x = \"two\";
+    let y: string = x;
+}
+", Some("test/scopes/shadowing")).unwrap();
+
+    assert_eq!(main_registers(&ast), vec!["int32", "int32", "string", "string"]);
+}
+
+#[test]
+fn block_shadows_outer_variable() {
+    let ast = get_file_ast("
+fn main() {
+    let x: int32 = 1;
+    if x > 0 {
+        let x: string = \"inner\";
+        let y: string = x;
+    }
+    let z: int32 = x;
+}
+", Some("test/scopes/block_shadowing")).unwrap();
+
+    assert_eq!(main_registers(&ast), vec!["int32", "string", "string", "int32"]);
+}
+
+#[test]
+fn registers_are_reused_after_block() {
+    let ast = get_file_ast("
+fn main() {
+    let flag = true;
+    if flag {
+        let a = 1;
+        let b = \"b\";
+    } else {
+        let c = 2;
+        let d = 3;
+    }
+    let e = 4;
+}
+", Some("test/scopes/reuse")).unwrap();
+
+    assert_eq!(main_registers(&ast), vec!["bool", "int32", "string", "int32"]);
+}
+
+#[test]
+fn outer_variables_are_visible_in_loops() {
+    get_file_ast("
+fn main() {
+    let mut i = 0;
+    while i < 10 {
+        let next: int32 = i + 1;
+        i = next;
+    }
+}
+", Some("test/scopes/loop")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn variable_invisible_outside_block() {
+    get_file_ast("
+fn take(x: int32) {
+}
+
+fn main() {
+    {
+        let x = 1;
+    }
+    take(x);
+}
+", Some("test/scopes/invisible")).unwrap();
+}
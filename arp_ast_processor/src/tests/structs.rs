@@ -0,0 +1,117 @@
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn struct_fields() {
+    get_file_ast("
+struct Point {
+    x: int32,
+    y: int32
+}
+
+impl Point {
+    fn length(this) -> int32 {
+        return this.x + this.y;
+    }
+
+    fn move_by(mut this, dx: int32) {
+        this.x = this.x + dx;
+    }
+}
+
+fn main() {
+    let mut point = Point { x: 1, y: 2 };
+    let copy = point;
+    point.move_by(3);
+    point.x = 4;
+    let length = copy.length();
+}
+", Some("test/structs/fields")).unwrap();
+}
+
+#[test]
+fn nested_struct() {
+    get_file_ast("
+struct Point {
+    x: int32,
+    y: int32
+}
+
+struct Line {
+    start: Point,
+    end: Point
+}
+
+impl Line {
+    fn origin() -> Line {
+        return Line { start: Point { x: 0, y: 0 }, end: Point { x: 0, y: 0 } };
+    }
+}
+
+fn main() {
+    let mut line = Line.origin();
+    line.start.x = 3;
+    let x = Line.origin().end.x;
+}
+", Some("test/structs/nested")).unwrap();
+}
+
+#[test]
+fn generic_struct() {
+    get_file_ast("
+struct Pair<K, V> {
+    key: K,
+    value: V
+}
+
+fn main() {
+    let pair = Pair<int32, string> { key: 1, value: \"one\" };
+    let key = pair.key;
+}
+", Some("test/structs/generic")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn mutation_without_mut_this() {
+    get_file_ast("
+struct Counter {
+    count: int32
+}
+
+impl Counter {
+    fn increment(this) {
+        this.count = this.count + 1;
+    }
+}
+", Some("test/structs/immutable_this")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn inherit_from_struct() {
+    get_file_ast("
+struct Point {
+    x: int32
+}
+
+class Point3 : Point {
+    z: int32
+}
+", Some("test/structs/base")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn struct_equality_without_eq() {
+    get_file_ast("
+struct Point {
+    x: int32
+}
+
+fn main() {
+    let a = Point { x: 1 };
+    let same = a == a;
+}
+", Some("test/structs/equality")).unwrap();
+}
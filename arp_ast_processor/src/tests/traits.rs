@@ -0,0 +1,235 @@
+use arp_types::sources::Source;
+
+use crate::build_multiple_sources;
+
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn trait_implementation() {
+    get_file_ast("
+trait Printable {
+    fn print(this) -> string;
+}
+
+class Point {
+    x: int32
+}
+
+impl Printable for Point {
+    fn print(this) -> string {
+        return \"point\";
+    }
+}
+
+fn main() {
+    let point = Point { x: 1 };
+    let text = point.print();
+}
+", Some("test/traits/implementation")).unwrap();
+}
+
+#[test]
+fn default_method() {
+    get_file_ast("
+trait Named {
+    fn name(this) -> string;
+
+    fn greeting(this) -> string {
+        return this.name();
+    }
+}
+
+class Dog {
+    age: int32
+}
+
+impl Named for Dog {
+    fn name(this) -> string {
+        return \"dog\";
+    }
+}
+
+fn main() {
+    let dog = Dog { age: 3 };
+    let greeting = dog.greeting();
+}
+", Some("test/traits/default")).unwrap();
+}
+
+#[test]
+fn trait_as_bound() {
+    get_file_ast("
+trait Printable {
+    fn print(this) -> string;
+}
+
+class Point {
+    x: int32
+}
+
+impl Printable for Point {
+    fn print(this) -> string {
+        return \"point\";
+    }
+}
+
+class Printer<T: Printable> {
+    value: T
+}
+
+impl Printer<T> {
+    fn run(this) -> string {
+        return this.value.print();
+    }
+}
+
+fn main() {
+    let printer = Printer<Point> { value: Point { x: 1 } };
+    let text = printer.run();
+}
+", Some("test/traits/bound")).unwrap();
+}
+
+#[test]
+fn inherited_implementation() {
+    get_file_ast("
+trait Printable {
+    fn print(this) -> string;
+}
+
+class Shape {
+    sides: int32
+}
+
+impl Printable for Shape {
+    fn print(this) -> string {
+        return \"shape\";
+    }
+}
+
+class Square : Shape {
+    size: int32
+}
+
+class Printer<T: Printable> {
+    value: T
+}
+
+fn main() {
+    let printer = Printer<Square> { value: Square { sides: 4, size: 2 } };
+    let text = printer.value.print();
+}
+", Some("test/traits/inherited")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn missing_method() {
+    get_file_ast("
+trait Printable {
+    fn print(this) -> string;
+}
+
+class Point {
+    x: int32
+}
+
+impl Printable for Point { }
+", Some("test/traits/missing")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn extra_method() {
+    get_file_ast("
+trait Printable {
+    fn print(this) -> string;
+}
+
+class Point {
+    x: int32
+}
+
+impl Printable for Point {
+    fn print(this) -> string {
+        return \"point\";
+    }
+
+    fn length(this) -> int32 {
+        return 0;
+    }
+}
+", Some("test/traits/extra")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn signature_mismatch() {
+    get_file_ast("
+trait Printable {
+    fn print(this) -> string;
+}
+
+class Point {
+    x: int32
+}
+
+impl Printable for Point {
+    fn print(this) -> int32 {
+        return 0;
+    }
+}
+", Some("test/traits/mismatch")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn implement_class() {
+    get_file_ast("
+class Printable {
+    x: int32
+}
+
+class Point {
+    x: int32
+}
+
+impl Printable for Point { }
+", Some("test/traits/not_trait")).unwrap();
+}
+
+#[test]
+fn imported_trait() {
+    let sources = [
+        Source::new_inline("Main.arp", "
+from Shapes import Point
+
+fn main() {
+    let point = Point { x: 1 };
+    let text = point.describe();
+}
+"),
+        Source::new_inline("Shapes.arp", "
+trait Printable {
+    fn print(this) -> string;
+
+    fn describe(this) -> string {
+        return this.print();
+    }
+}
+
+class Point {
+    x: int32
+}
+
+impl Printable for Point {
+    fn print(this) -> string {
+        return \"point\";
+    }
+}
+"),
+    ];
+
+    build_multiple_sources(&sources).unwrap();
+}
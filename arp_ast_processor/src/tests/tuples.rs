@@ -0,0 +1,72 @@
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn literal_and_fields() {
+    get_file_ast("
+fn main() {
+    let pair = (1, \"one\");
+    let number = pair.0;
+    let name = pair.1;
+    let nested = ((1, 2), true);
+    let inner = nested.0.1 + number;
+}
+", Some("test/tuples/literal")).unwrap();
+}
+
+#[test]
+fn destructuring_let() {
+    get_file_ast("
+fn main() {
+    let (number, name) = (1, \"one\");
+    let (first, _, third) = (number, name, 2.5);
+    let (x, y): (int32, int32) = (number, number + 1);
+    let sum = x + y;
+}
+", Some("test/tuples/let")).unwrap();
+}
+
+#[test]
+fn returned_and_destructured_parameter() {
+    get_file_ast("
+class Pairs {
+    count: int32
+}
+
+impl Pairs {
+    fn swap((a, b): (int32, string)) -> (string, int32) {
+        return (b, a);
+    }
+
+    fn first(pair: (string, int32)) -> string {
+        return pair.0;
+    }
+}
+
+fn main() {
+    let (name, number) = Pairs.swap((1, \"one\"));
+    let swapped = Pairs.swap((number, name));
+    let first = Pairs.first(swapped);
+}
+", Some("test/tuples/parameter")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn arity_mismatch() {
+    get_file_ast("
+fn main() {
+    let (a, b) = (1, 2, 3);
+}
+", Some("test/tuples/arity")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn destructure_non_tuple() {
+    get_file_ast("
+fn main() {
+    let (a, b) = 1;
+}
+", Some("test/tuples/non_tuple")).unwrap();
+}
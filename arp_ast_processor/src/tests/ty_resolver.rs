@@ -1,6 +1,6 @@
 use arp_types::sources::Source;
 
-use crate::{chumsky_folder::ChumskyNodeVisitor, errors::ProcessingError, post::post_process, type_resolver::resolve_types_loop, types::ast_node_value::Ast, validations};
+use crate::{chumsky_folder::ChumskyNodeVisitor, errors::ProcessingError, post::post_process, type_resolver::resolve_types, types::{ast_node_value::Ast, expression::{Expression, Literal}}, validations};
 
 pub fn get_file_ast(input: &str, tag: Option<&str>) -> Result<Ast, ProcessingError> {
     let source = Source::new_inline(tag.unwrap_or("untagged"), input);
@@ -9,11 +9,11 @@ pub fn get_file_ast(input: &str, tag: Option<&str>) -> Result<Ast, ProcessingErr
     let mut visitor = ChumskyNodeVisitor::default();
     visitor.fold_file(&chumsky_root);
     let ast = visitor.consume();
-    let ast = resolve_types_loop(ast).unwrap();
+    let ast = resolve_types(ast)?;
     let ast = post_process(ast)?;
     
     dbg!(&ast);
-    validations::validate(&ast).unwrap();
+    validations::validate(&ast)?;
     
     Ok(ast)
 }
@@ -225,4 +225,27 @@ fn mismatched_types_bin() {
 #[should_panic]
 fn unexpected_type_un() {
     get_file_ast("fn func() {let x = -false;}", Some("test/expression_statement")).unwrap();
-}
\ No newline at end of file
+}
+
+#[test]
+fn expression_types_are_cached() {
+    let ast = get_file_ast("class Point { x: int32 } fn func() { let point = Point { x: 1 }; let y = point.x + 2; }", Some("test/cached_types")).unwrap();
+    let expressions = ast.get_children_of_kind::<Expression, _>(ast.get_root_index());
+
+    assert!(!expressions.is_empty());
+    assert!(expressions.iter().all(|expr| ast.get_cached_type(*expr).is_some_and(|ty| ty.is_strong())));
+    assert!(ast.cached_types_in_sync());
+}
+
+#[test]
+fn stale_cached_types() {
+    let mut ast = get_file_ast("fn func() { let x = 1; }", Some("test/cached_types")).unwrap();
+    let literal = ast.get_children_of_kind::<Expression, _>(ast.get_root_index())
+        .into_iter()
+        .find(|expr| matches!(ast.get(expr), Expression::Literal(_)))
+        .unwrap();
+
+    ast.set_value(&literal, || Expression::Literal(Literal::String("one".into())));
+
+    assert!(!ast.cached_types_in_sync());
+}
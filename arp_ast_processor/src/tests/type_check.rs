@@ -0,0 +1,382 @@
+use crate::{errors::ProcessingError, types::expression::{Expression, Literal}, validations::ValidationError};
+
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn compatible_types() {
+    get_file_ast("
+class Shape {
+    sides: int32
+}
+
+class Square : Shape {
+    size: int32
+}
+
+impl Shape {
+    fn describe(shape: Shape) -> int32 {
+        return shape.sides;
+    }
+}
+
+fn main() {
+    let mut shape: Shape = Square { sides: 4, size: 2 };
+    shape = Shape { sides: 3 };
+    shape.sides = 5;
+    let sides = Shape.describe(Square { sides: 4, size: 1 });
+
+    let mut i = 0;
+    while i < sides {
+        i = i + 1;
+    }
+}
+", Some("test/type_check/compatible")).unwrap();
+}
+
+#[test]
+fn assign_wrong_type() {
+    let error = get_file_ast("
+fn main() {
+    let mut x = 1;
+    x = \"str\";
+}
+", Some("test/type_check/assignment")).unwrap_err();
+
+    match error {
+        ProcessingError::Validation(ValidationError::TypeMismatch { expected, actual }) => {
+            assert_eq!((expected.as_ref(), actual.as_ref()), ("int32", "string"));
+        },
+        error => panic!("unexpected error {error:?}"),
+    }
+}
+
+#[test]
+#[should_panic]
+fn assign_field_wrong_type() {
+    get_file_ast("
+class Point {
+    x: int32
+}
+
+fn main() {
+    let mut point = Point { x: 1 };
+    point.x = true;
+}
+", Some("test/type_check/field")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn declared_type_mismatch() {
+    get_file_ast("
+fn main() {
+    let x: string = 1;
+}
+", Some("test/type_check/declaration")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn return_wrong_type() {
+    get_file_ast("
+fn number() -> int32 {
+    return \"one\";
+}
+", Some("test/type_check/return")).unwrap();
+}
+
+#[test]
+fn argument_wrong_type() {
+    let error = get_file_ast("
+class Point {
+    x: int32
+}
+
+impl Point {
+    fn move_by(mut this, dx: int32, dy: int32) {
+        this.x = this.x + dx + dy;
+    }
+}
+
+fn main() {
+    let mut point = Point { x: 1 };
+    point.move_by(1, \"two\");
+}
+", Some("test/type_check/argument")).unwrap_err();
+
+    match error {
+        ProcessingError::Validation(ValidationError::ArgumentMismatch { method, position, expected, actual }) => {
+            assert_eq!((method.as_ref(), position, expected.as_ref(), actual.as_ref()), ("move_by", 1, "int32", "string"));
+        },
+        error => panic!("unexpected error {error:?}"),
+    }
+}
+
+#[test]
+fn function_argument_wrong_type() {
+    // Inferred and declared locals report the same mismatch.
+    for main in ["let x = twice(\"two\");", "let x: int32 = twice(1, \"two\");"] {
+        let error = get_file_ast(&format!("
+fn twice(a: int32, b: int32 = 1) -> int32 {{
+    return a + a;
+}}
+
+fn main() {{
+    {main}
+}}
+"), Some("test/type_check/function_argument")).unwrap_err();
+
+        match error {
+            ProcessingError::Validation(ValidationError::ArgumentMismatch { method, expected, actual, .. }) => {
+                assert_eq!((method.as_ref(), expected.as_ref(), actual.as_ref()), ("twice", "int32", "string"));
+            },
+            error => panic!("unexpected error {error:?}"),
+        }
+    }
+}
+
+#[test]
+fn void_value_stored() {
+    let cases = [
+        ("let x = log();", "a value"),
+        ("let x: int32 = log();", "int32"),
+        ("let mut x = 1; x = log();", "int32"),
+    ];
+
+    for (main, expected) in cases {
+        let error = get_file_ast(&format!("
+fn log() {{}}
+
+fn main() {{
+    {main}
+}}
+"), Some("test/type_check/void_value")).unwrap_err();
+
+        match error {
+            ProcessingError::Validation(ValidationError::TypeMismatch { expected: found, actual }) => {
+                assert_eq!((found.as_ref(), actual.as_ref()), (expected, "void"));
+            },
+            error => panic!("unexpected error {error:?}"),
+        }
+    }
+}
+
+#[test]
+fn trailing_expression_type() {
+    get_file_ast("
+fn same(a: int32) -> int32 {
+    a
+}
+", Some("test/type_check/trailing")).unwrap();
+
+    let error = get_file_ast("
+fn number() -> int32 {
+    \"one\"
+}
+", Some("test/type_check/trailing_wrong")).unwrap_err();
+
+    match error {
+        ProcessingError::Validation(ValidationError::TypeMismatch { expected, actual }) => {
+            assert_eq!((expected.as_ref(), actual.as_ref()), ("int32", "string"));
+        },
+        error => panic!("unexpected error {error:?}"),
+    }
+}
+
+#[test]
+#[should_panic]
+fn condition_not_bool() {
+    get_file_ast("
+fn main() {
+    if 1 {
+    }
+}
+", Some("test/type_check/condition")).unwrap();
+}
+
+#[test]
+fn overloads_rank_conversions() {
+    get_file_ast("
+class Printer {}
+
+impl Printer {
+    fn show(value: int32) -> int32 {
+        return value;
+    }
+
+    fn show(value: float64) -> string {
+        return \"float64\";
+    }
+
+    fn show(value: object) -> bool {
+        return true;
+    }
+}
+
+fn main() {
+    let exact: int32 = Printer.show(1);
+    let widened: string = Printer.show(1.5);
+    let referenced: bool = Printer.show(\"text\");
+    let boxed: bool = Printer.show(true);
+}
+", Some("test/type_check/overloads")).unwrap();
+}
+
+#[test]
+fn ambiguous_overloads() {
+    let error = get_file_ast("
+class Printer {}
+
+impl Printer {
+    fn pick(a: float32, b: object) {}
+
+    fn pick(a: object, b: float32) {}
+}
+
+fn main() {
+    Printer.pick(1, 2);
+}
+", Some("test/type_check/ambiguous")).unwrap_err();
+
+    match error {
+        ProcessingError::Validation(ValidationError::AmbiguousCall { method, candidates }) => {
+            assert_eq!(method.as_ref(), "pick");
+            assert_eq!(candidates, vec!["pick(float32, object)".into(), "pick(object, float32)".into()]);
+        },
+        error => panic!("unexpected error {error:?}"),
+    }
+}
+
+#[test]
+fn value_types_box_into_object() {
+    get_file_ast("
+trait Printable {
+    fn print(this) -> string;
+}
+
+struct Point {
+    x: int32
+}
+
+impl Printable for Point {
+    fn print(this) -> string {
+        return \"point\";
+    }
+}
+
+fn describe(value: object, printable: Printable) -> object {
+    return 1;
+}
+
+fn main() {
+    let mut any: object = 1;
+    any = true;
+    let printable: Printable = Point { x: 1 };
+    let result = describe(2.5, Point { x: 2 });
+}
+", Some("test/type_check/boxing")).unwrap();
+}
+
+#[test]
+fn new_needs_a_constructor() {
+    let error = get_file_ast("
+class Point {
+    x: int32
+}
+
+fn main() {
+    let point = new Point(1);
+}
+", Some("test/type_check/new")).unwrap_err();
+
+    match error {
+        ProcessingError::Validation(ValidationError::NoConstructor(ty, args)) => {
+            assert_eq!(ty.as_ref(), "Point");
+            assert_eq!(args.as_ref(), "int32");
+        },
+        error => panic!("unexpected error {error:?}"),
+    }
+}
+
+#[test]
+fn numbers_widen_implicitly() {
+    get_file_ast("
+class Reading {
+    value: float64
+}
+
+fn half() -> float64 {
+    return 1;
+}
+
+fn main() {
+    let exact: float64 = 1;
+    let mut single: float32 = 2;
+    single = 3;
+    let mut reading = Reading { value: 4 };
+    reading.value = single;
+}
+", Some("test/type_check/widening")).unwrap();
+
+    // Narrowing loses information, it needs an explicit `as`.
+    let error = get_file_ast("
+fn main() {
+    let whole: int32 = 2.5;
+}
+", Some("test/type_check/narrowing")).unwrap_err();
+
+    match error {
+        ProcessingError::Validation(ValidationError::TypeMismatch { expected, actual }) => {
+            assert_eq!((expected.as_ref(), actual.as_ref()), ("int32", "float32"));
+        },
+        error => panic!("unexpected error {error:?}"),
+    }
+}
+
+#[test]
+fn positional_field_values() {
+    let ast = get_file_ast("
+class Shape {
+    sides: int32
+}
+
+class Square : Shape {
+    size: int32
+}
+
+fn main() {
+    let square = Square { 4, 2 };
+    let named = Square { size: 3, 4 };
+}
+", Some("test/type_check/positional")).unwrap();
+
+    // Values without a name fill the remaining fields in declaration order, base fields first.
+    let fields = ast.get_nodes_of_kind::<Expression>().into_iter().filter_map(|expr| match ast.get(&expr) {
+        Expression::Construct { args, positional, .. } => {
+            assert!(positional.is_empty());
+            Some(args.iter().map(|(field, value)| (field.as_ref().to_string(), ast.get(value).clone())).collect::<Vec<_>>())
+        },
+        _ => None,
+    }).collect::<Vec<_>>();
+
+    let value = |field: &str, value: i64| (field.to_string(), Expression::Literal(Literal::Integer(value)));
+    assert_eq!(fields, [vec![value("sides", 4), value("size", 2)], vec![value("size", 3), value("sides", 4)]]);
+
+    let error = get_file_ast("
+class Shape {
+    sides: int32
+}
+
+fn main() {
+    let shape = Shape { 4, 2 };
+}
+", Some("test/type_check/positional_count")).unwrap_err();
+
+    match error {
+        ProcessingError::Validation(ValidationError::TooManyFieldValues { ty, fields, values }) => {
+            assert_eq!((ty.as_ref(), fields, values), ("Shape", 1, 2));
+        },
+        error => panic!("unexpected error {error:?}"),
+    }
+}
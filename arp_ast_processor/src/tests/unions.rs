@@ -0,0 +1,116 @@
+use crate::{errors::ProcessingError, validations::ValidationError};
+
+use super::ty_resolver::get_file_ast;
+
+
+#[test]
+fn union_match() {
+    get_file_ast("
+union Shape { 
+    Circle { r: int32 }, 
+    Rect { w: int32, h: int32 },
+    Empty,
+}
+
+class Geometry { }
+
+impl Geometry {
+    fn area(shape: Shape) -> int32 {
+        return match shape { 
+            Shape.Circle { r } => r * r * 3, 
+            Shape.Rect { w: width, h } => width * h,
+            Shape.Empty => 0,
+        };
+    }
+}
+
+fn main() {
+    let circle = Shape.Circle { r: 2 };
+    let a = Geometry.area(circle);
+}
+", Some("test/unions/match")).unwrap();
+}
+
+#[test]
+fn union_match_wildcard() {
+    get_file_ast("
+union Shape { Circle { r: int32 }, Empty }
+
+fn main() {
+    let shape = Shape.Empty { };
+    let x = match shape { Shape.Circle { r } => r, _ => 0 };
+    let y = match shape { other => 1 };
+}
+", Some("test/unions/wildcard")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn union_match_non_exhaustive() {
+    get_file_ast("
+union Shape { Circle { r: int32 }, Empty }
+
+fn main() {
+    let shape = Shape.Empty { };
+    let x = match shape { Shape.Circle { r } => r };
+}
+", Some("test/unions/non_exhaustive")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn union_match_unreachable_after_wildcard() {
+    get_file_ast("
+union Shape { Circle { r: int32 }, Empty }
+
+fn main() {
+    let shape = Shape.Empty { };
+    let x = match shape { _ => 0, Shape.Empty => 1 };
+}
+", Some("test/unions/unreachable")).unwrap();
+}
+
+#[test]
+fn union_match_unreachable_catch_all() {
+    for catch_all in ["_", "other"] {
+        let error = get_file_ast(&format!("
+union Shape {{ Circle {{ r: int32 }}, Rect {{ w: int32, h: int32 }}, Empty }}
+
+fn main() {{
+    let shape = Shape.Empty {{ }};
+    let x = match shape {{ Shape.Circle {{ r }} => r, Shape.Rect {{ w, h }} => w, Shape.Empty => 0, {catch_all} => 5 }};
+}}
+"), Some("test/unions/unreachable_catch_all")).unwrap_err();
+
+        match error {
+            ProcessingError::Validation(ValidationError::UnreachableMatchArm(arm)) => assert_eq!(arm.as_ref(), catch_all),
+            error => panic!("unexpected error {error:?}"),
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn union_match_duplicate_arm() {
+    get_file_ast("
+union Shape { Circle { r: int32 }, Empty }
+
+fn main() {
+    let shape = Shape.Empty { };
+    let x = match shape { Shape.Empty => 0, Shape.Empty => 1, Shape.Circle { r } => r };
+}
+", Some("test/unions/duplicate")).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn union_match_mismatched_arms() {
+    get_file_ast("
+union Shape { Circle { r: int32 }, Empty }
+
+fn main() {
+    let shape = Shape.Empty { };
+    let x = match shape { Shape.Empty => true, Shape.Circle { r } => r };
+}
+", Some("test/unions/mismatched")).unwrap();
+}
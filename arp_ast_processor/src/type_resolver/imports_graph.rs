@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::{errors::ProcessingError, types::{ast_node_value::{Ast, Id}, file::ArpFile, type_collection::TypeId}};
+use crate::{errors::ProcessingError, types::{ast_node_value::{Ast, Id}, file::ArpFile, type_collection::{TypeId, TypeKind}}};
 
 use super::{managed_dll_info, TypeResolverError};
 
@@ -35,23 +35,40 @@ pub fn resolve_imports(mut ast: Ast) -> Result<Ast, ProcessingError> {
 
     let types = nodes.iter().flat_map(|node| {
         node.import.iter().map(|(node_id, types)| {
+            let from = &ast.get(&graph.get(*node_id).unwrap().file).type_collection;
+
+            // Base classes and implemented traits are needed to look up inherited fields and methods.
             let types = types.iter()
-                .map(|ty| ast
-                    .get(&graph.get(*node_id).unwrap().file)
-                    .type_collection
-                    .try_get_strong(ty)
-                    .unwrap().clone())
+                .flat_map(|ty| std::iter::once(ty.clone()).chain(from.base_chain(ty)))
+                .flat_map(|ty| std::iter::once(ty.clone()).chain(from.try_get_strong(&ty).map(|info| info.traits.clone()).unwrap_or_default()))
+                .flat_map(|ty| from.try_get_strong(&ty).cloned())
+                .flat_map(|ty| {
+                    // Variants, type parameters and method type parameters travel with the type.
+                    let owned = match &ty.kind {
+                        TypeKind::Union(variants) => variants.clone(),
+                        TypeKind::Generic(params) => params.clone(),
+                        _ => vec![],
+                    };
+
+                    let dependents = owned.iter()
+                        .chain(ty.methods.iter().flat_map(|mi| mi.generics.iter()))
+                        .flat_map(|dependent| from.try_get_strong(dependent))
+                        .cloned()
+                        .collect::<Vec<_>>();
+
+                    std::iter::once(ty).chain(dependents)
+                })
                 .collect::<Vec<_>>();
 
-            (node.file, ast.get(&graph.get(*node_id).unwrap().file).arp_path.clone(), types)
+            (node.file, ast.get(&graph.get(*node_id).unwrap().file).arp_path.clone(), types, from.clone())
         })
     }).collect::<Vec<_>>();
 
-    for (file, path, types) in types.into_iter().clone() {
+    for (file, path, types, from) in types.into_iter() {
         for ty in types {
             let import_to = ast.get_mut::<ArpFile>(&file);
 
-            import_to.type_collection.copy_from(&ty, &path.0);
+            import_to.type_collection.copy_from(&ty, &path.0, &from);
         }
     }
 
@@ -0,0 +1,265 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{scope::Binding, types::{ast_node_value::{Ast, Id, WId}, expression::Expression, function::Function, simple::Identifier, statement::Statement, type_collection::TypeId}};
+
+use super::TypeResolverError;
+
+/// Type left out of the source: a `let` without a declared type, or the return type of a lambda.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Unknown {
+    Local(Id<Statement>),
+    Return(Id<Function>),
+}
+
+/// `unknown` has the type of `value`, once the unknowns `value` mentions are solved.
+/// Assignments to a local are only used when its initializer doesn't tell its type.
+struct Constraint {
+    unknown: Unknown,
+    value: Id<Expression>,
+    assignments: Vec<Id<Expression>>,
+    dependencies: Vec<usize>,
+}
+
+/// Solves every unknown type in a single pass. Constraints are solved in dependency order,
+/// a value is typed only after the types it mentions are known, so each one is evaluated once.
+pub fn infer_types(mut ast: Ast) -> Result<Ast, TypeResolverError> {
+    let mut solver = Solver::new(&ast);
+
+    loop {
+        while let Some(position) = solver.ready.pop_front() {
+            let constraint = &solver.constraints[position];
+            let mut ty = type_of(&mut ast, &constraint.value);
+
+            // `let mut items = [];` is typed by what is stored in it later.
+            for assignment in &constraint.assignments {
+                if ty.as_ref().is_ok_and(|ty| ty.is_strong()) {
+                    break;
+                }
+
+                if let Ok(assigned @ TypeId::Strong(_)) = type_of(&mut ast, assignment) {
+                    ty = Ok(assigned);
+                }
+            }
+
+            let ty = ty?;
+
+            if ty.is_strong() {
+                solver.settle(&mut ast, position, ty);
+            }
+        }
+
+        // Locals caught in a cycle can still be typed by a later assignment, which may unblock the rest.
+        let mut blocked = (0..solver.constraints.len()).filter(|position| !solver.solved[*position] && solver.pending[*position] > 0);
+        let Some((position, ty)) = blocked.find_map(|position| {
+            solver.constraints[position].assignments.iter()
+                .find_map(|assignment| ast.get_type(assignment).ok().filter(|ty| ty.is_strong()))
+                .map(|ty| (position, ty))
+        }) else { break };
+
+        solver.settle(&mut ast, position, ty);
+    }
+
+    let unsolved = solver.constraints.iter().zip(&solver.solved).filter_map(|(constraint, solved)| match constraint.unknown {
+        Unknown::Local(stmt) if !solved => Some((stmt, constraint.value)),
+        _ => None,
+    }).collect::<Vec<_>>();
+
+    // `let x = y;` can't be typed because `y` doesn't exist, which is the actual mistake.
+    let undeclared = unsolved.iter().find_map(|(_, value)| {
+        ast.rec_iter_start_from(value.as_weak()).filter_map(|(id, _)| ast.try_promote::<Expression>(id)).find_map(|expr| match ast.get(&expr) {
+            Expression::Variable(ident) if ast.lookup_binding(expr.as_weak(), ident).is_none() && ast.get_function_value(&expr, None, ident).is_none() => {
+                Some(TypeResolverError::UndeclaredVariable(ident.as_ref().into(), ast.get_node(&expr).span))
+            },
+            _ => None,
+        })
+    });
+
+    if let Some(error) = undeclared {
+        return Err(error);
+    }
+
+    // A local initialized by a call which doesn't resolve stays untyped, along with the locals depending on it.
+    // The validation tells why the call doesn't resolve, which says more than the local not being inferred.
+    let has_unresolved_call = unsolved.iter().any(|(_, value)| {
+        ast.rec_iter_start_from(value.as_weak()).filter_map(|(id, _)| ast.try_promote::<Expression>(id)).any(|expr| ast.is_unresolved_call(&expr))
+    });
+
+    if let Some((stmt, _)) = unsolved.first().filter(|_| !has_unresolved_call) {
+        let name = match ast.get(stmt) {
+            Statement::LocalVariableDeclaration { ident, .. } => ident.as_ref().into(),
+            Statement::Destructure { bindings, .. } => bindings.iter().map(|binding| binding.as_ref()).collect::<Vec<_>>().join(", ").into(),
+            _ => Box::default(),
+        };
+
+        return Err(TypeResolverError::CantInferType(name, ast.get_node(stmt).span));
+    }
+
+    // Generic methods may return types like `Box<U>` that were never spelled out with these arguments.
+    for expr in ast.get_children_of_kind::<Expression, _>(ast.get_root_index()) {
+        if let Err(error @ TypeResolverError::MissingInstantiation(_)) = type_of(&mut ast, &expr) {
+            return Err(error);
+        }
+    }
+
+    Ok(ast)
+}
+
+struct Solver {
+    constraints: Vec<Constraint>,
+    /// Constraints mentioning each constraint.
+    dependents: HashMap<usize, Vec<usize>>,
+    /// Number of unsolved dependencies of each constraint.
+    pending: Vec<usize>,
+    solved: Vec<bool>,
+    ready: VecDeque<usize>,
+}
+
+impl Solver {
+    fn new(ast: &Ast) -> Self {
+        let mut constraints = collect_constraints(ast);
+        let unknowns = constraints.iter().map(|constraint| constraint.unknown).collect::<Vec<_>>();
+
+        for constraint in &mut constraints {
+            let mut dependencies = dependencies_of(ast, constraint.value.as_weak(), &unknowns);
+            dependencies.retain(|dependency| unknowns[*dependency] != constraint.unknown);
+            dependencies.sort_unstable();
+            dependencies.dedup();
+            constraint.dependencies = dependencies;
+        }
+
+        let mut dependents: HashMap<usize, Vec<usize>> = HashMap::new();
+
+        for (position, constraint) in constraints.iter().enumerate() {
+            for dependency in &constraint.dependencies {
+                dependents.entry(*dependency).or_default().push(position);
+            }
+        }
+
+        let pending = constraints.iter().map(|constraint| constraint.dependencies.len()).collect::<Vec<_>>();
+        let ready = (0..constraints.len()).filter(|position| pending[*position] == 0).collect();
+
+        Self { solved: vec![false; constraints.len()], constraints, dependents, pending, ready }
+    }
+
+    fn settle(&mut self, ast: &mut Ast, position: usize, ty: TypeId) {
+        apply(ast, self.constraints[position].unknown, ty);
+        self.solved[position] = true;
+        self.pending[position] = 0;
+
+        for dependent in self.dependents.get(&position).into_iter().flatten() {
+            if self.pending[*dependent] == 0 {
+                continue;
+            }
+
+            self.pending[*dependent] -= 1;
+
+            if self.pending[*dependent] == 0 && !self.solved[*dependent] {
+                self.ready.push_back(*dependent);
+            }
+        }
+    }
+}
+
+fn collect_constraints(ast: &Ast) -> Vec<Constraint> {
+    let mut constraints = vec![];
+
+    for stmt in ast.get_children_of_kind::<Statement, _>(ast.get_root_index()) {
+        if let Statement::LocalVariableDeclaration { ty: TypeId::None | TypeId::Weak(_), expr, .. } | Statement::Destructure { ty: TypeId::None | TypeId::Weak(_), expr, .. } = ast.get(&stmt) {
+            constraints.push(Constraint { unknown: Unknown::Local(stmt), value: *expr, assignments: assignments_to(ast, &stmt), dependencies: vec![] });
+        }
+    }
+
+    for expr in ast.get_children_of_kind::<Expression, _>(ast.get_root_index()) {
+        let Expression::Lambda { func, .. } = ast.get(&expr) else { continue };
+
+        if !ast.get(func).return_type.is_none() {
+            continue;
+        }
+
+        // The folder lowers the body into a single `return`.
+        if let Some(Statement::Return(body)) = ast.get(&ast.get(func).block).statements.first().map(|stmt| ast.get(stmt)) {
+            constraints.push(Constraint { unknown: Unknown::Return(*func), value: *body, assignments: vec![], dependencies: vec![] });
+        }
+    }
+
+    constraints
+}
+
+/// Values assigned to the local declared by `stmt` later on.
+fn assignments_to(ast: &Ast, stmt: &Id<Statement>) -> Vec<Id<Expression>> {
+    let Some(func) = ast.get_parent_of_kind::<Function, _>(*stmt) else { return vec![] };
+
+    ast.get_children_of_kind::<Statement, _>(func)
+        .into_iter()
+        .filter_map(|assignment| match ast.get(&assignment) {
+            Statement::Assignment { on: None, field, expr } if ast.lookup_binding(assignment.as_weak(), field) == Some(Binding::Local(*stmt)) => Some(*expr),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Constraints whose types have to be known to type the expression at `index`.
+fn dependencies_of(ast: &Ast, index: WId, unknowns: &[Unknown]) -> Vec<usize> {
+    let mut dependencies = vec![];
+
+    for (id, _) in ast.rec_iter_start_from(index) {
+        let Some(expr) = ast.try_promote::<Expression>(id) else { continue };
+
+        match ast.get(&expr) {
+            // Calls by name may call a local holding a function.
+            Expression::Variable(ident) | Expression::Call { on: None, method: ident, .. } => dependencies.extend(binding_dependencies(ast, expr.as_weak(), ident, unknowns)),
+            Expression::Lambda { func, .. } => dependencies.extend(position_of(unknowns, Unknown::Return(*func))),
+            _ => {},
+        }
+    }
+
+    dependencies
+}
+
+fn binding_dependencies(ast: &Ast, index: WId, ident: &Identifier, unknowns: &[Unknown]) -> Vec<usize> {
+    match ast.lookup_binding(index, ident) {
+        Some(Binding::Local(stmt)) => position_of(unknowns, Unknown::Local(stmt)).into_iter().collect(),
+        Some(Binding::Captured(lambda)) => binding_dependencies(ast, lambda.as_weak(), ident, unknowns),
+        // Arm bindings are typed by the matched value.
+        Some(Binding::MatchArm(arm)) => match ast.try_promote::<Expression>(ast.get_node(&arm).get_parent()).map(|parent| ast.get(&parent)) {
+            Some(Expression::Match { on, .. }) => dependencies_of(ast, on.as_weak(), unknowns),
+            _ => vec![],
+        },
+        _ => vec![],
+    }
+}
+
+fn position_of(unknowns: &[Unknown], unknown: Unknown) -> Option<usize> {
+    unknowns.iter().position(|candidate| *candidate == unknown)
+}
+
+fn apply(ast: &mut Ast, unknown: Unknown, new_ty: TypeId) {
+    match unknown {
+        Unknown::Local(stmt) => ast.mutate_value(&stmt, |value| {
+            if let Statement::LocalVariableDeclaration { ty, .. } | Statement::Destructure { ty, .. } = value {
+                *ty = new_ty.clone();
+            }
+        }),
+        Unknown::Return(func) => ast.mutate_value(&func, |func| func.return_type = new_ty.clone()),
+    }
+}
+
+/// Type of `expr`, instantiating the generic types it mentions on the way.
+fn type_of(ast: &mut Ast, expr: &Id<Expression>) -> Result<TypeId, TypeResolverError> {
+    let mut instantiated = vec![];
+
+    loop {
+        match ast.get_type(expr) {
+            Err(TypeResolverError::MissingInstantiation(name)) if !instantiated.contains(&name) => {
+                let ty = ast.get_mut_arp_file_in_parent(expr.as_weak()).map(|file| file.type_collection.get_or_allocate(&name));
+
+                if !ty.is_some_and(|ty| ty.is_strong()) {
+                    return Err(TypeResolverError::MissingInstantiation(name));
+                }
+
+                instantiated.push(name);
+            },
+            result => return result,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::{path::Path, process::Command};
+use std::{collections::HashMap, path::{Path, PathBuf}, process::Command, sync::{LazyLock, Mutex}};
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -19,17 +19,30 @@ pub enum DllImporterError {
 
     #[error("Type not found")]
     TypeNotFound,
+
+    #[error("Dll {0:?} not found")]
+    DllNotFound(PathBuf),
 }
 
+/// Project of the exporter that lists the public types of a dll as json.
+const EXPORTER_PROJECT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../csharp-assembly-info-exporter");
+
+/// Every import of a dll reads the same export, so it's only run once per dll.
+static EXPORTED: LazyLock<Mutex<HashMap<PathBuf, SharpTypeCollection>>> = LazyLock::new(Default::default);
+
+/// The installed runtimes don't change while compiling, `dotnet` is only asked once.
+static FRAMEWORK_DIRS: LazyLock<Vec<PathBuf>> = LazyLock::new(framework_dirs);
+
 pub fn get_info<P: AsRef<Path>>(path: P) -> Result<SharpTypeCollection, DllImporterError> {
-    let _ = path;
-    let cmd_result = Command::new("dotnet") // TODO Temporary path to exporter
-        .args([
-            "run",
-            "--project",
-            "/Users/stas/learn/csharp-assembly-info-exporter",
-            "/Users/stas/learn/csharp-assembly-info-exporter/System.Console.dll", // &path.as_ref().to_string_lossy()
-        ])
+    let path = resolve_dll_path(path.as_ref(), &FRAMEWORK_DIRS)?;
+
+    if let Some(collection) = EXPORTED.lock().unwrap().get(&path) {
+        return Ok(collection.clone());
+    }
+
+    let cmd_result = Command::new("dotnet")
+        .args(["run", "--project", EXPORTER_PROJECT])
+        .arg(&path)
         .output()?;
 
     if !cmd_result.status.success() {
@@ -41,9 +54,38 @@ pub fn get_info<P: AsRef<Path>>(path: P) -> Result<SharpTypeCollection, DllImpor
     let output = String::from_utf8(cmd_result.stdout)?;
     let sharp_collection: SharpTypeCollection = serde_json::from_str(&output)?;
 
+    EXPORTED.lock().unwrap().insert(path, sharp_collection.clone());
+
     Ok(sharp_collection)
 }
 
+/// `from extern System.Collections.dll` names a dll relative to the project, or one of the framework assemblies.
+pub fn resolve_dll_path(path: &Path, framework_dirs: &[PathBuf]) -> Result<PathBuf, DllImporterError> {
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+
+    framework_dirs.iter()
+        .map(|dir| dir.join(path))
+        .find(|candidate| candidate.is_file())
+        .ok_or_else(|| DllImporterError::DllNotFound(path.to_path_buf()))
+}
+
+/// Directory of the newest shared `Microsoft.NETCore.App` runtime, parsed from `dotnet --list-runtimes`:
+/// `Microsoft.NETCore.App 8.0.8 [/usr/share/dotnet/shared/Microsoft.NETCore.App]`.
+fn framework_dirs() -> Vec<PathBuf> {
+    let Ok(output) = Command::new("dotnet").arg("--list-runtimes").output() else { return vec![] };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .rev()
+        .filter_map(|line| line.strip_prefix("Microsoft.NETCore.App "))
+        .filter_map(|rest| rest.split_once(" ["))
+        .map(|(version, dir)| Path::new(dir.trim_end_matches(']')).join(version))
+        .take(1)
+        .collect()
+}
+
 pub fn resolve_type<P: AsRef<Path>, N: AsRef<str>>(
     path: &P,
     name: N,
@@ -51,7 +93,7 @@ pub fn resolve_type<P: AsRef<Path>, N: AsRef<str>>(
     let info = get_info(path)?;
     info.collection
         .iter()
-        .find(|ty| ty.full_name == name.as_ref())
+        .find(|ty| ty.arp_name() == name.as_ref())
         .cloned()
         .ok_or(DllImporterError::TypeNotFound)
 }
@@ -61,14 +103,54 @@ pub struct SharpTypeCollection {
     pub collection: Vec<SharpTypeInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct SharpTypeInfo {
     pub full_name: String,
     pub short_name: Option<String>,
+    /// Names of the type parameters of a generic definition like ``List`1``.
+    #[serde(default)]
+    pub generic_params: Vec<String>,
+    /// Interfaces are imported as traits, arp types can implement them with `impl IComparable for Point`.
+    #[serde(default)]
+    pub is_interface: bool,
+    #[serde(default)]
+    pub is_value_type: bool,
+    /// Enums are imported with their members, `ConsoleColor.Red`.
+    #[serde(default)]
+    pub is_enum: bool,
+    #[serde(default)]
+    pub enum_members: Vec<SharpEnumMember>,
+    /// Delegates take arp function values, their signature is the one of `Invoke`.
+    #[serde(default)]
+    pub is_delegate: bool,
+    /// Public constructors, named `.ctor` and returning `System.Void`.
+    #[serde(default)]
+    pub constructors: Vec<SharpMethodInfo>,
     pub fields: Vec<SharpTypedIdent>,
+    /// Public static fields, `Math.PI` and `String.Empty`.
+    #[serde(default)]
+    pub static_fields: Vec<SharpStaticFieldInfo>,
+    /// Properties, their accessors are exported as methods too.
+    #[serde(default)]
+    pub properties: Vec<SharpPropertyInfo>,
     pub methods: Vec<SharpMethodInfo>,
 }
 
+impl SharpTypeInfo {
+    /// Name without the CLR arity suffix, ``System.Collections.Generic.List`1`` is imported as `System.Collections.Generic.List`.
+    pub fn arp_name(&self) -> &str {
+        strip_arity(&self.full_name)
+    }
+
+    pub fn arp_short_name(&self) -> Option<&str> {
+        self.short_name.as_deref().map(strip_arity)
+    }
+}
+
+fn strip_arity(name: &str) -> &str {
+    name.split('`').next().unwrap_or(name)
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SharpTypedIdent {
     pub ident: String,
@@ -76,8 +158,44 @@ pub struct SharpTypedIdent {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SharpEnumMember {
+    pub ident: String,
+    pub value: i64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct SharpMethodInfo {
     pub ident: String,
     pub return_ty_full_name: String,
     pub args: Vec<SharpTypedIdent>,
+    /// Default values of `args`, `null` for required parameters.
+    #[serde(default)]
+    pub defaults: Vec<Option<serde_json::Value>>,
+    /// The last parameter is a `params` array, `Console.WriteLine(string, params object[])`.
+    #[serde(default)]
+    pub is_params: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct SharpStaticFieldInfo {
+    pub ident: String,
+    pub ty_full_name: String,
+    /// `readonly` and `const` fields can't be assigned.
+    #[serde(default)]
+    pub is_read_only: bool,
+    /// Value of a `const` field.
+    #[serde(default)]
+    pub constant: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct SharpPropertyInfo {
+    pub ident: String,
+    pub ty_full_name: String,
+    #[serde(default)]
+    pub is_static: bool,
+    #[serde(default)]
+    pub can_read: bool,
+    #[serde(default)]
+    pub can_write: bool,
 }
@@ -1,7 +1,10 @@
 pub mod imports_graph;
 pub mod managed_dll_info;
+mod inference;
 
-use crate::types::{ast_node_value::{Ast, Id}, block_scope::BlockScope, expression::Expression, function::Function, statement::Statement, structure::Structure, type_collection::{StrongTypeId, TypeId}};
+use arp_types::span::Span;
+
+use crate::types::{ast_node_value::{Ast, Id}, expression::Expression, function::Function, structure::Structure, type_collection::{StrongTypeId, TypeId}};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -16,6 +19,45 @@ pub enum TypeResolverError {
         actual: TypeId,
     },
 
+    #[error("Match arms have different types")]
+    MatchArmsMismatchedTypes {
+        expected: TypeId,
+        actual: TypeId,
+    },
+
+    #[error("`?` can only be applied to Option or Result")]
+    CantPropagate(TypeId),
+
+    #[error("`?` can't propagate into the function's return type")]
+    IncompatiblePropagation {
+        expected: TypeId,
+        actual: TypeId,
+    },
+
+    #[error("Array elements have incompatible types")]
+    ArrayElementMismatch {
+        expected: TypeId,
+        actual: TypeId,
+    },
+
+    #[error("Element type of an empty array has to be declared")]
+    EmptyArrayLiteral,
+
+    #[error("Only arrays can be indexed")]
+    NotAnArray(TypeId),
+
+    #[error("Tuples have between 2 and 7 elements, found {0}")]
+    UnsupportedTupleLength(usize),
+
+    #[error("Only functions can be called")]
+    NotAFunction(TypeId),
+
+    #[error("Function takes {expected} arguments, found {actual}")]
+    ArgumentCountMismatch {
+        expected: usize,
+        actual: usize,
+    },
+
     #[error("Unexpected type")]
     FunctionTypeMustBeDeclared(Id<Function>),
 
@@ -35,74 +77,60 @@ pub enum TypeResolverError {
     #[error("TypeNotFoundInFilePath")]
     TypeNotFoundInFilePath(String),
 
+    #[error("{0} has to be instantiated first")]
+    MissingInstantiation(String),
+
     #[error("Parent Arp file not found")]
     ArpFileNotFound,
 
-    #[error("Infinite type loop. No idea why")]
-    InfiniteTypeLoopDetected,
-}
+    #[error("Type of {0} can't be inferred, declare it")]
+    CantInferType(Box<str>, Span),
 
-pub enum TypeMutation {
-    ImplicitVariableDeclaration(Id<BlockScope>, Id<Statement>, TypeId),
-    ConvertVariableToTypeExpression(Id<Expression>, TypeId),
+    #[error("{0} isn't declared in this scope")]
+    UndeclaredVariable(Box<str>, Span),
 }
 
-impl ExecuteMutation for TypeMutation {
-    fn execute(&self, mut ast: Ast) -> Result<Ast, TypeResolverError> {
-        match self {
-            TypeMutation::ImplicitVariableDeclaration(_block, stmt, new_ty) => {
-                ast.mutate_value(stmt, |value| {
-                    if let Statement::LocalVariableDeclaration { ty, .. } = value {
-                        *ty = new_ty.clone();
-                    }
-                });
-            },
-            TypeMutation::ConvertVariableToTypeExpression(expr, ty) => {
-                ast.set_value(expr, || {
-                    Expression::Type(ty.clone())
-                })
-            },
-        }
+/// Resolves the types left out of the source. Names of types used as values are turned into type
+/// expressions first, then the types of locals and lambdas are inferred in a single pass.
+/// Types of all expressions are cached afterwards, the tree isn't expected to change them anymore.
+pub fn resolve_types(mut ast: Ast) -> Result<Ast, TypeResolverError> {
+    let expressions = ast.get_children_of_kind::<Expression, _>(ast.get_root_index());
+    let mut conversions = vec![];
 
-        Ok(ast)
+    for expr in &expressions {
+        if let Some(ty) = convert_variables_to_known_types(&ast, expr)? {
+            conversions.push((*expr, ty));
+        }
     }
-}
 
-pub trait ExecuteMutation {
-    fn execute(&self, ast: Ast) -> Result<Ast, TypeResolverError>;
-}
+    // A whole chain like `Name.Space.Class` becomes a single type, its parts aren't converted on their own.
+    let converted = conversions.iter().map(|(expr, _)| *expr).collect::<Vec<_>>();
+    conversions.retain(|(expr, _)| {
+        let mut parent = ast.try_promote::<Expression>(ast.get_node(expr).get_parent());
 
-pub fn resolve_types_loop(mut ast: Ast) -> Result<Ast, TypeResolverError> {
-    let mut i = 0;
-    while let Some(mu) = resolve_types(&ast)? {
-        ast = mu.execute(ast)?;
+        while let Some(outer) = parent.filter(|outer| outer != expr) {
+            if converted.contains(&outer) {
+                return false;
+            }
 
-        i += 1;
-        if i == 100000 {
-            return Err(TypeResolverError::InfiniteTypeLoopDetected);
+            parent = ast.try_promote::<Expression>(ast.get_node(&outer).get_parent());
         }
-    }
 
-    Ok(ast)
-}
+        true
+    });
 
-pub fn resolve_types(ast: &Ast) -> Result<Option<TypeMutation>, TypeResolverError> {
-    for variable in ast.get_children_of_kind(ast.get_root_index()) {
-        if let Some(m)  = convert_variables_to_known_types(ast, &variable)? {
-            return Ok(Some(m));
-        }
+    for (expr, ty) in conversions {
+        ast.set_value(&expr, || Expression::Type(ty.clone()));
     }
 
-    for bs in ast.get_children_of_kind(ast.get_root_index()) {
-        if let Some(m)  = resolve_types_in_local_block(ast, &bs)? {
-            return Ok(Some(m));
-        }
-    }
+    let mut ast = inference::infer_types(ast)?;
+    ast.cache_types();
 
-    Ok(None)
+    Ok(ast)
 }
 
-pub fn convert_variables_to_known_types(ast: &Ast, expr: &Id<Expression>) -> Result<Option<TypeMutation>, TypeResolverError> {
+/// Type named by a variable or a chain of fields, `MyClass` or `Name.Space.MyClass`.
+pub fn convert_variables_to_known_types(ast: &Ast, expr: &Id<Expression>) -> Result<Option<TypeId>, TypeResolverError> {
     match ast.get(expr) {
         Expression::Variable(ident) => {
             let ty = ast.get_arp_file_in_parent(expr.as_weak())
@@ -112,7 +140,7 @@ pub fn convert_variables_to_known_types(ast: &Ast, expr: &Id<Expression>) -> Res
 
             match ty {
                 TypeId::Strong(_) => {
-                    Ok(Some(TypeMutation::ConvertVariableToTypeExpression(*expr, ty)))
+                    Ok(Some(ty))
                 },
                 _ => Ok(None)
             }
@@ -130,7 +158,7 @@ pub fn convert_variables_to_known_types(ast: &Ast, expr: &Id<Expression>) -> Res
 
                 match ty {
                     TypeId::Strong(_) => {
-                        Ok(Some(TypeMutation::ConvertVariableToTypeExpression(*expr, ty)))
+                        Ok(Some(ty))
                     },
                     _ => Ok(None)
                 }
@@ -159,19 +187,3 @@ fn try_collect_get_chain(ast: &Ast, expr: &Id<Expression>, sum_str: String) -> R
         _ => Ok(None)
     }
 }
-
-pub fn resolve_types_in_local_block(ast: &Ast, block: &Id<BlockScope>) -> Result<Option<TypeMutation>, TypeResolverError> {
-    for stmt in ast.get(block).statements.iter() {
-        if let Statement::LocalVariableDeclaration { ty: TypeId::None | TypeId::Weak(_), expr, .. } = ast.get(stmt) {
-            match ast.get_type(expr) {
-                Ok(TypeId::Strong(ty)) => {
-                    return Ok(Some(TypeMutation::ImplicitVariableDeclaration(*block, *stmt, TypeId::Strong(ty))));
-                },
-                Err(err) => return Err(err),
-                _ => {}
-            }
-        }
-    }
-
-    Ok(None)
-}
\ No newline at end of file
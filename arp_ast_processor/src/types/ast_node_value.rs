@@ -1,6 +1,6 @@
 use crate::{ast::{index::WeakIndex, traits::{AstNodeUnion, GetChildren, PushRemoveRootChildren}}, derive_implementations};
 
-use super::{block_scope::BlockScope, declaration::Declaration, expression::Expression, file::ArpFile, function::Function, implementation::Implementation, statement::Statement, structure::Structure};
+use super::{type_collection::TypeId, block_scope::BlockScope, declaration::Declaration, expression::Expression, file::ArpFile, function::Function, implementation::Implementation, statement::Statement, structure::Structure};
 
 
 
@@ -41,7 +41,9 @@ derive_implementations!(AstNodeValue, AstNodeValue::Implementation, Implementati
 derive_implementations!(AstNodeValue, AstNodeValue::ArpFile, ArpFile);
 
 
-impl AstNodeUnion for AstNodeValue { }
+impl AstNodeUnion for AstNodeValue {
+    type Type = TypeId;
+}
 
 impl GetChildren for AstNodeValue {
     fn get_children(&self) -> Vec<WId> {
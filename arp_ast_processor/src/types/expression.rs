@@ -1,11 +1,13 @@
 use arp_parser::types::{BinaryOp, UnaryOp};
-use crate::{ast::traits::GetChildren, type_resolver::TypeResolverError, types::{block_scope::BlockScope, file::ArpFile, statement::Statement}, validations::{Validate, ValidationError}};
-use super::{ast_node_value::{Ast, Id, WId}, function::Function, simple::Identifier, type_collection::TypeId};
+use crate::{ast::traits::GetChildren, type_resolver::TypeResolverError, types::{file::ArpFile, statement::Statement}, validations::{Validate, ValidationError}};
+use super::{ast_node_value::{Ast, Id, WId}, function::{Function, FunctionKind}, simple::Identifier, type_collection::{BoundArgument, MethodInfo, Overload, PreludeType, TypeCollection, TypeId, TypeKind, TypeSourceKind}};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Expression {
     Literal(Literal),
     This(TypeId),
+    /// `base` inside of an implementation, holds the implemented type like `This`.
+    Base(TypeId),
     Variable(Identifier),
     Type(TypeId),
     Unary {
@@ -21,14 +23,93 @@ pub enum Expression {
         on: Id<Expression>,
         ident: Identifier,
     },
+    /// `names` are the names of named arguments by position, `join(items, sep: ", ")`. It's empty once
+    /// the call is lowered to positional arguments, with defaults filled in and `params` arrays built.
+    /// Arguments which then come before ones written earlier are `spilled`: evaluated first, in source order, into hidden locals.
     Call {
         on: Option<Id<Expression>>,
         method: Identifier,
         args: Vec<Id<Expression>>,
+        names: Vec<Option<Identifier>>,
+        spilled: Vec<Id<Expression>>,
     },
+    /// `positional` are values given without a field name, `MyClass { 5 }`. They're moved into `args`
+    /// once lowered, named after the fields they initialize.
     Construct {
         ident: Identifier,
         args: Vec<(Identifier, Id<Expression>)>,
+        positional: Vec<Id<Expression>>,
+    },
+    /// `new Random(42)`, calls the constructor of a managed type picked by the arguments. Arguments are lowered like the ones of a call.
+    New {
+        ty: TypeId,
+        args: Vec<Id<Expression>>,
+        names: Vec<Option<Identifier>>,
+        spilled: Vec<Id<Expression>>,
+    },
+    Match {
+        on: Id<Expression>,
+        arms: Vec<MatchArm>,
+    },
+    Try {
+        expr: Id<Expression>,
+    },
+    /// `value as float32`, converts a number, enum or reference to `ty`.
+    Cast {
+        expr: Id<Expression>,
+        ty: TypeId,
+    },
+    /// `[1, 2, 3]`, the element type is the most general one among the elements.
+    Array(Vec<Id<Expression>>),
+    /// `items[0]`.
+    Index {
+        on: Id<Expression>,
+        index: Id<Expression>,
+    },
+    /// `(1, "one")`.
+    Tuple(Vec<Id<Expression>>),
+    /// Trailing arguments of a call to a `params` method, collected into an array of `ty` when the call is lowered.
+    ParamsArray {
+        ty: TypeId,
+        elements: Vec<Id<Expression>>,
+    },
+    /// `fn(x: int32) => x + offset`. The body is the `Invoke` method of a closure class,
+    /// `captures` are the outer variables it reads, copied into the closure when it's created.
+    Lambda {
+        func: Id<Function>,
+        captures: Vec<(Identifier, TypeId)>,
+    },
+}
+
+/// Longest tuple which still fits into a single `System.ValueTuple`.
+pub const MAX_TUPLE_LEN: usize = 7;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub expr: Id<Expression>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pattern {
+    Wildcard,
+    Binding(Identifier),
+    Variant {
+        ty: TypeId,
+        fields: Vec<(Identifier, Identifier)>,
+    },
+}
+
+impl Pattern {
+    /// Names bound by the pattern, `_` doesn't bind anything.
+    pub fn bindings(&self) -> Vec<&Identifier> {
+        let bindings = match self {
+            Pattern::Wildcard => vec![],
+            Pattern::Binding(binding) => vec![binding],
+            Pattern::Variant { fields, .. } => fields.iter().map(|(_, binding)| binding).collect(),
+        };
+
+        bindings.into_iter().filter(|binding| binding.as_ref() != "_").collect()
     }
 }
 
@@ -38,12 +119,17 @@ impl GetChildren for Expression {
             Expression::Literal(_) |
             Expression::Variable(_) |
             Expression::This(_) |
+            Expression::Base(_) |
             Expression::Type(_) => vec![],
 
             Expression::Unary { op: _, expr } => vec![expr.as_weak()],
+            Expression::Try { expr } | Expression::Cast { expr, .. } => vec![expr.as_weak()],
             Expression::Binary { lhs, op: _, rhs } => vec![lhs.as_weak(), rhs.as_weak()],
             Expression::GetField { on, ident: _ } => vec![on.as_weak()],
-            Expression::Call { on, method: _, args } => {
+            Expression::Array(elements) | Expression::Tuple(elements) | Expression::New { args: elements, .. } | Expression::ParamsArray { elements, .. } => elements.iter().map(|i| i.as_weak()).collect(),
+            Expression::Index { on, index } => vec![on.as_weak(), index.as_weak()],
+            Expression::Lambda { func, .. } => vec![func.as_weak()],
+            Expression::Call { on, args, .. } => {
                 let mut result = args.iter().map(|i| i.as_weak()).collect::<Vec<_>>();
                 if let Some(on) = on {
                     result.push(on.as_weak());
@@ -51,32 +137,62 @@ impl GetChildren for Expression {
 
                 result
             },
-            Expression::Construct { args, .. } => {
-                args.iter().map(|i| i.1.as_weak()).collect::<Vec<_>>()
+            Expression::Construct { args, positional, .. } => {
+                args.iter().map(|i| i.1.as_weak()).chain(positional.iter().map(Id::as_weak)).collect::<Vec<_>>()
             },
-            
+            Expression::Match { on, arms } => {
+                let mut result = vec![on.as_weak()];
+                result.extend(arms.iter().map(|arm| arm.expr.as_weak()));
+                result
+            },
+
         }
     }
 }
 
 impl Ast {
+    /// Type of the expression at `index`. Once types are resolved they come from the side table,
+    /// expressions missing from it are typed on demand.
     pub fn get_type(&self, index: &Id<Expression>) -> Result<TypeId, TypeResolverError> {
+        match self.get_cached_type(*index) {
+            Some(ty) => Ok(ty.clone()),
+            None => self.compute_type(index),
+        }
+    }
+
+    /// Fills the side table with the type of every expression of the tree. Children are typed
+    /// before their parents and reuse their cached types, but variables aren't children of what
+    /// they refer to: a match arm binding still types the matched value on demand, as it comes
+    /// after the arms in this order.
+    pub fn cache_types(&mut self) {
+        let expressions = self.get_children_of_kind::<Expression, _>(self.get_root_index());
+
+        for expr in expressions.into_iter().rev() {
+            let ty = self.compute_type(&expr).ok().filter(TypeId::is_strong);
+            self.set_cached_type(expr, ty);
+        }
+    }
+
+    /// Whether the side table still agrees with the types computed from the tree.
+    pub fn cached_types_in_sync(&self) -> bool {
+        self.get_children_of_kind::<Expression, _>(self.get_root_index())
+            .iter()
+            .all(|expr| self.get_cached_type(*expr) == self.compute_type(expr).ok().filter(TypeId::is_strong).as_ref())
+    }
+
+    fn compute_type(&self, index: &Id<Expression>) -> Result<TypeId, TypeResolverError> {
         if let Some(arp_file) = self.get_parent_of_kind::<ArpFile, _>(*index).map(|id| self.get(&id)) {
             match self.get(index) {
-                Expression::Literal(lit) => match lit {
-                    Literal::Integer(_) => Ok(arp_file.type_collection.get_int()),
-                    Literal::Float(_) => Ok(arp_file.type_collection.get_float()),
-                    Literal::String(_) => Ok(arp_file.type_collection.get_string()),
-                    Literal::Bool(_) => Ok(arp_file.type_collection.get_bool()),
-                }
+                Expression::Literal(lit) => Ok(arp_file.type_collection.get_literal_type(lit)),
                 Expression::Unary { op, expr } => {
                     if let TypeId::Strong(ty) = self.get_type(expr)? {
                         match op {
                             UnaryOperator::Negate => {
                                 let int = self.get_arp_file_in_parent(index.as_weak()).unwrap().type_collection.get_int();
                                 let float = self.get_arp_file_in_parent(index.as_weak()).unwrap().type_collection.get_float();
+                                let double = self.get_arp_file_in_parent(index.as_weak()).unwrap().type_collection.get_double();
 
-                                if &ty == int.try_into_strong().unwrap() || &ty == float.try_into_strong().unwrap() {
+                                if &ty == int.try_into_strong().unwrap() || &ty == float.try_into_strong().unwrap() || &ty == double.try_into_strong().unwrap() {
                                     Ok(TypeId::Strong(ty))
                                 } else {
                                     // TODO After IAdd and ISub remove this error.
@@ -97,6 +213,10 @@ impl Ast {
                     }
                 }
                 Expression::Binary { lhs, op, rhs } => {
+                    if let Some((_, ty)) = self.get_operator_overload(index)? {
+                        return Ok(ty);
+                    }
+
                     if let TypeId::Strong(lty) = self.get_type(lhs)? {
                         if let TypeId::Strong(rty) = self.get_type(rhs)? {
                             if lty != rty {
@@ -104,21 +224,40 @@ impl Ast {
                             } else {
                                 match op {
                                     BinaryOperator::Or |
-                                    BinaryOperator::And |
+                                    BinaryOperator::And => {
+                                        let bool = self.get_arp_file_in_parent(index.as_weak()).unwrap().type_collection.get_bool();
+
+                                        Ok(bool)
+                                    },
+
                                     BinaryOperator::Equal |
-                                    BinaryOperator::NotEqual |
+                                    BinaryOperator::NotEqual => {
+                                        let collection = &self.get_arp_file_in_parent(index.as_weak()).unwrap().type_collection;
+                                        let lty = TypeId::Strong(lty);
+
+                                        // Structs have no reference identity to fall back on without an `eq` method.
+                                        if collection.is_value_type(&lty) && !collection.is_enum(&lty) && !collection.try_get_strong(&lty).is_some_and(|info| info.source == TypeSourceKind::Standard) {
+                                            Err(TypeResolverError::UnexpectedType { expected: collection.get_bool(), actual: lty })
+                                        } else {
+                                            Ok(collection.get_bool())
+                                        }
+                                    },
+
                                     BinaryOperator::Greater |
                                     BinaryOperator::GreaterOrEqual |
                                     BinaryOperator::Less |
                                     BinaryOperator::LessOrEqual => {
-                                        let bool = self.get_arp_file_in_parent(index.as_weak()).unwrap().type_collection.get_bool();
+                                        let collection = &self.get_arp_file_in_parent(index.as_weak()).unwrap().type_collection;
 
-                                        Ok(bool)
-                                        // if &lty == bool.try_into_strong().unwrap() {
-                                        //     Ok(bool)
-                                        // } else {
-                                        //     Err(TypeResolverError::UnexpectedType { expected: bool.clone(), actual: lty.into() })
-                                        // }
+                                        let lty = TypeId::Strong(lty);
+
+                                        // Enums compare by their underlying values.
+                                        if collection.is_numeric(&lty) || collection.is_enum(&lty) {
+                                            Ok(collection.get_bool())
+                                        } else {
+                                            // Neither a `cmp` method nor a managed comparison operator.
+                                            Err(TypeResolverError::UnexpectedType { expected: collection.get_int(), actual: lty })
+                                        }
                                     },
         
                                     BinaryOperator::Add |
@@ -127,11 +266,12 @@ impl Ast {
                                     BinaryOperator::Divide => {
                                         let int = self.get_arp_file_in_parent(index.as_weak()).unwrap().type_collection.get_int();
                                         let float = self.get_arp_file_in_parent(index.as_weak()).unwrap().type_collection.get_float();
+                                        let double = self.get_arp_file_in_parent(index.as_weak()).unwrap().type_collection.get_double();
 
-                                        if &lty == int.try_into_strong().unwrap() || &lty == float.try_into_strong().unwrap() {
+                                        if &lty == int.try_into_strong().unwrap() || &lty == float.try_into_strong().unwrap() || &lty == double.try_into_strong().unwrap() {
                                             Ok(TypeId::Strong(lty))
                                         } else {
-                                            // TODO After IAdd and ISub remove this error.
+                                            // Neither an operator method nor a managed operator.
                                             Err(TypeResolverError::UnexpectedType { expected: int.clone(), actual: lty.into() })
                                         }
                                     },
@@ -144,72 +284,269 @@ impl Ast {
                         Ok(TypeId::None)
                     }
                 },
-                Expression::Variable(var_ident) => {
-
-                    let mut stmt = self.get_parent_of_kind::<Statement, _>(index.as_weak()).expect("variable must be in statement");
+                Expression::Variable(var_ident) => self.get_variable_type(index, var_ident),
+                Expression::Construct { ident, .. } => {
+                    let ty = arp_file.type_collection.resolve_name(ident);
 
-                    while let Some(bs) = self.get_parent_of_kind::<BlockScope, _>(stmt.as_weak()) {
-                        let mut index_of_statement = self.get(&bs).statements.iter().position(|id| *id == stmt).expect("variable must be in statement");
+                    match arp_file.type_collection.try_get_strong(&ty).map(|info| &info.kind) {
+                        Some(TypeKind::UnionVariant(union)) => Ok(union.clone()),
+                        _ => Ok(ty),
+                    }
+                },
+                Expression::This(ty) => {
+                    Ok(ty.clone())
+                }
+                Expression::Base(ty) => {
+                    Ok(arp_file.type_collection.get_base(ty).unwrap_or_default())
+                }
+                Expression::GetField { on, ident } => {
+                    let ty = self.get_type(on)?;
 
-                        loop {
-                            if let Some(Statement::LocalVariableDeclaration { ident,  ty, .. }) = self.get(&bs).statements.get(index_of_statement).map(|id| self.get(id)) {
-                                if var_ident == ident {
-                                    return Ok(ty.clone());
-                                }
-                            }
-    
-                            if let Some(new_index_of_statement) = index_of_statement.checked_sub(1) {
-                                index_of_statement = new_index_of_statement;
-                            } else {
-                                break;
-                            }
+                    // `Math.square` is a static method used as a function value.
+                    if let Expression::Type(_) = self.get(on) {
+                        if let Some(func) = self.get_function_value(index, Some(&ty), ident) {
+                            return self.get_function_value_type(&func);
                         }
+                    }
 
-                        if let Some(block_stmt) = self.try_promote::<Statement>(self.get_node(&bs).get_parent()).as_ref() {
-                            match self.get(block_stmt) {
-                                Statement::Block(_) => stmt = *block_stmt,
-                                _ => break,
-                            };
-                        } else {
-                            break;
+                    // `Color.Red` is a member of the enum, `color.value` its underlying integer.
+                    if let (Expression::Type(_), true) = (self.get(on), arp_file.type_collection.is_enum(&ty)) {
+                        return Ok(arp_file.type_collection.get_enum_value(&ty, ident.as_ref()).map(|_| ty).unwrap_or_default());
+                    }
+
+                    // `Math.PI` is a static field, `Console.Out` a static property and `builder.Length` a property of the value.
+                    let is_static = matches!(self.get(on), Expression::Type(_));
+                    if let Some(fld) = arp_file.type_collection.get_static_field(&ty, ident.as_ref()).filter(|_| is_static) {
+                        return Ok(fld.ty.clone());
+                    }
+
+                    Ok(arp_file.type_collection.get_field_type(&ty, ident.as_ref())
+                        .or_else(|| arp_file.type_collection.find_property(&ty, ident.as_ref())
+                            .filter(|property| property.is_static == is_static && property.can_read)
+                            .map(|property| property.ty.clone()))
+                        .unwrap_or_default())
+                },
+                Expression::Call { on: None, method, args, names, .. } => {
+                    // `id(5)` binds `T` of `fn id<T>(x: T) -> T` to `int32`.
+                    if let Some(mi) = self.get_top_level_method(index, method).filter(|mi| !mi.generics.is_empty()) {
+                        let arg_types = args.iter().map(|arg| self.get_type(arg)).collect::<Result<Vec<_>, _>>()?;
+                        let collection = &arp_file.type_collection;
+
+                        if arg_types.iter().any(TypeId::is_none) {
+                            return Ok(TypeId::None);
                         }
+
+                        return match collection.bind_generics(&mi, names, &arg_types) {
+                            Some(bindings) => {
+                                let map = mi.generics.iter().cloned().zip(bindings).collect::<Vec<_>>();
+
+                                collection.find_substituted(&mi.return_type, &map)
+                                    .map_err(TypeResolverError::MissingInstantiation)
+                            },
+                            None => Ok(TypeId::None),
+                        };
                     }
 
-                    if let Some(func) = self.get_parent_of_kind::<Function, _>(stmt.as_weak()).map(|id| self.get(&id)) {
-                        if let Some((_, ty)) = func.parameters.iter().find(|(ident, _)| ident == var_ident) {
-                            return Ok(ty.clone());
+                    let callee = self.get_variable_type(index, method)?;
+                    if callee.is_none() {
+                        return Ok(TypeId::None);
+                    }
+
+                    let collection = &arp_file.type_collection;
+                    let (params, return_type) = collection.get_callable_signature(&callee).ok_or(TypeResolverError::NotAFunction(callee))?;
+
+                    // Top level functions take named arguments and leave out parameters with defaults.
+                    let passed = match self.get_top_level_method(index, method) {
+                        Some(mi) if !names.is_empty() || mi.defaults.iter().any(Option::is_some) => match mi.bind_arguments(names, args.len(), false) {
+                            Ok(bound) => params.iter().zip(bound).filter_map(|(param, argument)| match argument {
+                                BoundArgument::Argument(position) => Some((param, &args[position])),
+                                _ => None,
+                            }).collect(),
+                            // Reported by the type check.
+                            Err(_) => return Ok(TypeId::None),
+                        },
+                        _ if params.len() != args.len() => {
+                            return Err(TypeResolverError::ArgumentCountMismatch { expected: params.len(), actual: args.len() });
+                        },
+                        _ => params.iter().zip(args).collect::<Vec<_>>(),
+                    };
+
+                    // Arguments of the wrong type are reported by the type check, with the names of the types.
+                    for (_, arg) in passed {
+                        if self.get_type(arg)?.is_none() {
+                            return Ok(TypeId::None);
                         }
                     }
 
-                    Ok(TypeId::None)
+                    Ok(return_type)
                 },
-                Expression::Construct { ident, .. } => {
-                    Ok(self.get_arp_file_in_parent(index.as_weak()).unwrap().type_collection.resolve_name(ident))
+                Expression::Call { on: Some(on), method, args, names, .. } => {
+                    let on_type = self.get_type(on)?;
+
+                    let collection = &arp_file.type_collection;
+                    let arg_types = args.iter().flat_map(|arg| self.get_type(arg)).collect::<Vec<_>>();
+
+                    match collection.resolve_overload(&on_type, method, names, &arg_types) {
+                        Overload::Found(_, mi, bindings) => {
+                            let map = mi.generics.iter().cloned().zip(bindings).collect::<Vec<_>>();
+
+                            collection.find_substituted(&mi.return_type, &map)
+                                .map_err(TypeResolverError::MissingInstantiation)
+                        },
+                        _ => Ok(TypeId::None),
+                    }
                 },
-                Expression::This(ty) => {
-                    Ok(ty.clone())
-                }
-                Expression::GetField { on, ident } => {
-                    let ty = self.get_type(on)?;
-                    if let Some(ty) = self.get_arp_file_in_parent(index.as_weak()).unwrap().type_collection.try_get_strong(&ty) {
-                        Ok(ty.fields.iter().find(|(i, _)| i == ident).map(|(_, ty)| ty.clone()).unwrap_or_default())
-                    } else {
-                        Ok(TypeId::None)
+                Expression::ParamsArray { ty, .. } => Ok(ty.clone()),
+                Expression::Lambda { func, .. } => {
+                    let func = self.get(func);
+
+                    // The return type is inferred from the body first.
+                    if func.return_type.is_none() || func.parameters.iter().any(|(_, ty)| !ty.is_strong()) {
+                        return Ok(TypeId::None);
+                    }
+
+                    let params = func.parameters.iter().map(|(_, ty)| ty.clone()).collect::<Vec<_>>();
+                    let name = arp_file.type_collection.function_name(&params, &func.return_type);
+
+                    match arp_file.type_collection.resolve_name(&name) {
+                        ty @ TypeId::Strong(_) => Ok(ty),
+                        _ => Err(TypeResolverError::MissingInstantiation(name)),
                     }
                 },
-                Expression::Call { on, method, args } => {
-                    let on = on.expect("calls without targets not supported");
-                    let on_type = self.get_type(&on)?;
+                Expression::Type(ty) => Ok(ty.clone()),
+                Expression::Match { on, arms } => {
+                    if self.get_type(on)?.is_none() {
+                        return Ok(TypeId::None);
+                    }
 
-                    let return_type = if let Some(ty) = self.get_arp_file_in_parent(index.as_weak()).unwrap().type_collection.try_get_strong(&on_type) {
-                        ty.find_method(method, args.iter().flat_map(|arg| self.get_type(arg)).collect())
-                    } else {
-                        None
+                    let mut result: Option<TypeId> = None;
+                    for arm in arms {
+                        let ty = self.get_type(&arm.expr)?;
+
+                        if ty.is_none() {
+                            return Ok(TypeId::None);
+                        }
+
+                        match &result {
+                            Some(expected) if !expected.is_same(&ty) => {
+                                return Err(TypeResolverError::MatchArmsMismatchedTypes { expected: expected.clone(), actual: ty })
+                            },
+                            Some(_) => {},
+                            None => result = Some(ty),
+                        }
+                    }
+
+                    Ok(result.unwrap_or_else(|| arp_file.type_collection.get_void()))
+                },
+                Expression::Cast { expr, ty } => {
+                    if self.get_type(expr)?.is_none() {
+                        return Ok(TypeId::None);
+                    }
+
+                    Ok(arp_file.type_collection.strengthen(ty))
+                },
+                Expression::New { ty, .. } => Ok(arp_file.type_collection.strengthen(ty)),
+                Expression::Try { expr } => {
+                    let ty = self.get_type(expr)?;
+                    if ty.is_none() {
+                        return Ok(TypeId::None);
+                    }
+
+                    let collection = &arp_file.type_collection;
+                    let return_type = self.get_parent_of_kind::<Function, _>(*index)
+                        .map(|func| self.get(&func).return_type.clone())
+                        .unwrap_or_default();
+
+                    let value_variant = match (collection.get_prelude(&ty), collection.get_prelude(&return_type)) {
+                        (Some(PreludeType::Option), Some(PreludeType::Option)) => "Some",
+                        (Some(PreludeType::Result), Some(PreludeType::Result)) => {
+                            let error_of = |ty: &TypeId| collection.get_variant(ty, "Err").and_then(|err| collection.get_field_type(&err, "error"));
+
+                            match (error_of(&ty), error_of(&return_type)) {
+                                (Some(lhs), Some(rhs)) if lhs.is_same(&rhs) => "Ok",
+                                _ => return Err(TypeResolverError::IncompatiblePropagation { expected: return_type, actual: ty }),
+                            }
+                        },
+                        (Some(_), _) => return Err(TypeResolverError::IncompatiblePropagation { expected: return_type, actual: ty }),
+                        (None, _) => return Err(TypeResolverError::CantPropagate(ty)),
                     };
 
-                    Ok(return_type.map(|mi| mi.return_type.clone()).unwrap_or_default())
-                }
-                Expression::Type(ty) => Ok(ty.clone()),
+                    Ok(collection.get_variant(&ty, value_variant)
+                        .and_then(|variant| collection.get_field_type(&variant, "value"))
+                        .unwrap_or_default())
+                },
+                Expression::Array(elements) => {
+                    let collection = &arp_file.type_collection;
+
+                    // `let items: [int32] = [];` takes the element type from the declaration.
+                    if elements.is_empty() {
+                        return match self.get_parent_of_kind::<Statement, _>(index.as_weak()).map(|stmt| self.get(&stmt)) {
+                            Some(Statement::LocalVariableDeclaration { ty, .. }) if collection.get_array_element(ty).is_some() => Ok(ty.clone()),
+                            _ => Err(TypeResolverError::EmptyArrayLiteral),
+                        };
+                    }
+
+                    let mut element = TypeId::None;
+                    for ty in elements.iter().map(|expr| self.get_type(expr)) {
+                        let ty = ty?;
+
+                        if ty.is_none() {
+                            return Ok(TypeId::None);
+                        }
+
+                        if element.is_none() || collection.is_subtype(&element, &ty) {
+                            element = ty;
+                        } else if !collection.is_subtype(&ty, &element) {
+                            return Err(TypeResolverError::ArrayElementMismatch { expected: element, actual: ty });
+                        }
+                    }
+
+                    let name = format!("[{}]", collection.get_source_name(&element).unwrap_or_default());
+
+                    match collection.resolve_name(&name) {
+                        ty @ TypeId::Strong(_) => Ok(ty),
+                        _ => Err(TypeResolverError::MissingInstantiation(name)),
+                    }
+                },
+                Expression::Index { on, index } => {
+                    let collection = &arp_file.type_collection;
+                    let (on_type, index_type) = (self.get_type(on)?, self.get_type(index)?);
+
+                    if on_type.is_none() || index_type.is_none() {
+                        return Ok(TypeId::None);
+                    }
+
+                    if !index_type.is_same(&collection.get_int()) {
+                        return Err(TypeResolverError::UnexpectedType { expected: collection.get_int(), actual: index_type });
+                    }
+
+                    collection.get_array_element(&on_type).ok_or(TypeResolverError::NotAnArray(on_type))
+                },
+                Expression::Tuple(elements) => {
+                    let collection = &arp_file.type_collection;
+
+                    if !(2..=MAX_TUPLE_LEN).contains(&elements.len()) {
+                        return Err(TypeResolverError::UnsupportedTupleLength(elements.len()));
+                    }
+
+                    let mut names = vec![];
+                    for ty in elements.iter().map(|expr| self.get_type(expr)) {
+                        let ty = ty?;
+
+                        if ty.is_none() {
+                            return Ok(TypeId::None);
+                        }
+
+                        names.push(collection.get_source_name(&ty).unwrap_or_default().to_string());
+                    }
+
+                    let name = format!("({})", names.join(", "));
+
+                    match collection.resolve_name(&name) {
+                        ty @ TypeId::Strong(_) => Ok(ty),
+                        _ => Err(TypeResolverError::MissingInstantiation(name)),
+                    }
+                },
             }
         } else {
             Err(TypeResolverError::ArpFileNotFound)
@@ -218,13 +555,381 @@ impl Ast {
 }
 
 
+impl Ast {
+    /// Type of variable `var_ident` as seen from `index`. Lambdas see the variables around them,
+    /// top level functions are values of their function type.
+    pub fn get_variable_type(&self, index: &Id<Expression>, var_ident: &Identifier) -> Result<TypeId, TypeResolverError> {
+        if let Some(binding) = self.lookup_binding(index.as_weak(), var_ident) {
+            return self.get_binding_type(&binding, var_ident);
+        }
+
+        match self.get_function_value(index, None, var_ident) {
+            Some(func) => self.get_function_value_type(&func),
+            None => Ok(TypeId::None),
+        }
+    }
+
+    /// Top level function `ident` called at `index`, as a method. Function values held by variables aren't methods,
+    /// they have no parameter names or defaults. Generic functions are called with type arguments inferred from the arguments.
+    pub fn get_top_level_method(&self, index: &Id<Expression>, ident: &Identifier) -> Option<MethodInfo> {
+        if self.lookup_binding(index.as_weak(), ident).is_some() {
+            return None;
+        }
+
+        let arp_file = self.get_arp_file_in_parent(index.as_weak())?;
+        let func = arp_file.functions.iter().find(|func| &self.get(func).name == ident && self.get(func).kind == FunctionKind::Static)?;
+
+        Some(MethodInfo::from_function(*func, self.get(func).clone()))
+    }
+
+    /// Whether the call at `index` doesn't resolve to a method or function, although its receiver and arguments are typed.
+    pub fn is_unresolved_call(&self, index: &Id<Expression>) -> bool {
+        let Expression::Call { on, args, .. } = self.get(index) else { return false };
+        let is_typed = |expr: &Id<Expression>| self.get_type(expr).is_ok_and(|ty| ty.is_strong());
+
+        self.get_type(index).is_ok_and(|ty| ty.is_none()) && on.iter().chain(args).all(is_typed)
+    }
+
+    /// Lambda expression whose body is `func`.
+    pub fn get_enclosing_lambda(&self, func: &Id<Function>) -> Option<Id<Expression>> {
+        self.try_promote::<Expression>(self.get_node(func).get_parent())
+            .filter(|expr| matches!(self.get(expr), Expression::Lambda { .. }))
+    }
+
+    /// Function named `ident` used as a value, a static method of `on` or a top level function of the file.
+    /// Generic functions can't be used as values, their type parameters would stay unbound.
+    pub fn get_function_value(&self, index: &Id<Expression>, on: Option<&TypeId>, ident: &Identifier) -> Option<Id<Function>> {
+        let arp_file = self.get_arp_file_in_parent(index.as_weak())?;
+
+        let mut candidates = match on {
+            Some(ty) => arp_file.type_collection.try_get_strong(ty)?.methods.iter()
+                .filter(|mi| &mi.name == ident)
+                .filter_map(|mi| mi.definition)
+                .collect::<Vec<_>>(),
+            None => arp_file.functions.iter().filter(|func| &self.get(func).name == ident).copied().collect(),
+        };
+
+        candidates.retain(|func| self.get(func).kind == FunctionKind::Static && self.get(func).generics.is_empty());
+        candidates.first().copied()
+    }
+
+    /// Function type of `func`, `fn(int32) -> bool` for `fn is_even(x: int32) -> bool`.
+    pub fn get_function_value_type(&self, func: &Id<Function>) -> Result<TypeId, TypeResolverError> {
+        let collection = &self.get_arp_file_in_parent(func.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+        let func = self.get(func);
+
+        let params = func.parameters.iter().map(|(_, ty)| ty.clone()).collect::<Vec<_>>();
+        let name = collection.function_name(&params, &func.return_type);
+
+        match collection.resolve_name(&name) {
+            ty @ TypeId::Strong(_) => Ok(ty),
+            _ => Err(TypeResolverError::MissingInstantiation(name)),
+        }
+    }
+
+    /// Looks for `ident` among the bindings of the match arms enclosing `index`.
+    pub fn get_pattern_binding_type(&self, match_index: &Id<Expression>, pattern: &Pattern, ident: &Identifier) -> Result<Option<TypeId>, TypeResolverError> {
+        match pattern {
+            Pattern::Wildcard => Ok(None),
+            Pattern::Binding(binding) => match binding == ident {
+                true => match self.get(match_index) {
+                    Expression::Match { on, .. } => Ok(Some(self.get_type(on)?)),
+                    _ => Ok(None),
+                },
+                false => Ok(None),
+            },
+            Pattern::Variant { ty, fields } => {
+                let Some((field, _)) = fields.iter().find(|(_, binding)| binding == ident) else { return Ok(None) };
+
+                let variant = self.resolve_type(ty.clone(), &match_index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?;
+
+                Ok(variant.fields.iter().find(|(i, _)| i == field).map(|(_, ty)| ty.clone()))
+            },
+        }
+    }
+
+    fn validate_match(&self, index: &Id<Expression>, on: &Id<Expression>, arms: &[MatchArm]) -> Result<(), ValidationError> {
+        let collection = &self.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+        let name = |ty: &TypeId| -> Box<str> { collection.get_source_name(ty).unwrap_or_default().into() };
+
+        let on_type = self.get_type(on)?;
+        let union = match collection.try_get_strong(&on_type).map(|info| &info.kind) {
+            Some(TypeKind::Union(_)) => Some(on_type.clone()),
+            Some(TypeKind::UnionVariant(union)) => Some(union.clone()),
+            _ => None,
+        };
+        let variants = match union.as_ref().and_then(|union| collection.try_get_strong(union)).map(|info| &info.kind) {
+            Some(TypeKind::Union(variants)) => variants.clone(),
+            _ => vec![],
+        };
+
+        let mut covered: Vec<TypeId> = vec![];
+        let mut has_catch_all = false;
+
+        for arm in arms {
+            match &arm.pattern {
+                Pattern::Variant { ty, fields } => {
+                    let Some(union) = &union else { return Err(ValidationError::MatchOnNonUnion(name(&on_type))) };
+
+                    if !variants.iter().any(|variant| variant.is_same(ty)) {
+                        return Err(ValidationError::PatternNotInUnion(name(ty), name(union)));
+                    }
+
+                    if has_catch_all || covered.iter().any(|variant| variant.is_same(ty)) {
+                        return Err(ValidationError::UnreachableMatchArm(name(ty)));
+                    }
+
+                    let variant_info = collection.try_get_strong(ty).ok_or(TypeResolverError::ArpFileNotFound)?;
+                    if let Some((field, _)) = fields.iter().find(|(field, _)| !variant_info.fields.iter().any(|(i, _)| i == field)) {
+                        return Err(ValidationError::PatternFieldNotFound(name(ty), field.as_ref().into()));
+                    }
+
+                    covered.push(ty.clone());
+                },
+                Pattern::Wildcard | Pattern::Binding(_) => {
+                    // Arms covering every variant already leave nothing for a catch-all.
+                    let is_covered = !variants.is_empty() && variants.iter().all(|variant| covered.iter().any(|ty| ty.is_same(variant)));
+
+                    if has_catch_all || is_covered {
+                        return Err(ValidationError::UnreachableMatchArm(match &arm.pattern {
+                            Pattern::Binding(binding) => binding.as_ref().into(),
+                            _ => "_".into(),
+                        }));
+                    }
+
+                    has_catch_all = true;
+                },
+            }
+        }
+
+        if has_catch_all {
+            return Ok(());
+        }
+
+        let missing = variants.iter()
+            .filter(|variant| !covered.iter().any(|ty| ty.is_same(variant)))
+            .map(name)
+            .collect::<Vec<_>>();
+
+        if union.is_none() || !missing.is_empty() {
+            Err(ValidationError::NonExhaustiveMatch(missing))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+
+impl Ast {
+    /// Method carrying out binary expression `index` together with the expression's type.
+    /// `None` for operators on primitive types, those map onto IL instructions.
+    pub fn get_operator_overload(&self, index: &Id<Expression>) -> Result<Option<(OperatorOverload, TypeId)>, TypeResolverError> {
+        let Expression::Binary { lhs, op, rhs } = self.get(index) else { return Ok(None) };
+
+        let collection = &self.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+        let (lty, rty) = (self.get_type(lhs)?, self.get_type(rhs)?);
+
+        if !lty.is_strong() || !rty.is_strong() || (lty.is_same(&rty) && (collection.is_numeric(&lty) || matches!(op, BinaryOperator::Or | BinaryOperator::And))) {
+            return Ok(None);
+        }
+
+        let string = collection.get_string();
+        if *op == BinaryOperator::Add && lty.is_same(&string) && rty.is_same(&string) {
+            return Ok(Some((OperatorOverload::Concat, string)));
+        }
+
+        let bool = collection.get_bool();
+
+        if let Some(name) = op.method_name() {
+            let name = Identifier::from(name);
+
+            if let Some(method) = collection.find_method(&lty, &name, std::slice::from_ref(&rty)).map(|(mi, _)| mi) {
+                let ty = match op.method_name() {
+                    Some("eq") if method.return_type.is_same(&bool) => Some(bool.clone()),
+                    Some("cmp") if method.return_type.is_same(&collection.get_int()) => Some(bool.clone()),
+                    Some("eq" | "cmp") => None,
+                    _ => Some(method.return_type.clone()),
+                };
+
+                if let Some(ty) = ty {
+                    return Ok(Some((OperatorOverload::Method(name), ty)));
+                }
+            }
+        }
+
+        let managed = [&lty, &rty].into_iter()
+            .filter(|ty| collection.try_get_strong(ty).is_some_and(|info| matches!(info.source, TypeSourceKind::ManagedDll(_))))
+            .find_map(|ty| {
+                let name = Identifier::from(op.managed_name());
+                let (owner, method, _) = collection.find_method_with_owner(ty, &name, &[lty.clone(), rty.clone()])?;
+                Some((OperatorOverload::Managed(owner, name), method.return_type.clone()))
+            });
+
+        Ok(managed)
+    }
+
+    /// Struct receivers are accessed through their address. Locals, arguments, `this` and fields of
+    /// classes have one already, anything else is spilled into a hidden local first.
+    pub fn address_root_to_spill(&self, expr: &Id<Expression>) -> Result<Option<Id<Expression>>, TypeResolverError> {
+        match self.get(expr) {
+            Expression::Variable(_) | Expression::This(_) | Expression::Base(_) | Expression::Type(_) => Ok(None),
+            // Array elements are addressed in place with `ldelema`.
+            Expression::Index { .. } => Ok(None),
+            Expression::GetField { on, ident } => {
+                let collection = &self.get_arp_file_in_parent(expr.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+
+                // Properties and static fields are read into temporaries.
+                if collection.find_field(&self.get_type(on)?, ident.as_ref()).is_none() {
+                    return Ok(Some(*expr));
+                }
+
+                match collection.is_value_type(&self.get_type(on)?) {
+                    true => self.address_root_to_spill(on),
+                    false => Ok(None),
+                }
+            }
+            _ => Ok(Some(*expr)),
+        }
+    }
+
+    /// Whether `index` is part of a lambda body.
+    pub fn is_in_lambda(&self, index: &Id<Expression>) -> bool {
+        self.get_parent_of_kind::<Function, _>(*index).is_some_and(|func| self.get_enclosing_lambda(&func).is_some())
+    }
+
+    /// Type of the member `ident` of `on` a value is assigned to. That's a field, a static field of a managed type
+    /// assigned through the type, or a property.
+    pub fn get_assigned_member_type(&self, on: &Id<Expression>, ident: &Identifier) -> Result<Option<TypeId>, TypeResolverError> {
+        let collection = &self.get_arp_file_in_parent(on.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+        let ty = self.get_type(on)?;
+        let is_static = matches!(self.get(on), Expression::Type(_));
+
+        if let Some(fld) = collection.get_static_field(&ty, ident.as_ref()).filter(|_| is_static) {
+            return Ok(Some(fld.ty.clone()));
+        }
+
+        Ok(collection.get_field_type(&ty, ident.as_ref())
+            .or_else(|| collection.find_property(&ty, ident.as_ref())
+                .filter(|property| property.is_static == is_static)
+                .map(|property| property.ty.clone())))
+    }
+
+    /// Rejects an `Option` or `Result` used where a plain value of type `expected` is required.
+    pub fn check_not_optional(&self, expected: &TypeId, expr: &Id<Expression>) -> Result<(), ValidationError> {
+        let collection = &self.get_arp_file_in_parent(expr.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+        let actual = self.get_type(expr)?;
+
+        if expected.is_none() || collection.get_prelude(expected).is_some() || collection.get_prelude(&actual).is_none() {
+            return Ok(());
+        }
+
+        Err(ValidationError::OptionalUsedAsValue(
+            collection.get_source_name(expected).unwrap_or_default().into(),
+            collection.get_source_name(&actual).unwrap_or_default().into(),
+        ))
+    }
+}
+
+
 impl Validate for Expression {
     fn validate(&self, index: Id<Expression>, ast: &Ast) -> Result<(), ValidationError> {
         ast.get_type(&index)?;
+
+        match self {
+            Expression::Match { on, arms } => ast.validate_match(&index, on, arms)?,
+            Expression::This(_) | Expression::Base(_) if ast.is_in_lambda(&index) => return Err(ValidationError::ThisInLambda),
+            Expression::Variable(ident) if ast.lookup_binding(index.as_weak(), ident).is_none() && ast.get_function_value(&index, None, ident).is_none() => {
+                return Err(ValidationError::UndeclaredVariable(ident.as_ref().into()));
+            },
+            Expression::Lambda { func, captures } => {
+                let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+                let func = ast.get(func);
+
+                // Closure classes aren't generic, so neither are their fields and `Invoke`.
+                let types = func.parameters.iter().chain(captures).map(|(_, ty)| ty).chain(std::iter::once(&func.return_type));
+                if let Some(ty) = types.into_iter().find(|ty| collection.mentions_parameter(ty)) {
+                    return Err(ValidationError::GenericLambda(collection.get_source_name(ty).unwrap_or_default().into()));
+                }
+            },
+            Expression::Base(ty) => {
+                let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+
+                if collection.get_base(ty).is_none() {
+                    return Err(ValidationError::NoBaseClass(collection.get_source_name(ty).unwrap_or("function").into()));
+                }
+            },
+            Expression::Cast { expr, .. } => {
+                let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+                let (from, to) = (ast.get_type(expr)?, ast.get_type(&index)?);
+
+                if from.is_strong() && to.is_strong() && collection.explicit_conversion(&from, &to).is_none() {
+                    return Err(ValidationError::InvalidCast(
+                        collection.get_source_name(&from).unwrap_or_default().into(),
+                        collection.get_source_name(&to).unwrap_or_default().into(),
+                    ));
+                }
+            },
+            Expression::Construct { ident, args, .. } => {
+                let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+                let ty = collection.resolve_name(ident);
+
+                for (field, arg) in args {
+                    if let Some(field_ty) = collection.get_field_type(&ty, field.as_ref()) {
+                        ast.check_not_optional(&field_ty, arg)?;
+                    }
+                }
+            },
+            Expression::Call { on: Some(on), method: method_name, args, .. } => {
+                let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+                let on_ty = ast.get_type(on)?;
+                let method = std::iter::once(on_ty.clone())
+                    .chain(collection.base_chain(&on_ty))
+                    .filter_map(|ty| collection.try_get_strong(&ty))
+                    .find_map(|info| info.methods.iter().find(|mi| &mi.name == method_name && mi.args.len() == args.len()));
+
+                if let Some(method) = method {
+                    for ((_, arg_ty), arg) in method.args.iter().zip(args) {
+                        ast.check_not_optional(arg_ty, arg)?;
+                    }
+                }
+
+                let arg_types = args.iter().map(|arg| ast.get_type(arg)).collect::<Result<Vec<_>, _>>()?;
+
+                if let Some((mi, bindings)) = collection.find_method(&ast.get_type(on)?, method_name, &arg_types) {
+                    check_bounds(collection, &mi.generics, &bindings)?;
+                }
+            },
+            Expression::Call { on: None, method, args, names, .. } => {
+                let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+                let arg_types = args.iter().map(|arg| ast.get_type(arg)).collect::<Result<Vec<_>, _>>()?;
+
+                if let Some(mi) = ast.get_top_level_method(&index, method) {
+                    if let Some(bindings) = collection.bind_generics(&mi, names, &arg_types) {
+                        check_bounds(collection, &mi.generics, &bindings)?;
+                    }
+                }
+            },
+            _ => {},
+        }
+
         Ok(())
     }
 }
 
+/// Type arguments have to implement the bounds of the type parameters they are bound to.
+fn check_bounds(collection: &TypeCollection, generics: &[TypeId], bindings: &[TypeId]) -> Result<(), ValidationError> {
+    for (param, arg) in generics.iter().zip(bindings) {
+        if let Some(bound) = collection.unsatisfied_bound(param, arg) {
+            return Err(ValidationError::UnsatisfiedBound(
+                collection.get_source_name(arg).unwrap_or_default().into(),
+                collection.get_source_name(&bound).unwrap_or_default().into(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Literal {
     Integer(i64),
@@ -250,6 +955,17 @@ impl From<UnaryOp> for UnaryOperator {
 }
 
 
+/// How a binary operator is carried out on operands that aren't primitive.
+#[derive(Debug, PartialEq, Clone)]
+pub enum OperatorOverload {
+    /// `lhs.add(rhs)`, the method can be declared in an impl or come from a trait.
+    Method(Identifier),
+    /// Static operator method of a managed type, like `op_Addition(lhs, rhs)`.
+    Managed(TypeId, Identifier),
+    /// `string + string` is `String.Concat`.
+    Concat,
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum BinaryOperator {
     Or,
@@ -271,6 +987,40 @@ pub enum BinaryOperator {
     Divide
 }
 
+impl BinaryOperator {
+    /// Method implementing the operator for arp types. `==` and `!=` call `eq`, which returns a bool,
+    /// comparisons call `cmp`, which returns a negative number, zero or a positive number.
+    pub fn method_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Add => Some("add"),
+            Self::Subtract => Some("sub"),
+            Self::Multiply => Some("mul"),
+            Self::Divide => Some("div"),
+            Self::Equal | Self::NotEqual => Some("eq"),
+            Self::Greater | Self::GreaterOrEqual | Self::Less | Self::LessOrEqual => Some("cmp"),
+            Self::Or | Self::And => None,
+        }
+    }
+
+    /// Operator method of managed types.
+    pub fn managed_name(&self) -> &'static str {
+        match self {
+            Self::Add => "op_Addition",
+            Self::Subtract => "op_Subtraction",
+            Self::Multiply => "op_Multiply",
+            Self::Divide => "op_Division",
+            Self::Equal => "op_Equality",
+            Self::NotEqual => "op_Inequality",
+            Self::Greater => "op_GreaterThan",
+            Self::GreaterOrEqual => "op_GreaterThanOrEqual",
+            Self::Less => "op_LessThan",
+            Self::LessOrEqual => "op_LessThanOrEqual",
+            Self::Or => "op_BitwiseOr",
+            Self::And => "op_BitwiseAnd",
+        }
+    }
+}
+
 impl From<BinaryOp> for BinaryOperator {
     fn from(value: BinaryOp) -> Self {
         match value {
@@ -1,6 +1,6 @@
-use crate::ast::traits::GetChildren;
+use crate::{ast::traits::GetChildren, validations::{Validate, ValidationError}};
 
-use super::{ast_node_value::Id, function::Function, implementation::Implementation, simple::Identifier, structure::Structure, type_collection::TypeCollection};
+use super::{ast_node_value::{Ast, Id}, function::Function, implementation::Implementation, simple::Identifier, structure::Structure, type_collection::{Overload, TypeCollection, TypeKind, TypeSourceKind}};
 
 
 
@@ -38,4 +38,48 @@ impl GetChildren for ArpFile {
             .chain(self.functions.iter().map(|n| n.as_weak()))
             .collect()
     }
-}
\ No newline at end of file
+}
+
+impl Validate for ArpFile {
+    fn validate(&self, _index: Id<ArpFile>, _ast: &Ast) -> Result<(), ValidationError> {
+        let collection = &self.type_collection;
+        let name = |ty| -> Box<str> { collection.get_source_name(ty).unwrap_or_default().into() };
+
+        if let Some((arg, bound)) = collection.find_unsatisfied_bound() {
+            return Err(ValidationError::UnsatisfiedBound(name(&arg), name(&bound)));
+        }
+
+        for (ty, info) in collection.iter_resolved() {
+            let Some(base) = &info.base else { continue };
+
+            let valid_base = collection.try_get_strong(base).is_some_and(|base| {
+                matches!(base.kind, TypeKind::Class | TypeKind::Generic(_) | TypeKind::Instance { .. })
+                    && !matches!(base.source, TypeSourceKind::Standard | TypeSourceKind::Prelude(_))
+                    && !base.is_value_type
+            });
+
+            if !valid_base {
+                return Err(ValidationError::InvalidBase(name(&ty), name(base)));
+            }
+
+            if collection.has_inheritance_cycle(&ty) {
+                return Err(ValidationError::InheritanceCycle(name(&ty)));
+            }
+
+            // The constructor of an arp class only takes fields, there is nothing to pass to a managed base.
+            let has_parameterless = matches!(collection.resolve_constructor(base, &[], &[]), Overload::Found(_, ctor, _) if ctor.args.is_empty());
+            if !collection.is_managed(&ty) && collection.is_managed(base) && !has_parameterless {
+                return Err(ValidationError::NoBaseConstructor(name(&ty), name(base)));
+            }
+        }
+
+        // Prelude types are emitted as closed classes, `Option<T>` has no IL counterpart yet.
+        for (ty, _) in collection.iter_resolved() {
+            if collection.get_prelude(&ty).is_some() && collection.mentions_parameter(&ty) {
+                return Err(ValidationError::PreludeOfTypeParameter(name(&ty)));
+            }
+        }
+
+        Ok(())
+    }
+}
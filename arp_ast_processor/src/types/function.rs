@@ -2,7 +2,7 @@ use std::vec;
 
 use crate::ast::{index::WeakIndex, traits::GetChildren};
 
-use super::{ast_node_value::Id, block_scope::BlockScope, simple::Identifier, type_collection::TypeId};
+use super::{ast_node_value::{Ast, Id}, block_scope::BlockScope, expression::Literal, simple::Identifier, type_collection::TypeId};
 
 
 
@@ -12,10 +12,17 @@ pub struct Function {
     pub kind: FunctionKind,
     pub name: Identifier,
     pub parameters: Vec<(Identifier, TypeId)>,
+    /// Default values of `parameters`, `fn pad(text: string, width: int32 = 8)`.
+    pub defaults: Vec<Option<Literal>>,
     pub return_type: TypeId,
     pub block: Id<BlockScope>,
+    pub generics: Vec<TypeId>,
 
+    /// Types of the local registers, named after the first local stored in each.
     pub registers: Vec<(Identifier, TypeId)>,
+    /// Register of every local, by the node declaring it: a `let` statement, the body of a match arm,
+    /// or the expression owning a hidden local.
+    pub locals: Vec<(WeakIndex, Identifier, usize)>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -33,8 +40,20 @@ impl GetChildren for Function {
 }
 
 impl Function {
-    pub fn register_index_of(&self, ident: &Identifier) -> Option<usize> {
-        self.registers.iter().position(|i| i.0 == *ident)
+    pub fn register_of(&self, declaration: WeakIndex, ident: &Identifier) -> Option<usize> {
+        self.locals.iter().find(|(node, local, _)| *node == declaration && local == ident).map(|(_, _, register)| *register)
     }
-    
-}
\ No newline at end of file
+
+}
+
+impl Ast {
+    /// Function returning a value with an empty body, `fn parse() -> Config {}`. It's a placeholder which
+    /// throws `NotImplementedException` until the body is written.
+    pub fn is_stub(&self, func: &Id<Function>) -> bool {
+        let func = self.get(func);
+        let returns_value = self.get_arp_file_in_parent(func.block.as_weak())
+            .is_some_and(|file| !func.return_type.is_same(&file.type_collection.get_void()));
+
+        returns_value && self.get(&func.block).statements.is_empty()
+    }
+}
@@ -1,13 +1,17 @@
-use crate::ast::{index::WeakIndex, traits::GetChildren};
+use crate::{ast::{index::WeakIndex, traits::GetChildren}, type_resolver::TypeResolverError, validations::{Validate, ValidationError}};
 
-use super::{ast_node_value::Id, function::Function, type_collection::TypeId};
+use super::{ast_node_value::{Ast, Id}, function::{Function, FunctionKind}, type_collection::{TypeId, TypeKind}};
 
 
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Implementation {
     pub impl_type: TypeId,
+    /// `Printable` of `impl Printable for Point`.
+    pub trait_type: Option<TypeId>,
     pub functions: Vec<Id<Function>>,
+    /// `impl` for a type declared elsewhere, like `impl string`. Its methods are extension methods.
+    pub is_extension: bool,
 }
 
 
@@ -15,4 +19,55 @@ impl GetChildren for Implementation {
     fn get_children(&self) -> Vec<WeakIndex> {
         self.functions.iter().map(|n|n.as_weak()).collect()
     }
-}
\ No newline at end of file
+}
+
+impl Ast {
+    /// Type extended by `func` when it's an extension method.
+    pub fn get_extended_type(&self, func: &Id<Function>) -> Option<TypeId> {
+        let implementation = self.get(&self.try_promote::<Implementation>(self.get_node(func).get_parent())?);
+        implementation.is_extension.then(|| implementation.impl_type.clone())
+    }
+}
+
+impl Validate for Implementation {
+    /// `impl Trait for Type` has to implement every method of the trait without a default body,
+    /// with the same signature, and nothing else.
+    fn validate(&self, index: Id<Implementation>, ast: &Ast) -> Result<(), ValidationError> {
+        let Some(trait_type) = &self.trait_type else { return Ok(()) };
+
+        let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+        let name = |ty: &TypeId| -> Box<str> { collection.get_source_name(ty).unwrap_or_default().into() };
+
+        let trait_info = match collection.try_get_strong(trait_type) {
+            Some(info) if info.kind == TypeKind::Trait => info,
+            _ => return Err(ValidationError::NotATrait(name(trait_type))),
+        };
+
+        let functions = self.functions.iter().map(|id| ast.get(id)).collect::<Vec<_>>();
+
+        for func in functions.iter() {
+            let Some(method) = trait_info.methods.iter().find(|mi| mi.name == func.name) else {
+                return Err(ValidationError::NotATraitMethod(name(trait_type), func.name.0.clone()));
+            };
+
+            let matches = matches!(func.kind, FunctionKind::Method { .. })
+                && func.generics.is_empty()
+                && func.return_type.is_same(&method.return_type)
+                && func.parameters.len() == method.args.len()
+                && func.parameters.iter().zip(method.args.iter()).all(|((_, param), (_, arg))| param.is_same(arg));
+
+            if !matches {
+                return Err(ValidationError::TraitMethodMismatch(name(trait_type), func.name.0.clone()));
+            }
+        }
+
+        let missing = trait_info.methods.iter()
+            .filter(|mi| mi.definition.is_none())
+            .find(|mi| !functions.iter().any(|func| func.name == mi.name));
+
+        match missing {
+            Some(method) => Err(ValidationError::MissingTraitMethod(name(trait_type), method.name.0.clone())),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,7 +1,33 @@
 
+use super::{ast_node_value::Id, expression::Expression};
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Identifier(pub Box<str>);
 
+impl Identifier {
+    /// Name of the hidden local holding an intermediate value of `expr`,
+    /// like the scrutinee of a `match` or the operand of `?`.
+    pub fn temporary(expr: &Id<Expression>) -> Self {
+        Identifier(format!("$tmp_{:?}", expr.as_weak()).into())
+    }
+
+    /// Name of the hidden local holding a struct value that isn't stored anywhere yet,
+    /// like the result of a call, so its fields and methods can be reached through an address.
+    pub fn spilled(expr: &Id<Expression>) -> Self {
+        Identifier(format!("$addr_{:?}", expr.as_weak()).into())
+    }
+
+    /// Name of the compiler generated class holding the captured variables of lambda `expr`, with its body as `Invoke`.
+    pub fn closure(expr: &Id<Expression>) -> Self {
+        Identifier(format!("$Closure_{:?}", expr.as_weak()).into())
+    }
+
+    /// Name of the hidden parameter at `position` whose tuple is destructured into the bound names.
+    pub fn destructured(position: usize) -> Self {
+        Identifier(format!("$tuple_{position}").into())
+    }
+}
+
 impl From<&str> for Identifier {
     fn from(value: &str) -> Self {
         Identifier(value.into())
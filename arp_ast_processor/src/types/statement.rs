@@ -1,4 +1,4 @@
-use crate::{ast::{index::WeakIndex, traits::GetChildren}, validations::{Validate, ValidationError}};
+use crate::{ast::{index::WeakIndex, traits::GetChildren}, type_resolver::TypeResolverError, validations::{Validate, ValidationError}};
 
 use super::{ast_node_value::{Ast, Id}, block_scope::BlockScope, expression::Expression, function::Function, simple::Identifier, type_collection::TypeId};
 
@@ -10,12 +10,25 @@ pub enum Statement {
         field: Identifier,
         expr: Id<Expression>,
     },
+    /// `items[0] = expr;`
+    ElementAssignment {
+        on: Id<Expression>,
+        index: Id<Expression>,
+        expr: Id<Expression>,
+    },
     LocalVariableDeclaration {
         is_mutable: bool,
         ident: Identifier,
         ty: TypeId,
         expr: Id<Expression>,
     },
+    /// `let (a, b) = pair;`, `_` skips an element. `ty` is the type of the whole tuple.
+    Destructure {
+        is_mutable: bool,
+        bindings: Vec<Identifier>,
+        ty: TypeId,
+        expr: Id<Expression>,
+    },
     IfStmt(Vec<IfKind>),
     WhileStmt{
         expr: Id<Expression>,
@@ -28,6 +41,8 @@ pub enum Statement {
     },
     Block(Id<BlockScope>),
     Return(Id<Expression>),
+    /// Leaves the innermost `while` or `for` loop.
+    Break,
 }
 
 
@@ -35,13 +50,54 @@ impl Validate for Statement {
     fn validate(&self, index: Id<Statement>, ast: &Ast) -> Result<(), ValidationError> { 
         match self {
             Statement::Expression(_) => Ok(()),
-            Statement::Assignment { .. } => {
-                Ok(())
+            Statement::Assignment { on: Some(on), field, .. } => {
+                let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+                let ty = ast.get_type(on)?;
+
+                if collection.get_array_element(&ty).is_some() {
+                    return Err(ValidationError::ArrayLengthAssignment);
+                }
+
+                // `readonly` and `const` fields and properties without a setter can only be read.
+                let is_static = matches!(ast.get(on), Expression::Type(_));
+                let is_read_only = match collection.get_static_field(&ty, field.as_ref()).filter(|_| is_static) {
+                    Some(fld) => fld.is_read_only,
+                    None => collection.find_field(&ty, field.as_ref()).is_none() && collection.find_property(&ty, field.as_ref())
+                        .is_some_and(|property| property.is_static == is_static && !property.can_write),
+                };
+
+                match is_read_only {
+                    true => Err(ValidationError::ReadOnlyMember(
+                        collection.get_source_name(&ty).unwrap_or_default().into(),
+                        field.as_ref().into(),
+                    )),
+                    false => Ok(()),
+                }
+            },
+            Statement::Assignment { on: None, .. } => Ok(()),
+            Statement::ElementAssignment { on, index: position, expr } => {
+                let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+                let on_ty = ast.get_type(on)?;
+                let element = collection.get_array_element(&on_ty).ok_or(TypeResolverError::NotAnArray(on_ty))?;
+                let (index_ty, expr_ty) = (ast.get_type(position)?, ast.get_type(expr)?);
+
+                if !index_ty.is_same(&collection.get_int()) {
+                    return Err(TypeResolverError::UnexpectedType { expected: collection.get_int(), actual: index_ty }.into());
+                }
+
+                if !collection.is_subtype(&expr_ty, &element) {
+                    return Err(ValidationError::ArrayElementMismatch(
+                        collection.get_source_name(&element).unwrap_or_default().into(),
+                        collection.get_source_name(&expr_ty).unwrap_or_default().into(),
+                    ));
+                }
+
+                ast.check_not_optional(&element, expr)
             },
             Statement::LocalVariableDeclaration { ident, ty, .. } => {
 
                 let func = ast.get_parent_of_kind::<Function, _>(index).ok_or(ValidationError::StatementOutsideFunction)?;
-                ast.get(&func).register_index_of(ident).ok_or(ValidationError::IdentifierHasNoRegister)?;
+                ast.get(&func).register_of(index.as_weak(), ident).ok_or(ValidationError::IdentifierHasNoRegister)?;
 
 
                 if ty.is_none() | ty.is_weak() {
@@ -50,11 +106,43 @@ impl Validate for Statement {
                     Ok(())
                 }
             },
-            Statement::IfStmt(_) => Ok(()),
-            Statement::WhileStmt { .. } => Ok(()),
-            Statement::ForStmt { .. } => Ok(()),
-            Statement::Block(_) => Ok(()),
-            Statement::Return(_) => Ok(()),
+            Statement::Destructure { bindings, ty, .. } => {
+                let func = ast.get_parent_of_kind::<Function, _>(index).ok_or(ValidationError::StatementOutsideFunction)?;
+                let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+
+                if ty.is_none() | ty.is_weak() {
+                    return Err(ValidationError::VariableHasUndeclaredType(bindings.iter().map(|binding| binding.as_ref()).collect::<Vec<_>>().join(", ").into()));
+                }
+
+                let elements = collection.get_tuple_elements(ty)
+                    .ok_or_else(|| ValidationError::NotATuple(collection.get_source_name(ty).unwrap_or_default().into()))?;
+
+                if elements.len() != bindings.len() {
+                    return Err(ValidationError::TupleArityMismatch(elements.len(), bindings.len()));
+                }
+
+                for binding in bindings.iter().filter(|binding| binding.as_ref() != "_") {
+                    ast.get(&func).register_of(index.as_weak(), binding).ok_or(ValidationError::IdentifierHasNoRegister)?;
+                }
+
+                Ok(())
+            },
+            Statement::Break => {
+                let func = ast.get_parent_of_kind::<Function, _>(index);
+                let mut parent = ast.get_parent_of_kind::<Statement, _>(ast.get_node(&index).get_parent());
+
+                // Loops of the function around a lambda can't be left from its body.
+                while let Some(stmt) = parent.filter(|stmt| ast.get_parent_of_kind::<Function, _>(*stmt) == func) {
+                    if let Statement::WhileStmt { .. } | Statement::ForStmt { .. } = ast.get(&stmt) {
+                        return Ok(());
+                    }
+
+                    parent = ast.get_parent_of_kind::<Statement, _>(ast.get_node(&stmt).get_parent());
+                }
+
+                Err(ValidationError::BreakOutsideLoop(ast.get_node(&index).span))
+            },
+            Statement::IfStmt(_) | Statement::WhileStmt { .. } | Statement::ForStmt { .. } | Statement::Block(_) | Statement::Return(_) => Ok(()),
         }
     }
 }
@@ -97,8 +185,11 @@ impl GetChildren for Statement {
             Statement::WhileStmt { expr, block } => vec![expr.as_weak(), block.as_weak()],
             Statement::Block(value) => vec![value.as_weak()],
             Statement::Return(value) => vec![value.as_weak()],
+            Statement::Break => vec![],
             Statement::ForStmt { ident: _, enumerable, block } => vec![enumerable.as_weak(), block.as_weak()],
             Statement::LocalVariableDeclaration { expr, .. } => vec![expr.as_weak()],
+            Statement::Destructure { expr, .. } => vec![expr.as_weak()],
+            Statement::ElementAssignment { on, index, expr } => vec![on.as_weak(), index.as_weak(), expr.as_weak()],
         }
     }
 }
\ No newline at end of file
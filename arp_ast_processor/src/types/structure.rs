@@ -15,6 +15,8 @@ pub enum StructureKind {
     Class,
     Struct,
     Union,
+    Enum,
+    Trait,
 }
 
 impl GetChildren for Structure {
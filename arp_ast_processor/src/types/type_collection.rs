@@ -1,15 +1,17 @@
 use crate::type_resolver::managed_dll_info::SharpTypeInfo;
 
-use super::{ast_node_value::Id, function::Function, simple::Identifier};
+use super::{ast_node_value::Id, expression::Literal, function::Function, simple::Identifier};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct TypeCollection {
     collection: Vec<Type>,
+    /// Methods added by `impl` blocks of this file to types declared elsewhere, imported or managed ones.
+    extensions: Vec<(TypeId, MethodInfo)>,
 }
 
 impl Default for TypeCollection {
     fn default() -> Self {
-        Self { collection: TypeInfo::standard_types() }
+        Self { collection: TypeInfo::standard_types(), extensions: vec![] }
     }
 }
 
@@ -32,6 +34,15 @@ impl TypeCollection {
         }
     }
 
+    /// Strong id of the slot `id` points to once its type is resolved. Types named before
+    /// their declaration are issued as weak ids.
+    pub fn strengthen(&self, id: &TypeId) -> TypeId {
+        match self.try_get_strong(id) {
+            Some(_) => StrongTypeId::new(id.get_index().unwrap_or_default()).into(),
+            None => id.clone(),
+        }
+    }
+
     pub fn try_get_strong(&self, id: &TypeId) -> Option<&TypeInfo> {
         match id {
             TypeId::Strong(s) => self.collection.get(s.index).and_then(|t| match t {
@@ -60,136 +71,1397 @@ impl TypeCollection {
                 },
                 Type::PlaceHolder(n) => n == name.as_ref(),
             })
-            .map(|(index, ty)| {
-                match ty {
-                    Type::Resolved(_) => StrongTypeId::new(index).into(),
-                    Type::PlaceHolder(_) => WeakTypeId::new(index).into(),
-                }
-            }).unwrap_or_default()
+            .map(|(index, ty)| {
+                match ty {
+                    Type::Resolved(_) => StrongTypeId::new(index).into(),
+                    Type::PlaceHolder(_) => WeakTypeId::new(index).into(),
+                }
+            }).unwrap_or_else(|| self.resolve_short_generic_name(name.as_ref()))
+    }
+
+    /// `List<int32>` written with the short name of an imported generic type.
+    fn resolve_short_generic_name(&self, name: &str) -> TypeId {
+        let Some((base, args)) = split_generic_args(name) else { return TypeId::None };
+
+        match self.try_get_strong(&self.resolve_name(base)) {
+            Some(info) if info.full_name.as_ref() != base => self.resolve_name(&format!("{}<{}>", info.full_name, args.join(", "))),
+            _ => TypeId::None,
+        }
+    }
+
+    pub fn copy_from(&mut self, type_info: &TypeInfo, path: &str, from: &TypeCollection) {
+        let info = TypeInfo {
+            full_name: type_info.full_name.clone(),
+            short_name: None,
+            source: TypeSourceKind::ExternalArp(path.to_owned()),
+            kind: match &type_info.kind {
+                TypeKind::Class => TypeKind::Class,
+                TypeKind::Trait => TypeKind::Trait,
+                TypeKind::Enum(members) => TypeKind::Enum(members.clone()),
+                TypeKind::Array(element) => TypeKind::Array(self.translate(from, element)),
+                TypeKind::Tuple(elements) => TypeKind::Tuple(elements.iter().map(|ty| self.translate(from, ty)).collect()),
+                TypeKind::Function { params, return_type } => TypeKind::Function {
+                    params: params.iter().map(|ty| self.translate(from, ty)).collect(),
+                    return_type: self.translate(from, return_type),
+                },
+                TypeKind::Union(variants) => TypeKind::Union(variants.iter().map(|ty| self.translate(from, ty)).collect()),
+                TypeKind::UnionVariant(union) => TypeKind::UnionVariant(self.translate(from, union)),
+                TypeKind::Generic(params) => TypeKind::Generic(params.iter().map(|ty| self.translate(from, ty)).collect()),
+                TypeKind::Instance { definition, args } => TypeKind::Instance {
+                    definition: self.translate(from, definition),
+                    args: args.iter().map(|ty| self.translate(from, ty)).collect(),
+                },
+                TypeKind::Parameter { owner, index, bounds } => TypeKind::Parameter {
+                    owner: *owner,
+                    index: *index,
+                    bounds: bounds.iter().map(|ty| self.translate(from, ty)).collect(),
+                },
+            },
+            fields: type_info.fields.iter().map(|(ident, ty)| (ident.clone(), self.translate(from, ty))).collect(),
+            methods: type_info.methods.iter().map(|mi| MethodInfo {
+                name: mi.name.clone(),
+                args: mi.args.iter().map(|(ident, ty)| (ident.clone(), self.translate(from, ty))).collect(),
+                return_type: self.translate(from, &mi.return_type),
+                generics: mi.generics.iter().map(|ty| self.translate(from, ty)).collect(),
+                defaults: mi.defaults.clone(),
+                is_params: mi.is_params,
+                definition: mi.definition,
+            }).collect(),
+            static_fields: type_info.static_fields.iter().map(|fld| StaticFieldInfo {
+                ty: self.translate(from, &fld.ty),
+                ..fld.clone()
+            }).collect(),
+            properties: type_info.properties.iter().map(|property| PropertyInfo {
+                ty: self.translate(from, &property.ty),
+                ..property.clone()
+            }).collect(),
+            base: type_info.base.as_ref().map(|ty| self.translate(from, ty)),
+            traits: type_info.traits.iter().map(|ty| self.translate(from, ty)).collect(),
+            is_value_type: type_info.is_value_type,
+        };
+
+        let self_type: TypeId = match self.resolve_name(&info.full_name) {
+            TypeId::Strong(_) => return,
+            TypeId::Weak(n) => {
+                self.collection[n.index] = Type::Resolved(info);
+                StrongTypeId::new(n.index).into()
+            },
+            TypeId::None => {
+                self.collection.push(Type::Resolved(info));
+                StrongTypeId::new(self.collection.len() - 1).into()
+            },
+        };
+
+        self.resolve_recursive(self_type.clone());
+        self.instantiate_pending(&self_type);
+    }
+
+    /// Maps a type id issued by another file's collection onto this collection by name.
+    fn translate(&mut self, from: &TypeCollection, ty: &TypeId) -> TypeId {
+        match from.get_source_name(ty) {
+            Some(name) => self.get_or_allocate(&name),
+            None => TypeId::None,
+        }
+    }
+
+    /// Name of the type as it is written in arp sources: keyword aliases for standard types,
+    /// `Option<T>` for prelude instantiations and full names for everything else.
+    pub fn get_source_name(&self, id: &TypeId) -> Option<&str> {
+        match self.collection.get(id.get_index()?)? {
+            Type::Resolved(TypeInfo { short_name: Some(short_name), source: TypeSourceKind::Standard | TypeSourceKind::Prelude(_), .. }) => Some(short_name),
+            ty => Some(ty.get_name()),
+        }
+    }
+
+    pub fn iter_resolved(&self) -> impl Iterator<Item = (TypeId, &TypeInfo)> {
+        self.collection.iter().enumerate().filter_map(|(index, ty)| match ty {
+            Type::Resolved(info) => Some((StrongTypeId::new(index).into(), info)),
+            Type::PlaceHolder(_) => None,
+        })
+    }
+
+    pub fn get_prelude(&self, id: &TypeId) -> Option<PreludeType> {
+        match self.try_get_strong(id)?.source {
+            TypeSourceKind::Prelude(prelude) => Some(prelude),
+            _ => None,
+        }
+    }
+
+    /// Finds variant `name` (e.g. `Some`) of union `id`.
+    pub fn get_variant(&self, id: &TypeId, name: &str) -> Option<TypeId> {
+        match &self.try_get_strong(id)?.kind {
+            TypeKind::Union(variants) => variants.iter()
+                .find(|variant| self.get_name(variant).is_some_and(|full_name| full_name.ends_with(&format!(".{name}"))))
+                .cloned(),
+            _ => None,
+        }
+    }
+
+    pub fn get_field_type(&self, id: &TypeId, field: &str) -> Option<TypeId> {
+        self.find_field(id, field).map(|(_, ty)| ty)
+    }
+
+    /// Finds `field` in `id` or one of its base classes, as `(declaring type, field type)`.
+    pub fn find_field(&self, id: &TypeId, field: &str) -> Option<(TypeId, TypeId)> {
+        std::iter::once(id.clone()).chain(self.base_chain(id)).find_map(|owner| {
+            self.try_get_strong(&owner)?.fields.iter()
+                .find(|(ident, _)| ident.as_ref() == field)
+                .map(|(_, ty)| (owner.clone(), ty.clone()))
+        })
+    }
+
+    /// Static field `field` of the managed type `id`, `Math.PI`.
+    pub fn get_static_field(&self, id: &TypeId, field: &str) -> Option<&StaticFieldInfo> {
+        self.try_get_strong(id)?.static_fields.iter().find(|fld| fld.name.as_ref() == field)
+    }
+
+    /// Finds property `name` in `id` or one of its base classes.
+    pub fn find_property(&self, id: &TypeId, name: &str) -> Option<&PropertyInfo> {
+        std::iter::once(id.clone()).chain(self.base_chain(id)).find_map(|owner| {
+            self.try_get_strong(&owner)?.properties.iter().find(|property| property.name.as_ref() == name)
+        })
+    }
+
+    /// Fields of `id` including the inherited ones, starting with the fields of the topmost base.
+    /// That's the order of constructor parameters, so fields of managed bases are left out.
+    pub fn get_all_fields(&self, id: &TypeId) -> Vec<(Identifier, TypeId)> {
+        let mut chain = self.base_chain(id);
+        chain.reverse();
+        chain.push(id.clone());
+
+        chain.iter()
+            .filter_map(|ty| self.try_get_strong(ty))
+            .filter(|info| !matches!(info.source, TypeSourceKind::ManagedDll(_)))
+            .flat_map(|info| info.fields.iter().cloned())
+            .collect()
+    }
+
+    /// Fields of `id` which aren't among `named`, in declaration order. They take the values given without a name, `MyClass { 5 }`.
+    pub fn unnamed_fields(&self, id: &TypeId, named: &[Identifier]) -> Vec<Identifier> {
+        self.get_all_fields(id).into_iter().map(|(field, _)| field).filter(|field| !named.contains(field)).collect()
+    }
+
+    pub fn get_base(&self, id: &TypeId) -> Option<TypeId> {
+        self.try_get_strong(id)?.base.clone()
+    }
+
+    /// Base classes of `id`, nearest first. Stops at the first repeated type, cycles are reported by validation.
+    pub fn base_chain(&self, id: &TypeId) -> Vec<TypeId> {
+        self.walk_bases(id).0
+    }
+
+    /// Whether `id` inherits from itself, directly or through one of its bases.
+    pub fn has_inheritance_cycle(&self, id: &TypeId) -> bool {
+        self.walk_bases(id).1
+    }
+
+    fn walk_bases(&self, id: &TypeId) -> (Vec<TypeId>, bool) {
+        let mut chain: Vec<TypeId> = vec![];
+        let mut current = self.get_base(id);
+
+        while let Some(base) = current {
+            if base.is_same(id) || chain.iter().any(|ty| ty.is_same(&base)) {
+                return (chain, true);
+            }
+
+            current = self.get_base(&base);
+            chain.push(base);
+        }
+
+        (chain, false)
+    }
+
+    /// Traits implemented by `id` or one of its base classes.
+    pub fn get_traits(&self, id: &TypeId) -> Vec<TypeId> {
+        std::iter::once(id.clone())
+            .chain(self.base_chain(id))
+            .filter_map(|ty| self.try_get_strong(&ty))
+            .flat_map(|info| info.traits.iter().cloned())
+            .collect()
+    }
+
+    /// Whether `ty` is `of`, inherits from it or implements it. Classes are objects as well,
+    /// value types would need boxing first.
+    pub fn is_subtype(&self, ty: &TypeId, of: &TypeId) -> bool {
+        ty.is_same(of)
+            || self.base_chain(ty).iter().any(|base| base.is_same(of))
+            || self.get_traits(ty).iter().any(|implemented| implemented.is_same(of))
+            || (of.is_same(&self.get_object()) && self.try_get_strong(ty).is_some_and(|info| !info.is_value_type && info.kind != TypeKind::Trait))
+    }
+
+    pub fn is_value_type(&self, ty: &TypeId) -> bool {
+        self.try_get_strong(ty).is_some_and(|info| info.is_value_type)
+    }
+
+    /// Whether `ty` is imported from a managed dll, instances of managed generics like `List<int32>` are too.
+    pub fn is_managed(&self, ty: &TypeId) -> bool {
+        match self.try_get_strong(ty) {
+            Some(TypeInfo { kind: TypeKind::Instance { definition, .. }, .. }) => self.is_managed(definition),
+            Some(info) => matches!(info.source, TypeSourceKind::ManagedDll(_)),
+            None => false,
+        }
+    }
+
+    /// Element type of the array `ty`, `int32` for `[int32]`.
+    pub fn get_array_element(&self, ty: &TypeId) -> Option<TypeId> {
+        match &self.try_get_strong(ty)?.kind {
+            TypeKind::Array(element) => Some(element.clone()),
+            _ => None,
+        }
+    }
+
+    /// Element types of the tuple `ty`.
+    pub fn get_tuple_elements(&self, ty: &TypeId) -> Option<&[TypeId]> {
+        match &self.try_get_strong(ty)?.kind {
+            TypeKind::Tuple(elements) => Some(elements),
+            _ => None,
+        }
+    }
+
+    /// Tuple of `elements`, named `(int32, string)`. Fields are named by position, `0` and `1`.
+    pub(crate) fn tuple_of(&mut self, elements: Vec<TypeId>) -> TypeId {
+        let names = elements.iter().map(|ty| self.get_source_name(ty).unwrap_or_default().to_string()).collect::<Vec<_>>();
+        let name = format!("({})", names.join(", "));
+
+        if let ty @ TypeId::Strong(_) = self.resolve_name(&name) {
+            return ty;
+        }
+
+        self.place_resolved(TypeInfo {
+            full_name: name.into(),
+            short_name: None,
+            source: TypeSourceKind::Standard,
+            fields: elements.iter().enumerate().map(|(position, ty)| (position.to_string().into(), ty.clone())).collect(),
+            kind: TypeKind::Tuple(elements),
+            methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: true,
+        })
+    }
+
+    /// Parameter and return types of the function type `ty`.
+    pub fn get_function_signature(&self, ty: &TypeId) -> Option<(&[TypeId], &TypeId)> {
+        match &self.try_get_strong(ty)?.kind {
+            TypeKind::Function { params, return_type } => Some((params, return_type)),
+            _ => None,
+        }
+    }
+
+    /// Whether `ty` is an imported managed delegate, like `System.Comparison<int32>`.
+    pub fn is_delegate(&self, ty: &TypeId) -> bool {
+        self.base_chain(ty).iter().any(|base| self.get_name(base) == Some(DELEGATE_TYPE))
+    }
+
+    /// Signature of a function type, or of the `Invoke` method of a managed delegate.
+    pub fn get_callable_signature(&self, ty: &TypeId) -> Option<(Vec<TypeId>, TypeId)> {
+        if let Some((params, return_type)) = self.get_function_signature(ty) {
+            return Some((params.to_vec(), return_type.clone()));
+        }
+
+        if !self.is_delegate(ty) {
+            return None;
+        }
+
+        let invoke = self.try_get_strong(ty)?.methods.iter().find(|mi| mi.name.as_ref() == "Invoke")?;
+        Some((invoke.args.iter().map(|(_, ty)| ty.clone()).collect(), invoke.return_type.clone()))
+    }
+
+    /// Name of a function type, `fn(int32, int32) -> bool`. Functions returning `void` leave the arrow out, `fn(int32)`.
+    pub fn function_name(&self, params: &[TypeId], return_type: &TypeId) -> String {
+        let params = params.iter().map(|ty| self.get_source_name(ty).unwrap_or_default()).collect::<Vec<_>>().join(", ");
+
+        match return_type.is_same(&self.get_void()) {
+            true => format!("fn({params})"),
+            false => format!("fn({params}) -> {}", self.get_source_name(return_type).unwrap_or_default()),
+        }
+    }
+
+    /// Function type taking `params` and returning `return_type`, a `System.Func` or `System.Action` in IL.
+    pub(crate) fn function_of(&mut self, params: Vec<TypeId>, return_type: TypeId) -> TypeId {
+        let name = self.function_name(&params, &return_type);
+
+        if let ty @ TypeId::Strong(_) = self.resolve_name(&name) {
+            return ty;
+        }
+
+        self.place_resolved(TypeInfo {
+            full_name: name.into(),
+            short_name: None,
+            source: TypeSourceKind::Standard,
+            kind: TypeKind::Function { params, return_type },
+            fields: vec![],
+            methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: false,
+        })
+    }
+
+    /// Base class of imported delegates, only used to tell them apart from other managed classes.
+    fn delegate_base(&mut self) -> TypeId {
+        if let ty @ TypeId::Strong(_) = self.resolve_name(DELEGATE_TYPE) {
+            return ty;
+        }
+
+        self.place_resolved(TypeInfo {
+            full_name: DELEGATE_TYPE.into(),
+            short_name: None,
+            source: TypeSourceKind::Standard,
+            kind: TypeKind::Class,
+            fields: vec![],
+            methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: false,
+        })
+    }
+
+    /// Array of `element`, named `[int32]` after the source name of its element.
+    /// Its only field is the read-only `len`.
+    pub(crate) fn array_of(&mut self, element: &TypeId) -> TypeId {
+        let name = format!("[{}]", self.get_source_name(element).unwrap_or_default());
+
+        if let ty @ TypeId::Strong(_) = self.resolve_name(&name) {
+            return ty;
+        }
+
+        let len = self.get_int();
+
+        self.place_resolved(TypeInfo {
+            full_name: name.into(),
+            short_name: None,
+            source: TypeSourceKind::Standard,
+            kind: TypeKind::Array(element.clone()),
+            fields: vec![("len".into(), len)],
+            methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: false,
+        })
+    }
+
+
+    /// Instantiates `Option<T>` and `Result<T, E>` on first use. Paths to their variants,
+    /// like `Option<int32>.Some`, are resolved as well.
+    fn try_instantiate_prelude(&mut self, name: &str) -> Option<TypeId> {
+        let (generic, variant) = split_generic_path(name)?;
+        let (base, args) = split_generic_args(generic)?;
+
+        let prelude = match (base, args.len()) {
+            ("Option", 1) => PreludeType::Option,
+            ("Result", 2) => PreludeType::Result,
+            _ => return None,
+        };
+
+        let args = args.into_iter().map(|arg| self.get_or_allocate(&arg)).collect::<Vec<_>>();
+        let union = self.instantiate_prelude(prelude, args);
+
+        match variant {
+            Some(variant) => self.get_variant(&union, variant),
+            None => Some(union),
+        }
+    }
+
+    fn instantiate_prelude(&mut self, prelude: PreludeType, args: Vec<TypeId>) -> TypeId {
+        let base = match prelude {
+            PreludeType::Option => "Option",
+            PreludeType::Result => "Result",
+        };
+
+        let arg_names = args.iter().map(|arg| self.get_source_name(arg).unwrap_or_default().to_string()).collect::<Vec<_>>();
+        let name = format!("{base}<{}>", arg_names.join(", "));
+
+        if let ty @ TypeId::Strong(_) = self.resolve_name(&name) {
+            return ty;
+        }
+
+        // Generic arguments are folded into the class name, so it has to stay a valid IL identifier.
+        let il_name = format!("Prelude.{base}_{}", arg_names.join("_").chars().filter_map(|c| match c {
+            '<' | ',' | '.' => Some('_'),
+            '>' | ' ' => None,
+            c => Some(c),
+        }).collect::<String>());
+
+        let variants: Vec<(&str, Vec<(Identifier, TypeId)>)> = match prelude {
+            PreludeType::Option => vec![
+                ("Some", vec![("value".into(), args[0].clone())]),
+                ("None", vec![]),
+            ],
+            PreludeType::Result => vec![
+                ("Ok", vec![("value".into(), args[0].clone())]),
+                ("Err", vec![("error".into(), args[1].clone())]),
+            ],
+        };
+
+        let union = self.place_resolved(TypeInfo {
+            full_name: il_name.clone().into(),
+            short_name: Some(name.clone()),
+            source: TypeSourceKind::Prelude(prelude),
+            kind: TypeKind::Union(vec![]),
+            fields: vec![],
+            methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: false,
+        });
+
+        let variants = variants.into_iter().map(|(variant, fields)| self.place_resolved(TypeInfo {
+            full_name: format!("{il_name}.{variant}").into(),
+            short_name: Some(format!("{name}.{variant}")),
+            source: TypeSourceKind::Prelude(prelude),
+            kind: TypeKind::UnionVariant(union.clone()),
+            fields,
+            methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: false,
+        })).collect();
+
+        self.set_kind(&union, TypeKind::Union(variants));
+
+        union
+    }
+
+    fn place_resolved(&mut self, info: TypeInfo) -> TypeId {
+        match self.resolve_name(info.short_name.as_deref().unwrap_or(&info.full_name)) {
+            TypeId::Weak(n) => {
+                self.collection[n.index] = Type::Resolved(info);
+                StrongTypeId::new(n.index).into()
+            },
+            _ => {
+                self.collection.push(Type::Resolved(info));
+                StrongTypeId::new(self.collection.len() - 1).into()
+            },
+        }
+    }
+
+    pub fn insert_external<P : AsRef<str>>(&mut self, path: P, external: &SharpTypeInfo) {
+        if let existing @ TypeId::Strong(_) = self.resolve_name(external.arp_name()) {
+            // Importing a standard type like `System.String` makes its static methods available.
+            if self.try_get_strong(&existing).is_some_and(|info| info.source == TypeSourceKind::Standard && info.methods.is_empty()) {
+                let methods = self.external_methods(external, &[]);
+                self.extend_type_signatures(&existing, methods);
+
+                let (static_fields, properties) = self.external_members(external, &[]);
+                self.set_members(&existing, static_fields, properties);
+            }
+
+            return;
+        }
+
+        let self_type = self.place_resolved(TypeInfo {
+            full_name: external.arp_name().into(),
+            short_name: external.arp_short_name().map(str::to_string),
+            source: TypeSourceKind::ManagedDll(path.as_ref().into()),
+            kind: TypeKind::Class,
+            fields: vec![],
+            methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: external.is_value_type,
+        });
+
+        let params = external.generic_params.iter().enumerate().map(|(index, param)| {
+            self.allocate_parameter(&format!("{}.{param}", external.arp_name()), GenericOwner::Type, index, vec![])
+        }).collect::<Vec<_>>();
+
+        if !params.is_empty() {
+            self.set_kind(&self_type, TypeKind::Generic(params.clone()));
+        } else if external.is_interface {
+            // Generic interfaces like `IComparable<T>` are imported as plain generic classes for now.
+            self.set_kind(&self_type, TypeKind::Trait);
+        } else if external.is_enum {
+            let members = external.enum_members.iter().map(|member| (member.ident.clone().into(), member.value)).collect();
+            self.set_kind(&self_type, TypeKind::Enum(members));
+        }
+
+        // Members of an enum are its static literal fields, the instance field `value__` holds the underlying value.
+        let fields = match external.is_enum {
+            true => external.fields.iter()
+                .filter(|fld| fld.ident == "value__")
+                .map(|fld| (Identifier::from("value"), self.external_type(&fld.ty_full_name, &params).unwrap_or_default()))
+                .collect(),
+            false => external.fields.iter().map(|fld| {
+                (fld.ident.clone().into(), self.external_type(&fld.ty_full_name, &params).unwrap_or_default())
+            }).collect(),
+        };
+
+        let methods = self.external_methods(external, &params);
+        let (static_fields, properties) = self.external_members(external, &params);
+
+        self.set_fields(&self_type, fields);
+        self.set_members(&self_type, static_fields, properties);
+
+        // Lambdas and function values are converted to delegates passed to managed methods, `List.Sort(Comparison)`.
+        if external.is_delegate {
+            let base = self.delegate_base();
+            self.set_base(&self_type, base);
+        }
+
+        if let Some(Type::Resolved(info)) = self_type.get_index().and_then(|index| self.collection.get_mut(index)) {
+            info.methods = methods;
+        }
+
+        self.resolve_recursive(self_type.clone());
+        self.instantiate_pending(&self_type);
+    }
+
+    /// Methods with their own type parameters, like `ConvertAll<TOutput>`, aren't supported yet.
+    /// Constructors are methods named [`CONSTRUCTOR`].
+    /// Parameters with a default arp can't spell, like `null`, have to be passed.
+    fn external_methods(&mut self, external: &SharpTypeInfo, params: &[TypeId]) -> Vec<MethodInfo> {
+        external.methods.iter().chain(&external.constructors).filter_map(|mtd| {
+            let args = mtd.args.iter().map(|arg| {
+                Some((arg.ident.clone().into(), self.external_type(&arg.ty_full_name, params)?))
+            }).collect::<Option<Vec<(Identifier, TypeId)>>>()?;
+
+            let defaults = mtd.defaults.iter().zip(&args)
+                .map(|(value, (_, ty))| value.as_ref().and_then(|value| self.external_constant(value, ty)))
+                .collect();
+
+            Some(MethodInfo {
+                name: mtd.ident.clone().into(),
+                return_type: self.external_type(&mtd.return_ty_full_name, params)?,
+                args,
+                generics: vec![],
+                defaults,
+                is_params: mtd.is_params,
+                definition: None,
+            })
+        }).collect()
+    }
+
+    /// Static fields and properties of a managed type. Members of types which can't be imported are left out,
+    /// so are literal fields with constants arp can't spell.
+    fn external_members(&mut self, external: &SharpTypeInfo, params: &[TypeId]) -> (Vec<StaticFieldInfo>, Vec<PropertyInfo>) {
+        let static_fields = external.static_fields.iter().filter_map(|fld| {
+            let ty = self.external_type(&fld.ty_full_name, params)?;
+            let constant = match &fld.constant {
+                Some(value) => Some(self.external_constant(value, &ty)?),
+                None => None,
+            };
+
+            Some(StaticFieldInfo { name: fld.ident.clone().into(), ty, is_read_only: fld.is_read_only, constant })
+        }).collect();
+
+        let properties = external.properties.iter().filter_map(|property| {
+            Some(PropertyInfo {
+                name: property.ident.clone().into(),
+                ty: self.external_type(&property.ty_full_name, params)?,
+                is_static: property.is_static,
+                can_read: property.can_read,
+                can_write: property.can_write,
+            })
+        }).collect();
+
+        (static_fields, properties)
+    }
+
+    /// Value of a literal field of type `ty`, numbers are exported without their type.
+    fn external_constant(&self, value: &serde_json::Value, ty: &TypeId) -> Option<Literal> {
+        match value {
+            serde_json::Value::Bool(value) if ty.is_same(&self.get_bool()) => Some(Literal::Bool(*value)),
+            serde_json::Value::String(value) if ty.is_same(&self.get_string()) => Some(Literal::String(value.as_str().into())),
+            serde_json::Value::Number(value) if ty.is_same(&self.get_int()) => value.as_i64().map(Literal::Integer),
+            serde_json::Value::Number(value) if ty.is_same(&self.get_float()) || ty.is_same(&self.get_double()) => value.as_f64().map(Literal::Float),
+            _ => None,
+        }
+    }
+
+    /// Resolves a type name exported from a managed dll. Type parameters of the declaring type are
+    /// spelled `!0`, those of a method `!!0`, constructed generics `List<!0>` and arrays `System.String[]`.
+    /// `System.Func` and `System.Action` are function types.
+    fn external_type(&mut self, name: &str, params: &[TypeId]) -> Option<TypeId> {
+        if name.starts_with("!!") {
+            return None;
+        }
+
+        if name == ACTION_TYPE {
+            let void = self.get_void();
+            return Some(self.function_of(vec![], void));
+        }
+
+        if let Some(element) = name.strip_suffix("[]") {
+            let element = self.external_type(element, params)?;
+            return (!element.is_none()).then(|| self.array_of(&element));
+        }
+
+        if let Some(index) = name.strip_prefix('!') {
+            return params.get(index.parse::<usize>().ok()?).cloned();
+        }
+
+        match split_generic_args(name) {
+            Some((TUPLE_TYPE, args)) => {
+                let elements = args.iter().map(|arg| self.external_type(arg, params)).collect::<Option<Vec<_>>>()?;
+                Some(self.tuple_of(elements))
+            },
+            Some((FUNC_TYPE | ACTION_TYPE, args)) => {
+                let mut args = args.iter().map(|arg| self.external_type(arg, params)).collect::<Option<Vec<_>>>()?;
+
+                let return_type = match name.starts_with(FUNC_TYPE) {
+                    true => args.pop()?,
+                    false => self.get_void(),
+                };
+
+                Some(self.function_of(args, return_type))
+            },
+            Some((base, args)) => {
+                if self.get_generic_params(&self.resolve_name(base))?.len() != args.len() {
+                    return Some(TypeId::None);
+                }
+
+                let args = args.iter().map(|arg| {
+                    let arg = self.external_type(arg, params)?;
+                    self.get_source_name(&arg).map(str::to_string)
+                }).collect::<Option<Vec<_>>>()?;
+
+                Some(self.get_or_allocate(&format!("{base}<{}>", args.join(", "))))
+            },
+            None => Some(self.resolve_name(name)),
+        }
+    }
+
+    pub(crate) fn get_or_allocate<S : AsRef<str>>(&mut self, name: &S) -> TypeId {
+        let name = name.as_ref();
+
+        if let Some(element) = name.strip_prefix('[').and_then(|name| name.strip_suffix(']')) {
+            let element = self.get_or_allocate(&element);
+            return self.array_of(&element);
+        }
+
+        if let Some(elements) = name.strip_prefix('(').and_then(|name| name.strip_suffix(')')) {
+            let elements = split_top_level(elements).into_iter().map(|element| self.get_or_allocate(&element)).collect();
+            return self.tuple_of(elements);
+        }
+
+        if let Some((params, return_type)) = split_function(name) {
+            let params = params.into_iter().map(|param| self.get_or_allocate(&param)).collect();
+            let return_type = match return_type {
+                Some(return_type) => self.get_or_allocate(&return_type),
+                None => self.get_void(),
+            };
+
+            return self.function_of(params, return_type);
+        }
+
+        if let Some(ty) = self.try_instantiate_prelude(name).or_else(|| self.try_instantiate_generic(name)) {
+            return ty;
+        }
+        
+        let ty = self.resolve_name(name);
+
+        if ty.is_none() {
+            self.collection.push(Type::PlaceHolder(name.into()));
+            WeakTypeId::new(self.collection.len() - 1).into()
+        } else {
+            ty
+        }
+    }
+    
+    pub(crate) fn try_allocate(&mut self, weak_id: WeakTypeId, fields: Vec<(Identifier, TypeId)>) -> TypeId {
+        let info = TypeInfo {
+            full_name: self.collection[weak_id.index].get_name().into(),
+            short_name: None,
+            source: TypeSourceKind::LocalArp,
+            kind: TypeKind::Class,
+            fields,
+            methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: false,
+        };
+
+        match self.resolve_name(&info.full_name) {
+            TypeId::Strong(_) => { TypeId::None },
+            TypeId::Weak(n) => {
+                self.collection[n.index] = Type::Resolved(info);
+                StrongTypeId::new(n.index).into()
+            },
+            TypeId::None => {
+                self.collection.push(Type::Resolved(info));
+                StrongTypeId::new(self.collection.len() - 1).into()
+            },
+        }
+    }
+    
+    pub(crate) fn resolve_recursive(&mut self, self_type: TypeId) {
+        if let Some(id) = self_type.try_into_strong() {
+            for ty in self.collection.iter_mut() {
+                if let Type::Resolved(res) = ty {
+                    let references = res.fields.iter_mut().map(|(_, ty)| ty)
+                        .chain(res.methods.iter_mut().flat_map(|mtd| mtd.args.iter_mut().map(|(_, ty)| ty).chain(std::iter::once(&mut mtd.return_type))))
+                        .chain(res.base.iter_mut())
+                        .chain(res.traits.iter_mut())
+                        .chain(match &mut res.kind {
+                            TypeKind::Array(element) => vec![element],
+                            TypeKind::Tuple(elements) => elements.iter_mut().collect(),
+                            TypeKind::Function { params, return_type } => params.iter_mut().chain(std::iter::once(return_type)).collect(),
+                            _ => vec![],
+                        });
+
+                    for ty in references {
+                        if ty.get_index() == self_type.get_index() {
+                            *ty = TypeId::Strong(id.clone());
+                        }
+                    }
+                }
+            }
+
+            let extensions = self.extensions.iter_mut()
+                .flat_map(|(extended, mtd)| std::iter::once(extended)
+                    .chain(mtd.args.iter_mut().map(|(_, ty)| ty))
+                    .chain(std::iter::once(&mut mtd.return_type)));
+
+            for ty in extensions {
+                if ty.get_index() == self_type.get_index() {
+                    *ty = TypeId::Strong(id.clone());
+                }
+            }
+        }
+    }
+
+    pub(crate) fn set_kind(&mut self, ty: &TypeId, kind: TypeKind) {
+        if let Some(Type::Resolved(info)) = ty.get_index().and_then(|index| self.collection.get_mut(index)) {
+            info.kind = kind;
+        }
+    }
+    
+    pub(crate) fn extend_type_methods(&mut self, impl_type: TypeId, functions: Vec<(Id<Function>, Function)>) {
+        let methods = MethodInfo::from_functions(functions);
+
+        // Instances created before the implementation was folded receive the new methods as well.
+        let instances = self.iter_resolved().filter_map(|(id, info)| match &info.kind {
+            TypeKind::Instance { definition, args } if definition.is_same(&impl_type) => Some((id, args.clone())),
+            _ => None,
+        }).collect::<Vec<_>>();
+
+        for (instance, args) in instances {
+            let map = self.parameter_map(&impl_type, &args);
+            let substituted = methods.iter().map(|mi| self.substitute_method(mi, &map)).collect::<Vec<_>>();
+
+            if let Some(Type::Resolved(ty)) = instance.get_index().and_then(|index| self.collection.get_mut(index)) {
+                ty.methods.extend(substituted);
+            }
+        }
+
+        if let Some(Type::Resolved(ty)) = self.collection.get_mut(impl_type.get_index().unwrap()) {
+            ty.methods.extend(methods);
+        }
+    }
+
+    /// Methods of `impl` blocks for `ty` declared elsewhere. They are found after the type's own methods,
+    /// and only by this file.
+    pub(crate) fn add_extension_methods(&mut self, ty: TypeId, functions: Vec<(Id<Function>, Function)>) {
+        self.extensions.extend(MethodInfo::from_functions(functions).into_iter().map(|mi| (ty.clone(), mi)));
+    }
+
+    /// Methods without a definition, like the required methods of a trait.
+    pub(crate) fn extend_type_signatures(&mut self, ty: &TypeId, signatures: Vec<MethodInfo>) {
+        if let Some(Type::Resolved(info)) = ty.get_index().and_then(|index| self.collection.get_mut(index)) {
+            info.methods.extend(signatures);
+        }
+    }
+
+    pub(crate) fn set_base(&mut self, ty: &TypeId, base: TypeId) {
+        if let Some(Type::Resolved(info)) = ty.get_index().and_then(|index| self.collection.get_mut(index)) {
+            info.base = Some(base);
+        }
+    }
+
+    pub(crate) fn add_trait(&mut self, ty: &TypeId, implemented: TypeId) {
+        // Same as with methods, existing instances of a generic type implement the trait too.
+        let instances = self.iter_resolved().filter_map(|(id, info)| match &info.kind {
+            TypeKind::Instance { definition, args } if definition.is_same(ty) => Some((id, args.clone())),
+            _ => None,
+        }).collect::<Vec<_>>();
+
+        for (instance, args) in instances {
+            let map = self.parameter_map(ty, &args);
+            let substituted = self.substitute(&implemented, &map);
+
+            if let Some(Type::Resolved(info)) = instance.get_index().and_then(|index| self.collection.get_mut(index)) {
+                info.traits.push(substituted);
+            }
+        }
+
+        if let Some(Type::Resolved(info)) = ty.get_index().and_then(|index| self.collection.get_mut(index)) {
+            info.traits.push(implemented);
+        }
+    }
+
+    /// Marks `ty` as a `struct`, together with its existing instances.
+    pub(crate) fn set_value_type(&mut self, ty: &TypeId) {
+        let instances = self.iter_resolved().filter_map(|(id, info)| match &info.kind {
+            TypeKind::Instance { definition, .. } if definition.is_same(ty) => Some(id),
+            _ => None,
+        }).collect::<Vec<_>>();
+
+        for id in instances.iter().chain(std::iter::once(ty)) {
+            if let Some(Type::Resolved(info)) = id.get_index().and_then(|index| self.collection.get_mut(index)) {
+                info.is_value_type = true;
+            }
+        }
+    }
+
+    pub(crate) fn set_fields(&mut self, ty: &TypeId, fields: Vec<(Identifier, TypeId)>) {
+        if let Some(Type::Resolved(info)) = ty.get_index().and_then(|index| self.collection.get_mut(index)) {
+            info.fields = fields;
+        }
+    }
+
+    pub(crate) fn set_members(&mut self, ty: &TypeId, static_fields: Vec<StaticFieldInfo>, properties: Vec<PropertyInfo>) {
+        if let Some(Type::Resolved(info)) = ty.get_index().and_then(|index| self.collection.get_mut(index)) {
+            info.static_fields = static_fields;
+            info.properties = properties;
+        }
+    }
+
+    /// Allocates type parameter `full_name`, e.g. `Box.T` for `class Box<T>`.
+    pub(crate) fn allocate_parameter(&mut self, full_name: &str, owner: GenericOwner, index: usize, bounds: Vec<TypeId>) -> TypeId {
+        self.place_resolved(TypeInfo {
+            full_name: full_name.into(),
+            short_name: None,
+            source: TypeSourceKind::LocalArp,
+            kind: TypeKind::Parameter { owner, index, bounds },
+            fields: vec![],
+            methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: false,
+        })
+    }
+
+    pub fn get_generic_params(&self, id: &TypeId) -> Option<&[TypeId]> {
+        match &self.try_get_strong(id)?.kind {
+            TypeKind::Generic(params) => Some(params),
+            _ => None,
+        }
+    }
+
+    /// Instantiates `Box<int32>` once `Box` is declared, until then the name stays a placeholder
+    /// which is picked up by `instantiate_pending`.
+    fn try_instantiate_generic(&mut self, name: &str) -> Option<TypeId> {
+        if split_generic_path(name)? != (name, None) {
+            return None;
+        }
+
+        let (base, args) = split_generic_args(name)?;
+        let definition = self.resolve_name(base);
+
+        if self.get_generic_params(&definition)?.len() != args.len() {
+            return None;
+        }
+
+        let args = args.into_iter().map(|arg| self.get_or_allocate(&arg)).collect();
+        Some(self.instantiate(&definition, args))
+    }
+
+    /// Placeholders like `Box<int32>` allocated before `Box` itself was declared or imported.
+    /// Placeholders spelled with the short name of an imported type, `List<int32>`, are renamed
+    /// to the full one first, so there is a single instance per type.
+    pub(crate) fn instantiate_pending(&mut self, definition: &TypeId) {
+        let Some(info) = self.try_get_strong(definition) else { return };
+        let base = info.full_name.to_string();
+        let short_name = info.short_name.clone();
+
+        let pending = self.collection.iter().enumerate().filter_map(|(index, ty)| match ty {
+            Type::PlaceHolder(name) => match split_generic_args(name) {
+                Some((name, args)) if name == base || short_name.as_deref() == Some(name) => Some((index, format!("{base}<{}>", args.join(", ")))),
+                _ => None,
+            },
+            _ => None,
+        }).collect::<Vec<_>>();
+
+        for (index, name) in pending {
+            self.collection[index] = Type::PlaceHolder(name.clone());
+            self.try_instantiate_generic(&name);
+        }
+    }
+
+    /// Instantiates generic `definition` with `args`. Fields and methods are substituted right away,
+    /// the definition stays reachable through `TypeKind::Instance` for the open signatures.
+    pub(crate) fn instantiate(&mut self, definition: &TypeId, args: Vec<TypeId>) -> TypeId {
+        let Some(info) = self.try_get_strong(definition).cloned() else { return TypeId::None };
+        let TypeKind::Generic(params) = &info.kind else { return TypeId::None };
+
+        // `Box<T>` written inside `Box` itself is the definition.
+        if params.len() == args.len() && params.iter().zip(args.iter()).all(|(param, arg)| param.is_same(arg)) {
+            return definition.clone();
+        }
+
+        let arg_names = args.iter().map(|arg| self.get_source_name(arg).unwrap_or_default().to_string()).collect::<Vec<_>>();
+        let name = format!("{}<{}>", self.get_source_name(definition).unwrap_or_default(), arg_names.join(", "));
+
+        if let ty @ TypeId::Strong(_) = self.resolve_name(&name) {
+            return ty;
+        }
+
+        let map = self.parameter_map(definition, &args);
+
+        // Placed before substitution, so recursive fields like `next: Option<Node<T>>` find it.
+        let instance = self.place_resolved(TypeInfo {
+            full_name: name.into(),
+            short_name: None,
+            source: info.source.clone(),
+            kind: TypeKind::Instance { definition: definition.clone(), args },
+            fields: vec![],
+            methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: info.is_value_type,
+        });
+
+        let fields = info.fields.iter().map(|(ident, ty)| (ident.clone(), self.substitute(ty, &map))).collect();
+        let methods = info.methods.iter().map(|mi| self.substitute_method(mi, &map)).collect();
+        let static_fields = info.static_fields.iter().map(|fld| StaticFieldInfo { ty: self.substitute(&fld.ty, &map), ..fld.clone() }).collect();
+        let properties = info.properties.iter().map(|property| PropertyInfo { ty: self.substitute(&property.ty, &map), ..property.clone() }).collect();
+        let base = info.base.as_ref().map(|ty| self.substitute(ty, &map));
+        let traits = info.traits.iter().map(|ty| self.substitute(ty, &map)).collect();
+
+        if let Some(Type::Resolved(ty)) = instance.get_index().and_then(|index| self.collection.get_mut(index)) {
+            ty.fields = fields;
+            ty.methods = methods;
+            ty.static_fields = static_fields;
+            ty.properties = properties;
+            ty.base = base;
+            ty.traits = traits;
+        }
+
+        self.resolve_recursive(instance.clone());
+
+        instance
+    }
+
+    /// Pairs type parameters of `definition` with `args`.
+    pub fn parameter_map(&self, definition: &TypeId, args: &[TypeId]) -> Vec<(TypeId, TypeId)> {
+        self.get_generic_params(definition).unwrap_or_default().iter().cloned().zip(args.iter().cloned()).collect()
+    }
+
+    fn substitute_method(&mut self, mi: &MethodInfo, map: &[(TypeId, TypeId)]) -> MethodInfo {
+        MethodInfo {
+            name: mi.name.clone(),
+            args: mi.args.iter().map(|(ident, ty)| (ident.clone(), self.substitute(ty, map))).collect(),
+            return_type: self.substitute(&mi.return_type, map),
+            generics: mi.generics.clone(),
+            defaults: mi.defaults.clone(),
+            is_params: mi.is_params,
+            definition: mi.definition,
+        }
+    }
+
+    /// Replaces type parameters in `ty` according to `map`, instantiating generic types on the way.
+    fn substitute(&mut self, ty: &TypeId, map: &[(TypeId, TypeId)]) -> TypeId {
+        match self.substituted_name(ty, map) {
+            Some(name) => self.get_or_allocate(&name),
+            None => ty.clone(),
+        }
+    }
+
+    /// Same as `substitute`, but only finds instantiations that already exist.
+    /// Returns the name of the missing one otherwise.
+    pub fn find_substituted(&self, ty: &TypeId, map: &[(TypeId, TypeId)]) -> Result<TypeId, String> {
+        match self.substituted_name(ty, map) {
+            Some(name) => match self.resolve_name(&name) {
+                ty @ TypeId::Strong(_) => Ok(ty),
+                _ => Err(name),
+            },
+            None => Ok(ty.clone()),
+        }
+    }
+
+    /// Source name of `ty` with its type parameters replaced, `None` if `ty` doesn't mention any of them.
+    fn substituted_name(&self, ty: &TypeId, map: &[(TypeId, TypeId)]) -> Option<String> {
+        if let Some((_, arg)) = map.iter().find(|(param, _)| param.is_same(ty)) {
+            return self.get_source_name(arg).map(str::to_string);
+        }
+
+        if let Some(element) = self.get_array_element(ty) {
+            return self.substituted_name(&element, map).map(|name| format!("[{name}]"));
+        }
+
+        if let Some(elements) = self.get_tuple_elements(ty) {
+            let substituted = elements.iter().map(|element| self.substituted_name(element, map)).collect::<Vec<_>>();
+
+            if substituted.iter().all(Option::is_none) {
+                return None;
+            }
+
+            let names = elements.iter().zip(substituted).map(|(element, name)| {
+                name.unwrap_or_else(|| self.get_source_name(element).unwrap_or_default().to_string())
+            }).collect::<Vec<_>>();
+
+            return Some(format!("({})", names.join(", ")));
+        }
+
+        if let Some((params, return_type)) = self.get_function_signature(ty) {
+            let substituted = params.iter().chain(std::iter::once(return_type)).map(|ty| self.substituted_name(ty, map)).collect::<Vec<_>>();
+
+            if substituted.iter().all(Option::is_none) {
+                return None;
+            }
+
+            let mut names = params.iter().chain(std::iter::once(return_type)).zip(substituted).map(|(ty, name)| {
+                name.unwrap_or_else(|| self.get_source_name(ty).unwrap_or_default().to_string())
+            }).collect::<Vec<_>>();
+
+            let return_name = names.pop().unwrap_or_default();
+
+            return Some(match return_type.is_same(&self.get_void()) {
+                true => format!("fn({})", names.join(", ")),
+                false => format!("fn({}) -> {return_name}", names.join(", ")),
+            });
+        }
+
+        let (base, args) = self.get_generic_args(ty)?;
+        let substituted = args.iter().map(|arg| self.substituted_name(arg, map)).collect::<Vec<_>>();
+
+        if substituted.iter().all(Option::is_none) {
+            return None;
+        }
+
+        let args = args.iter().zip(substituted).map(|(arg, name)| {
+            name.unwrap_or_else(|| self.get_source_name(arg).unwrap_or_default().to_string())
+        }).collect::<Vec<_>>();
+
+        Some(format!("{base}<{}>", args.join(", ")))
+    }
+
+    /// Base name and type arguments of an instantiated generic or prelude type.
+    pub fn get_generic_args(&self, id: &TypeId) -> Option<(String, Vec<TypeId>)> {
+        let info = self.try_get_strong(id)?;
+
+        match (&info.kind, &info.source) {
+            (TypeKind::Instance { definition, args }, _) => Some((self.get_source_name(definition)?.to_string(), args.clone())),
+            // `Box<T>` inside of `Box` is the definition itself.
+            (TypeKind::Generic(params), _) => Some((self.get_source_name(id)?.to_string(), params.clone())),
+            (TypeKind::Union(_), TypeSourceKind::Prelude(prelude)) => {
+                let field = |variant: &str, field: &str| self.get_variant(id, variant).and_then(|variant| self.get_field_type(&variant, field));
+
+                match prelude {
+                    PreludeType::Option => Some(("Option".to_string(), vec![field("Some", "value")?])),
+                    PreludeType::Result => Some(("Result".to_string(), vec![field("Ok", "value")?, field("Err", "error")?])),
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// Finds method `name` of `ty` accepting `arg_types`. Type parameters of the method are inferred
+    /// from the arguments and returned in declaration order. Type parameters expose methods of their bounds.
+    pub fn find_method(&self, ty: &TypeId, name: &Identifier, arg_types: &[TypeId]) -> Option<(&MethodInfo, Vec<TypeId>)> {
+        self.find_method_with_owner(ty, name, arg_types).map(|(_, mi, bindings)| (mi, bindings))
+    }
+
+    /// Same as `find_method`, but also returns the type declaring the method. That's a base class
+    /// for inherited methods, a trait for default methods and a bound for methods of type parameters.
+    pub fn find_method_with_owner(&self, ty: &TypeId, name: &Identifier, arg_types: &[TypeId]) -> Option<(TypeId, &MethodInfo, Vec<TypeId>)> {
+        match self.resolve_overload(ty, name, &[], arg_types) {
+            Overload::Found(owner, mi, bindings) => Some((owner, mi, bindings)),
+            _ => None,
+        }
+    }
+
+    /// Picks the overload of `name` a call with `arg_types` resolves to. `names` are the names of named arguments by position,
+    /// it's empty when every argument is positional. Candidates are looked up on the nearest type declaring an applicable one,
+    /// the best of them wins like in C#: it needs no worse conversion than the others for every argument, and a better one for at least one.
+    pub fn resolve_overload(&self, ty: &TypeId, name: &Identifier, names: &[Option<Identifier>], arg_types: &[TypeId]) -> Overload<'_> {
+        let Some(info) = self.try_get_strong(ty) else { return Overload::NotFound };
+
+        if let TypeKind::Parameter { bounds, .. } = &info.kind {
+            return bounds.iter()
+                .map(|bound| self.resolve_overload(bound, name, names, arg_types))
+                .find(|overload| !matches!(overload, Overload::NotFound))
+                .unwrap_or(Overload::NotFound);
+        }
+
+        let owners = std::iter::once(ty.clone()).chain(self.base_chain(ty)).collect::<Vec<_>>();
+
+        // Default methods of implemented traits come last, implementations are methods of the type itself.
+        // Extension methods only fill in what the type doesn't have.
+        let own = owners.iter().cloned().chain(self.get_traits(ty)).map(|owner| {
+            let methods = self.try_get_strong(&owner).map(|info| info.methods.as_slice()).unwrap_or_default();
+            (owner, self.match_method(methods.iter(), name, names, arg_types))
+        });
+        let extensions = owners.iter().map(|owner| {
+            let extensions = self.extensions.iter().filter(|(extended, _)| extended.is_same(owner)).map(|(_, mi)| mi);
+            (owner.clone(), self.match_method(extensions, name, names, arg_types))
+        });
+
+        own.chain(extensions)
+            .find_map(|(owner, candidates)| match candidates {
+                Ok(Some((mi, bindings))) => Some(Overload::Found(owner, mi, bindings)),
+                Ok(None) => None,
+                Err(candidates) => Some(Overload::Ambiguous(candidates)),
+            })
+            .unwrap_or(Overload::NotFound)
     }
 
-    pub fn copy_from(&mut self, type_info: &TypeInfo, path: &str) {
-        let info = TypeInfo {
-            full_name: type_info.full_name.clone(),
-            short_name: None,
-            source: TypeSourceKind::ExternalArp(path.to_owned()),
-            fields: type_info.fields.clone(),
-            methods: type_info.methods.clone(),
+    /// Picks the constructor `new ty(args)` resolves to. Constructors aren't inherited, only the ones of `ty` are candidates.
+    pub fn resolve_constructor(&self, ty: &TypeId, names: &[Option<Identifier>], arg_types: &[TypeId]) -> Overload<'_> {
+        let Some(info) = self.try_get_strong(ty) else { return Overload::NotFound };
+
+        match self.match_method(info.methods.iter(), &Identifier::from(CONSTRUCTOR), names, arg_types) {
+            Ok(Some((mi, bindings))) => Overload::Found(ty.clone(), mi, bindings),
+            Ok(None) => Overload::NotFound,
+            Err(candidates) => Overload::Ambiguous(candidates),
+        }
+    }
+
+    /// Best applicable method of `methods`, or the equally good candidates if there isn't a single best one.
+    fn match_method<'a>(&self, methods: impl Iterator<Item = &'a MethodInfo>, name: &Identifier, names: &[Option<Identifier>], arg_types: &[TypeId]) -> Result<Option<(&'a MethodInfo, Vec<TypeId>)>, Vec<&'a MethodInfo>> {
+        let applicable = methods
+            .filter(|mi| &mi.name == name)
+            .filter_map(|mi| {
+                let (bound, bindings, conversions) = self.match_arguments(mi, names, arg_types)?;
+                // Candidates taking the arguments as they are beat ones filling in defaults or a `params` array.
+                let is_filled = bound.iter().any(|argument| !matches!(argument, BoundArgument::Argument(_)));
+
+                Some((mi, bindings, (conversions, is_filled)))
+            })
+            .collect::<Vec<_>>();
+
+        let is_better = |(lhs, lhs_filled): &(Vec<Conversion>, bool), (rhs, rhs_filled): &(Vec<Conversion>, bool)| {
+            lhs.iter().zip(rhs).all(|(lhs, rhs)| lhs <= rhs) && (lhs != rhs || (!lhs_filled && *rhs_filled))
         };
 
-        match self.resolve_name(&info.full_name) {
-            TypeId::Strong(_) => { },
-            TypeId::Weak(n) => {
-                self.collection[n.index] = Type::Resolved(info);
-            },
-            TypeId::None => {
-                self.collection.push(Type::Resolved(info));
+        let best = applicable.iter().position(|(_, _, conversions)| {
+            applicable.iter().all(|(_, _, other)| std::ptr::eq(conversions, other) || is_better(conversions, other))
+        });
+
+        match (best, applicable.len()) {
+            (_, 0) => Ok(None),
+            (Some(position), _) => {
+                let (mi, bindings, _) = applicable.into_iter().nth(position).unwrap();
+                Ok(Some((mi, bindings)))
             },
+            // Only the candidates nothing beats are worth listing.
+            (None, _) => Err(applicable.iter()
+                .filter(|(_, _, conversions)| !applicable.iter().any(|(_, _, other)| is_better(other, conversions)))
+                .map(|(mi, _, _)| *mi)
+                .collect()),
         }
     }
 
-    pub fn insert_external<P : AsRef<str>>(&mut self, path: P, external: &SharpTypeInfo) {
-        
-        let mut info = TypeInfo {
-            full_name: external.full_name.clone().into(),
-            short_name: external.short_name.clone(),
-            source: TypeSourceKind::ManagedDll(path.as_ref().into()),
-            fields: vec![],
-            methods: vec![],
-        };
+    /// How a call with `arg_types` passes its arguments to `mi`, `None` if they don't fit. See [`TypeCollection::resolve_overload`] for `names`.
+    pub fn bind_call(&self, mi: &MethodInfo, names: &[Option<Identifier>], arg_types: &[TypeId]) -> Option<Vec<BoundArgument>> {
+        self.match_arguments(mi, names, arg_types).map(|(bound, _, _)| bound)
+    }
+
+    /// Type arguments a call with `arg_types` binds the type parameters of `mi` to, `None` if the arguments don't fit or leave one unbound.
+    pub fn bind_generics(&self, mi: &MethodInfo, names: &[Option<Identifier>], arg_types: &[TypeId]) -> Option<Vec<TypeId>> {
+        self.match_arguments(mi, names, arg_types)
+            .map(|(_, bindings, _)| bindings)
+            .filter(|bindings| bindings.iter().all(TypeId::is_strong))
+    }
+
+    /// Binds the arguments to the parameters of `mi`, together with the type parameters they bind and the conversion
+    /// each argument needs. Like in C#, the expanded form of a `params` method is only used when the arguments don't fit the array itself.
+    fn match_arguments(&self, mi: &MethodInfo, names: &[Option<Identifier>], arg_types: &[TypeId]) -> Option<(Vec<BoundArgument>, Vec<TypeId>, Vec<Conversion>)> {
+        [false, true].into_iter().filter(|expanded| !expanded || mi.is_params).find_map(|expanded| {
+            let bound = mi.bind_arguments(names, arg_types.len(), expanded).ok()?;
+            let mut bindings = vec![TypeId::None; mi.generics.len()];
+            let mut conversions = vec![Conversion::Identity; arg_types.len()];
+
+            for ((_, param), argument) in mi.args.iter().zip(&bound) {
+                match argument {
+                    BoundArgument::Argument(position) => {
+                        conversions[*position] = self.argument_conversion(param, &arg_types[*position], &mi.generics, &mut bindings)?;
+                    },
+                    BoundArgument::Params(positions) => {
+                        let element = self.get_array_element(param)?;
+
+                        for position in positions {
+                            conversions[*position] = self.argument_conversion(&element, &arg_types[*position], &mi.generics, &mut bindings)?;
+                        }
+                    },
+                    BoundArgument::Default(_) => {},
+                }
+            }
+
+            bindings.iter().all(|ty| !ty.is_none()).then_some((bound, bindings, conversions))
+        })
+    }
 
-        for fld in external.fields.iter() {
-            info.fields.push((fld.ident.clone().into(), TypeId::None))
+    /// Conversion passing `arg` for the parameter `param` needs, binding type parameters of the method on the way.
+    fn argument_conversion(&self, param: &TypeId, arg: &TypeId, generics: &[TypeId], bindings: &mut [TypeId]) -> Option<Conversion> {
+        if self.unify(param, arg, generics, bindings) {
+            return Some(match param == arg || generics.iter().any(|generic| generic.is_same(param)) {
+                true => Conversion::Identity,
+                false => Conversion::Reference,
+            });
         }
 
-        for mtd in external.methods.iter() {
-            info.methods.push(MethodInfo {
-                name: mtd.ident.clone().into(),
-                args: mtd.args.iter().map(|arg| {
-                    (arg.ident.clone().into(), self.resolve_name(&arg.ty_full_name))
-                }).collect(),
-                return_type: self.resolve_name(&mtd.return_ty_full_name),
-                definition: None,
-            })
+        self.implicit_conversion(arg, param).filter(|conversion| *conversion > Conversion::Reference)
+    }
+
+    /// Conversion applied implicitly when a `value` is passed where `to` is expected. Numbers widen
+    /// from `int32` over `float32` to `float64`, value types are boxed into `object` or a trait they implement.
+    pub fn implicit_conversion(&self, value: &TypeId, to: &TypeId) -> Option<Conversion> {
+        if value.is_same(to) {
+            return Some(Conversion::Identity);
         }
 
-        // let info = dbg!(info);
+        if self.is_value_type(value) && (to.is_same(&self.get_object()) || (self.is_trait(to) && self.is_subtype(value, to))) {
+            return Some(Conversion::Boxing);
+        }
 
-        match self.resolve_name(&info.full_name) {
-            TypeId::Strong(_) => { },
-            TypeId::Weak(n) => {
-                self.collection[n.index] = Type::Resolved(info);
-            },
-            TypeId::None => {
-                self.collection.push(Type::Resolved(info));
-            },
+        if self.unify(to, value, &[], &mut []) {
+            return Some(Conversion::Reference);
+        }
+
+        let widenings = [self.get_int(), self.get_float(), self.get_double()];
+        let rank = |ty: &TypeId| widenings.iter().position(|numeric| numeric.is_same(ty));
+
+        if let (Some(value), Some(to)) = (rank(value), rank(to)) {
+            return (value < to).then(|| Conversion::Widening(to - value));
         }
+
+        None
     }
-    
-    pub(crate) fn get_or_allocate<S : AsRef<str>>(&mut self, name: &S) -> TypeId {
-        let name = name.as_ref();
-        
-        let ty = self.resolve_name(name);
 
-        if ty.is_none() {
-            self.collection.push(Type::PlaceHolder(name.into()));
-            WeakTypeId::new(self.collection.len() - 1).into()
+    /// How `value as to` converts its operand, `None` if the types can't be converted into each other.
+    /// Numbers convert into each other, enums into `int32` and back, and references up and down their hierarchy.
+    pub fn explicit_conversion(&self, value: &TypeId, to: &TypeId) -> Option<CastKind> {
+        let is_abstract = |ty: &TypeId| ty.is_same(&self.get_object()) || self.is_trait(ty);
+
+        if value.is_same(to) {
+            Some(CastKind::Identity)
+        } else if self.is_numeric(value) && self.is_numeric(to) {
+            Some(CastKind::Numeric)
+        } else if (self.is_enum(value) && to.is_same(&self.get_int())) || (value.is_same(&self.get_int()) && self.is_enum(to)) {
+            Some(CastKind::Enum)
+        } else if self.is_subtype(value, to) || (self.is_value_type(value) && to.is_same(&self.get_object())) {
+            Some(match self.is_value_type(value) {
+                true => CastKind::Box,
+                false => CastKind::Upcast,
+            })
+        } else if is_abstract(value) && (self.is_subtype(to, value) || self.is_trait(to) || (self.is_value_type(to) && value.is_same(&self.get_object()))) {
+            Some(match self.is_value_type(to) {
+                true => CastKind::Unbox,
+                false => CastKind::Downcast,
+            })
+        } else if !self.is_value_type(value) && self.is_subtype(to, value) {
+            Some(CastKind::Downcast)
         } else {
-            ty
+            None
         }
     }
-    
-    pub(crate) fn try_allocate(&mut self, weak_id: WeakTypeId, fields: Vec<(Identifier, TypeId)>) -> TypeId {
-        let info = TypeInfo {
-            full_name: self.collection[weak_id.index].get_name().into(),
-            short_name: None,
-            source: TypeSourceKind::LocalArp,
-            fields,
-            methods: vec![],
-        };
 
-        match self.resolve_name(&info.full_name) {
-            TypeId::Strong(_) => { TypeId::None },
-            TypeId::Weak(n) => {
-                self.collection[n.index] = Type::Resolved(info);
-                StrongTypeId::new(n.index).into()
-            },
-            TypeId::None => {
-                self.collection.push(Type::Resolved(info));
-                StrongTypeId::new(self.collection.len() - 1).into()
+    /// Whether `value as to` may lose information, like `float64` to `float32` or any float to `int32`.
+    pub fn is_lossy_cast(&self, value: &TypeId, to: &TypeId) -> bool {
+        let widenings = [self.get_int(), self.get_float(), self.get_double()];
+        let rank = |ty: &TypeId| widenings.iter().position(|numeric| numeric.is_same(ty));
+
+        matches!((rank(value), rank(to)), (Some(value), Some(to)) if value > to)
+    }
+
+    /// Whether a value of type `value` can be stored where `to` is expected. Value types are boxed
+    /// on the way and numbers widened, the same as for arguments.
+    pub fn is_assignable(&self, value: &TypeId, to: &TypeId) -> bool {
+        self.implicit_conversion(value, to).is_some()
+    }
+
+    fn unify(&self, param: &TypeId, arg: &TypeId, generics: &[TypeId], bindings: &mut [TypeId]) -> bool {
+        if let Some(position) = generics.iter().position(|generic| generic.is_same(param)) {
+            return match &bindings[position] {
+                TypeId::None => {
+                    bindings[position] = arg.clone();
+                    true
+                },
+                bound => bound.is_same(arg),
+            };
+        }
+
+        if param == arg || self.is_subtype(arg, param) {
+            return true;
+        }
+
+        if let (Some(param), Some(arg)) = (self.get_array_element(param), self.get_array_element(arg)) {
+            return self.unify(&param, &arg, generics, bindings);
+        }
+
+        if let (Some(params), Some(args)) = (self.get_tuple_elements(param), self.get_tuple_elements(arg)) {
+            return params.len() == args.len() && params.iter().zip(args).all(|(param, arg)| self.unify(param, arg, generics, bindings));
+        }
+
+        // Function values are passed for function types and managed delegates with the same signature.
+        if let (Some((params, return_type)), Some((args, arg_return))) = (self.get_callable_signature(param), self.get_function_signature(arg)) {
+            return params.len() == args.len()
+                && params.iter().zip(args).all(|(param, arg)| self.unify(param, arg, generics, bindings))
+                && self.unify(&return_type, arg_return, generics, bindings);
+        }
+
+        match (self.get_generic_args(param), self.get_generic_args(arg)) {
+            (Some((param_base, param_args)), Some((arg_base, arg_args))) => {
+                param_base == arg_base
+                    && param_args.len() == arg_args.len()
+                    && param_args.iter().zip(arg_args.iter()).all(|(param, arg)| self.unify(param, arg, generics, bindings))
             },
+            _ => false,
         }
     }
-    
-    pub(crate) fn resolve_recursive(&mut self, self_type: TypeId) {
-        if let Some(id) = self_type.try_into_strong() {
-            match &mut self.collection[id.index] {
-                Type::Resolved(res) => {
-                    for index in 0..res.fields.len() {
-                        if res.fields[index].1.get_index() == self_type.get_index() {
-                            res.fields[index].1 = TypeId::Strong(id.clone());
-                        }
-                    }
-                },
-                Type::PlaceHolder(_) => {},
-            }
+
+    /// Whether `ty` can be used for a type parameter bounded by `bound`.
+    pub fn satisfies(&self, ty: &TypeId, bound: &TypeId) -> bool {
+        self.is_subtype(ty, bound) || match self.try_get_strong(ty).map(|info| &info.kind) {
+            Some(TypeKind::Parameter { bounds, .. }) => bounds.iter().any(|inner| self.satisfies(inner, bound)),
+            _ => false,
         }
     }
-    
-    pub(crate) fn extend_type_methods(&mut self, impl_type: TypeId, functions: Vec<(Id<Function>, Function)>) {
-        if let Some(Type::Resolved(ty)) = self.collection.get_mut(impl_type.get_index().unwrap()) {
-            for (index, func) in functions {
-                ty.methods.push(MethodInfo {
-                    name: func.name,
-                    args: func.parameters,
-                    return_type: func.return_type,
-                    definition: Some(index),
-                });
-            }
+
+    /// First type argument in the collection that doesn't satisfy its parameter's bounds, as `(argument, bound)`.
+    pub fn find_unsatisfied_bound(&self) -> Option<(TypeId, TypeId)> {
+        self.iter_resolved().find_map(|(_, info)| match &info.kind {
+            TypeKind::Instance { definition, args } => self.parameter_map(definition, args)
+                .into_iter()
+                .find_map(|(param, arg)| self.unsatisfied_bound(&param, &arg).map(|bound| (arg, bound))),
+            _ => None,
+        })
+    }
+
+    /// Bound of `param` which `arg` doesn't satisfy.
+    pub fn unsatisfied_bound(&self, param: &TypeId, arg: &TypeId) -> Option<TypeId> {
+        match &self.try_get_strong(param)?.kind {
+            TypeKind::Parameter { bounds, .. } => bounds.iter().find(|bound| !self.satisfies(arg, bound)).cloned(),
+            _ => None,
+        }
+    }
+
+    /// Whether `ty` is a type parameter or is built from one, like `Option<T>`.
+    pub fn mentions_parameter(&self, ty: &TypeId) -> bool {
+        match self.try_get_strong(ty).map(|info| &info.kind) {
+            Some(TypeKind::Parameter { .. }) => true,
+            Some(TypeKind::Array(element)) => self.mentions_parameter(element),
+            Some(TypeKind::Tuple(elements)) => elements.iter().any(|element| self.mentions_parameter(element)),
+            Some(TypeKind::Function { params, return_type }) => params.iter().chain(std::iter::once(return_type)).any(|ty| self.mentions_parameter(ty)),
+            _ => self.get_generic_args(ty).is_some_and(|(_, args)| args.iter().any(|arg| self.mentions_parameter(arg))),
         }
     }
 }
 
+/// Placeholders only live until their declaration is resolved, so resolved types aren't boxed.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq, Clone)]
 pub enum Type {
     Resolved(TypeInfo),
@@ -222,6 +1494,11 @@ impl TypeId {
         }
     }
 
+    /// Compares two ids by the slot they point to, regardless of strength.
+    pub fn is_same(&self, other: &TypeId) -> bool {
+        self.get_index().is_some() && self.get_index() == other.get_index()
+    }
+
     pub fn is_none(&self) -> bool {
         matches!(self, TypeId::None)
     }
@@ -303,50 +1580,367 @@ pub struct TypeInfo {
     pub full_name: Box<str>,
     pub short_name: Option<String>,
     pub source: TypeSourceKind,
+    pub kind: TypeKind,
 
     pub fields: Vec<(Identifier, TypeId)>,
-    pub methods: Vec<MethodInfo>
+    pub methods: Vec<MethodInfo>,
+    /// Static fields of managed types, `Math.PI`.
+    pub static_fields: Vec<StaticFieldInfo>,
+    /// Properties of managed types, `Console.Out`.
+    pub properties: Vec<PropertyInfo>,
+    /// Class this one inherits from, `class Circle : Shape`.
+    pub base: Option<TypeId>,
+    /// Traits implemented with `impl Trait for Type`.
+    pub traits: Vec<TypeId>,
+    /// Declared with `struct`, or a managed value type. Copied on assignment.
+    pub is_value_type: bool,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub enum TypeKind {
+    #[default]
+    Class,
+    /// `trait Printable`, or an imported managed interface.
+    Trait,
+    Union(Vec<TypeId>),
+    UnionVariant(TypeId),
+
+    /// `enum Color { Red, Green = 5 }`, or an imported managed enum. Its only field is
+    /// the underlying `value`.
+    Enum(Vec<(Identifier, i64)>),
+    /// `[int32]`, a managed array of the element type.
+    Array(TypeId),
+    /// `(int32, string)`, a `System.ValueTuple` of the element types.
+    Tuple(Vec<TypeId>),
+    /// `fn(int32) -> bool`, a `System.Func` of the parameter and return types,
+    /// or a `System.Action` of the parameter types for functions returning `void`.
+    Function {
+        params: Vec<TypeId>,
+        return_type: TypeId,
+    },
+
+    /// Class declared with type parameters, `class Box<T>`.
+    Generic(Vec<TypeId>),
+    /// `Box<int32>`. Fields and methods are substituted, `definition` keeps the open ones.
+    Instance {
+        definition: TypeId,
+        args: Vec<TypeId>,
+    },
+    /// `T` of `class Box<T>` or `fn identity<T>`.
+    Parameter {
+        owner: GenericOwner,
+        index: usize,
+        bounds: Vec<TypeId>,
+    },
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GenericOwner {
+    Type,
+    Method,
+}
 
-impl TypeInfo {
-    pub fn find_method(&self, name: &Identifier, arg_types: Vec<TypeId>) -> Option<&MethodInfo> {
+/// Implicit conversion of an argument to a parameter type, from the best to the worst.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Conversion {
+    Identity,
+    /// Subtype passed for its base class, trait or `object`.
+    Reference,
+    /// `int32` passed for `float32` or `float64`, `float32` for `float64`. Counts the steps,
+    /// so the narrower of two wider types is preferred like in C#.
+    Widening(usize),
+    /// Value type passed for `object`.
+    Boxing,
+}
+
+/// Conversion carried out by `value as Type`, see [`TypeCollection::explicit_conversion`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CastKind {
+    Identity,
+    /// Between `int32`, `float32` and `float64`.
+    Numeric,
+    /// Enum to its underlying `int32` and back, the value already is the integer.
+    Enum,
+    /// Class to its base class, trait or `object`.
+    Upcast,
+    /// Value type to `object` or a trait it implements.
+    Box,
+    /// `object` or a trait to a class, or a class to a subclass. Checked at runtime.
+    Downcast,
+    /// `object` or a trait to a value type. Checked at runtime.
+    Unbox,
+}
 
-        // dbg!(&self.methods);
+/// Method a call resolves to, see [`TypeCollection::resolve_overload`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum Overload<'a> {
+    Found(TypeId, &'a MethodInfo, Vec<TypeId>),
+    /// Several candidates fit equally well.
+    Ambiguous(Vec<&'a MethodInfo>),
+    NotFound,
+}
 
-        for (index, mi) in self.methods.iter().enumerate() {
-            if &mi.name == name && mi.args.len() == arg_types.len() && mi.args.iter()
-                    .map(|(_, ty)| ty)
-                    .zip(arg_types.iter())
-                    .all(|(lhs, rhs)| lhs == rhs) {
-                return self.methods.get(index);
-            }
-        }
 
-        None
-    }
-    
+/// `String.Empty`. Literal fields like `Math.PI` have no storage, their `constant` is inlined instead.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StaticFieldInfo {
+    pub name: Identifier,
+    pub ty: TypeId,
+    pub is_read_only: bool,
+    pub constant: Option<Literal>,
+}
+
+/// `Console.ForegroundColor`, read and assigned through its `get_ForegroundColor` and `set_ForegroundColor` methods.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PropertyInfo {
+    pub name: Identifier,
+    pub ty: TypeId,
+    pub is_static: bool,
+    pub can_read: bool,
+    pub can_write: bool,
 }
 
+impl PropertyInfo {
+    pub fn getter(&self) -> Identifier {
+        format!("get_{}", self.name.as_ref()).into()
+    }
+
+    pub fn setter(&self) -> Identifier {
+        format!("set_{}", self.name.as_ref()).into()
+    }
+}
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct MethodInfo {
     pub name: Identifier,
     pub args: Vec<(Identifier, TypeId)>,
     pub return_type: TypeId,
+    pub generics: Vec<TypeId>,
+    /// Default values of `args`, arguments for parameters with one may be left out.
+    pub defaults: Vec<Option<Literal>>,
+    /// The last parameter is a `params` array, its elements may be passed one by one.
+    pub is_params: bool,
 
     pub definition: Option<Id<Function>>
 }
 
+impl MethodInfo {
+    fn from_functions(functions: Vec<(Id<Function>, Function)>) -> Vec<Self> {
+        functions.into_iter().map(|(index, func)| Self::from_function(index, func)).collect()
+    }
+
+    pub fn from_function(index: Id<Function>, func: Function) -> Self {
+        MethodInfo {
+            name: func.name,
+            args: func.parameters,
+            return_type: func.return_type,
+            generics: func.generics,
+            defaults: func.defaults,
+            is_params: false,
+            definition: Some(index),
+        }
+    }
+
+    /// Binds `count` arguments named by `names` to the parameters. Positional arguments come first and fill
+    /// the parameters in order, the named ones go to the parameter of their name. Parameters left out take
+    /// their default value. The `expanded` form of a `params` method collects the trailing positional arguments into the array.
+    pub fn bind_arguments(&self, names: &[Option<Identifier>], count: usize, expanded: bool) -> Result<Vec<BoundArgument>, ArgumentError> {
+        let params_position = (expanded && self.is_params).then(|| self.args.len() - 1);
+        let mut bound: Vec<Option<BoundArgument>> = vec![None; self.args.len()];
+        let mut is_named = false;
+
+        for (position, name) in (0..count).map(|position| names.get(position).and_then(Option::as_ref)).enumerate() {
+            let target = match name {
+                Some(name) => {
+                    is_named = true;
+                    self.args.iter().position(|(ident, _)| ident == name).ok_or_else(|| ArgumentError::Unknown(name.clone()))?
+                },
+                None if is_named => return Err(ArgumentError::PositionalAfterNamed),
+                None => match params_position {
+                    Some(params) if position >= params => {
+                        match &mut bound[params] {
+                            Some(BoundArgument::Params(elements)) => elements.push(position),
+                            slot => *slot = Some(BoundArgument::Params(vec![position])),
+                        }
+
+                        continue;
+                    },
+                    _ if position < self.args.len() => position,
+                    _ => return Err(ArgumentError::TooMany(self.args.len())),
+                },
+            };
+
+            if bound[target].is_some() {
+                return Err(ArgumentError::Duplicate(self.args[target].0.clone()));
+            }
+
+            bound[target] = Some(BoundArgument::Argument(position));
+        }
+
+        bound.into_iter().enumerate().map(|(position, argument)| match argument {
+            Some(argument) => Ok(argument),
+            None if params_position == Some(position) => Ok(BoundArgument::Params(vec![])),
+            None => match self.defaults.get(position).cloned().flatten() {
+                Some(default) => Ok(BoundArgument::Default(default)),
+                None => Err(ArgumentError::Missing(self.args[position].0.clone())),
+            },
+        }).collect()
+    }
+}
+
+/// Where a parameter of a call gets its value from, see [`MethodInfo::bind_arguments`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum BoundArgument {
+    /// Position of the argument passed for the parameter.
+    Argument(usize),
+    /// The argument is left out, the default value of the parameter is passed.
+    Default(Literal),
+    /// Positions of the arguments collected into the `params` array.
+    Params(Vec<usize>),
+}
+
+/// Why arguments don't fit the parameters of a method, regardless of their types.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ArgumentError {
+    /// More positional arguments than the method has parameters.
+    TooMany(usize),
+    Unknown(Identifier),
+    Duplicate(Identifier),
+    Missing(Identifier),
+    PositionalAfterNamed,
+}
+
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum TypeSourceKind {
     LocalArp,
     ExternalArp(String),
     Standard,
-    ManagedDll(String)
+    ManagedDll(String),
+    Prelude(PreludeType),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PreludeType {
+    Option,
+    Result,
+}
+
+/// Splits `Option<int32>.Some` into `Option<int32>` and `Some`.
+fn split_generic_path(name: &str) -> Option<(&str, Option<&str>)> {
+    let mut depth = 0;
+
+    for (index, c) in name.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' if is_arrow(name, index) => {},
+            '>' => {
+                depth -= 1;
+
+                if depth == 0 {
+                    let (generic, rest) = name.split_at(index + 1);
+
+                    return match rest.strip_prefix('.') {
+                        Some(variant) => Some((generic, Some(variant))),
+                        None if rest.is_empty() => Some((generic, None)),
+                        None => None,
+                    };
+                }
+            },
+            _ => {},
+        }
+    }
+
+    None
 }
 
+/// Splits `Result<Option<int32>, string>` into `Result` and its top level arguments.
+fn split_generic_args(name: &str) -> Option<(&str, Vec<String>)> {
+    if name.starts_with("fn(") {
+        return None;
+    }
+
+    let (base, args) = name.strip_suffix('>')?.split_once('<')?;
+
+    Some((base, split_top_level(args)))
+}
+
+/// Splits `Option<int32>, (int32, string)` at the commas outside of any brackets.
+fn split_top_level(list: &str) -> Vec<String> {
+    let mut result = vec![];
+    let mut current = String::new();
+    let mut depth = 0;
+
+    for (index, c) in list.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' if is_arrow(list, index) => {},
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                result.push(current.trim().to_string());
+                current.clear();
+                continue;
+            },
+            _ => {},
+        }
+
+        current.push(c);
+    }
+
+    result.push(current.trim().to_string());
+
+    result
+}
+
+/// Splits `fn(int32, string) -> bool` into its parameters and return type, `fn(int32)` has none.
+fn split_function(name: &str) -> Option<(Vec<String>, Option<String>)> {
+    let rest = name.strip_prefix("fn(")?;
+    let mut depth = 1;
+
+    for (index, c) in rest.char_indices() {
+        match c {
+            '(' | '<' | '[' => depth += 1,
+            '>' if is_arrow(rest, index) => {},
+            ')' | '>' | ']' => depth -= 1,
+            _ => {},
+        }
+
+        if depth == 0 {
+            let (params, tail) = (&rest[..index], rest[index + 1..].trim());
+            let params = match params.trim().is_empty() {
+                true => vec![],
+                false => split_top_level(params),
+            };
+
+            return match tail.strip_prefix("->") {
+                Some(return_type) => Some((params, Some(return_type.trim().to_string()))),
+                None if tail.is_empty() => Some((params, None)),
+                None => None,
+            };
+        }
+    }
+
+    None
+}
+
+/// Whether the `>` at `index` belongs to the `->` of a function type.
+fn is_arrow(name: &str, index: usize) -> bool {
+    name[..index].ends_with('-')
+}
+
+/// Name of the constructors of managed types, `new Random(42)` calls one of them.
+pub const CONSTRUCTOR: &str = ".ctor";
+
+/// Managed name of tuples, `System.ValueTuple<System.Int32, System.String>`.
+const TUPLE_TYPE: &str = "System.ValueTuple";
+
+/// Managed names of function types, `System.Func<System.Int32, System.Boolean>` and `System.Action<System.Int32>`.
+const FUNC_TYPE: &str = "System.Func";
+const ACTION_TYPE: &str = "System.Action";
+
+/// Base class of all managed delegates.
+const DELEGATE_TYPE: &str = "System.MulticastDelegate";
+
 
 impl TypeCollection {
     #[inline]
@@ -364,6 +1958,11 @@ impl TypeCollection {
         self.resolve_name("float32")
     }
 
+    #[inline]
+    pub fn get_double(&self) -> TypeId {
+        self.resolve_name("float64")
+    }
+
     #[inline]
     pub fn get_bool(&self) -> TypeId {
         self.resolve_name("bool")
@@ -373,6 +1972,41 @@ impl TypeCollection {
     pub fn get_string(&self) -> TypeId {
         self.resolve_name("string")
     }
+
+    #[inline]
+    pub fn get_object(&self) -> TypeId {
+        self.resolve_name("object")
+    }
+
+    pub fn get_literal_type(&self, literal: &Literal) -> TypeId {
+        match literal {
+            Literal::Integer(_) => self.get_int(),
+            Literal::Float(_) => self.get_float(),
+            Literal::String(_) => self.get_string(),
+            Literal::Bool(_) => self.get_bool(),
+        }
+    }
+
+    /// Types arithmetic and comparisons are built in for.
+    pub fn is_numeric(&self, ty: &TypeId) -> bool {
+        [self.get_int(), self.get_float(), self.get_double()].iter().any(|numeric| numeric.is_same(ty))
+    }
+
+    pub fn is_trait(&self, ty: &TypeId) -> bool {
+        self.try_get_strong(ty).is_some_and(|info| info.kind == TypeKind::Trait)
+    }
+
+    pub fn is_enum(&self, ty: &TypeId) -> bool {
+        self.try_get_strong(ty).is_some_and(|info| matches!(info.kind, TypeKind::Enum(_)))
+    }
+
+    /// Value of `member` of the enum `ty`, `Color.Green`.
+    pub fn get_enum_value(&self, ty: &TypeId, member: &str) -> Option<i64> {
+        match &self.try_get_strong(ty)?.kind {
+            TypeKind::Enum(members) => members.iter().find(|(ident, _)| ident.as_ref() == member).map(|(_, value)| *value),
+            _ => None,
+        }
+    }
 }
 
 impl TypeInfo {
@@ -384,6 +2018,8 @@ impl TypeInfo {
             Type::Resolved(Self::float()),
             Type::Resolved(Self::bool()),
             Type::Resolved(Self::string()),
+            Type::Resolved(Self::double()),
+            Type::Resolved(Self::object()),
         ]
     }
 
@@ -393,8 +2029,14 @@ impl TypeInfo {
             full_name: "System.Void".into(),
             short_name: Some("void".into()),
             source: TypeSourceKind::Standard,
+            kind: TypeKind::Class,
             fields: vec![],
             methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: false,
         }
     }
 
@@ -404,19 +2046,31 @@ impl TypeInfo {
             full_name: "System.Int32".into(),
             short_name: Some("int32".into()),
             source: TypeSourceKind::Standard,
+            kind: TypeKind::Class,
             fields: vec![],
             methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: true,
         }
     }
 
     #[inline]
     pub fn float() -> Self {
         Self {
-            full_name: "System.Float32".into(),
+            full_name: "System.Single".into(),
             short_name: Some("float32".into()),
             source: TypeSourceKind::Standard,
+            kind: TypeKind::Class,
             fields: vec![],
             methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: true,
         }
     }
 
@@ -426,8 +2080,14 @@ impl TypeInfo {
             full_name: "System.Boolean".into(),
             short_name: Some("bool".into()),
             source: TypeSourceKind::Standard,
+            kind: TypeKind::Class,
             fields: vec![],
             methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: true,
         }
     }
 
@@ -437,8 +2097,48 @@ impl TypeInfo {
             full_name: "System.String".into(),
             short_name: Some("string".into()),
             source: TypeSourceKind::Standard,
+            kind: TypeKind::Class,
+            fields: vec![],
+            methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: false,
+        }
+    }
+
+    #[inline]
+    pub fn double() -> Self {
+        Self {
+            full_name: "System.Double".into(),
+            short_name: Some("float64".into()),
+            source: TypeSourceKind::Standard,
+            kind: TypeKind::Class,
+            fields: vec![],
+            methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: true,
+        }
+    }
+
+    #[inline]
+    pub fn object() -> Self {
+        Self {
+            full_name: "System.Object".into(),
+            short_name: Some("object".into()),
+            source: TypeSourceKind::Standard,
+            kind: TypeKind::Class,
             fields: vec![],
             methods: vec![],
+            static_fields: vec![],
+            properties: vec![],
+            base: None,
+            traits: vec![],
+            is_value_type: false,
         }
     }
 }
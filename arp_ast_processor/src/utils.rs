@@ -1,29 +1,36 @@
-use crate::types::{
-    ast_node_value::{Ast, WId},
+use crate::{scope::Binding, types::{
+    ast_node_value::{Ast, Id, WId},
+    expression::Expression,
     function::{Function, FunctionKind},
     simple::Identifier,
     type_collection::{TypeId, TypeInfo},
-};
+}};
 
 pub enum VariableSource {
     Local(usize),
     Argument(usize),
+    /// Field of the closure of this lambda, copied from the enclosing function when the lambda was created.
+    Captured(Id<Expression>),
 }
 
 impl Ast {
     pub fn get_identifier_source(&self, ident: &Identifier, id: &WId) -> Option<VariableSource> {
-        let func = self
-            .get_parent_of_kind::<Function, _>(*id)
-            .map(|index| self.get(&index));
+        let func_index = self.get_parent_of_kind::<Function, _>(*id)?;
+        let func = self.get(&func_index);
 
-        match func {
-            Some(func) => match func.register_index_of(ident) {
-                Some(register_index) => Some(VariableSource::Local(register_index)),
-                None => {
-                    func.parameters.iter().position(|(i, _)| i == ident).map(|i| if matches!(func.kind, FunctionKind::Static) { i } else { i + 1 }).map(VariableSource::Argument)
-                }
-            },
-            None => None,
+        // Declarations store into their own locals, hidden locals are declared by the expression using them.
+        if let Some(register_index) = func.register_of(*id, ident) {
+            return Some(VariableSource::Local(register_index));
+        }
+
+        match self.lookup_binding(*id, ident)? {
+            binding @ (Binding::Local(_) | Binding::MatchArm(_)) => func.register_of(binding.declaration(), ident).map(VariableSource::Local),
+            Binding::ForLoop(_) => None,
+            Binding::Parameter(_, position) => Some(VariableSource::Argument(match func.kind {
+                FunctionKind::Static => position,
+                FunctionKind::Method { .. } => position + 1,
+            })),
+            Binding::Captured(lambda) => Some(VariableSource::Captured(lambda)),
         }
     }
 
@@ -0,0 +1,32 @@
+use crate::{cfg::ControlFlowGraph, type_resolver::TypeResolverError, types::{ast_node_value::Ast, function::Function}};
+
+use super::ValidationError;
+
+/// Checks the control flow of every function: loops have to be left somehow, no statement may follow
+/// a `return` or `break` in its block, and functions returning a value have to return on every path, unless
+/// their body is empty.
+pub fn validate_control_flow(ast: &Ast) -> Result<(), ValidationError> {
+    for func in ast.get_nodes_of_kind::<Function>() {
+        let cfg = ControlFlowGraph::build(ast, &func);
+        let reachable = cfg.reachable();
+
+        if let Some(lp) = cfg.loops.iter().find(|lp| reachable[lp.header] && !cfg.exits(lp, &reachable)) {
+            return Err(ValidationError::InfiniteLoop(ast.get_node(&lp.statement).span));
+        }
+
+        let unreachable = cfg.blocks.iter().enumerate().find_map(|(block, bb)| (!reachable[block]).then(|| bb.statements.first()).flatten());
+
+        if let Some(stmt) = unreachable {
+            return Err(ValidationError::UnreachableCode(ast.get_node(stmt).span));
+        }
+
+        let collection = &ast.get_arp_file_in_parent(func.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+        let return_type = &ast.get(&func).return_type;
+
+        if reachable[cfg.end] && !return_type.is_none() && !return_type.is_same(&collection.get_void()) && !ast.is_stub(&func) {
+            return Err(ValidationError::MissingReturn(ast.get(&func).name.as_ref().into()));
+        }
+    }
+
+    Ok(())
+}
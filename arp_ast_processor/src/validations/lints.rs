@@ -0,0 +1,34 @@
+use arp_types::span::Span;
+use thiserror::Error;
+
+use crate::types::{ast_node_value::Ast, expression::Expression};
+
+/// Findings which don't stop compilation. They are only collected when asked for, see [`lint`].
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum Lint {
+    #[error("Casting {from} to {to} may lose information")]
+    LossyCast { from: Box<str>, to: Box<str>, span: Span },
+}
+
+/// Collects the lints of the whole tree.
+pub fn lint(ast: &Ast) -> Vec<Lint> {
+    let mut lints = vec![];
+
+    for expr in ast.get_nodes_of_kind::<Expression>() {
+        let Expression::Cast { expr: value, .. } = ast.get(&expr) else { continue };
+        let Some(file) = ast.get_arp_file_in_parent(expr.as_weak()) else { continue };
+        let collection = &file.type_collection;
+
+        if let (Ok(from), Ok(to)) = (ast.get_type(value), ast.get_type(&expr)) {
+            if collection.is_lossy_cast(&from, &to) {
+                lints.push(Lint::LossyCast {
+                    from: collection.get_source_name(&from).unwrap_or_default().into(),
+                    to: collection.get_source_name(&to).unwrap_or_default().into(),
+                    span: ast.get_node(&expr).span,
+                });
+            }
+        }
+    }
+
+    lints
+}
@@ -1,5 +1,11 @@
+use arp_types::span::Span;
 use thiserror::Error;
 
+pub mod control_flow;
+pub mod lints;
+pub mod mutability;
+pub mod type_check;
+
 use crate::{type_resolver::TypeResolverError, types::ast_node_value::{Ast, AstNodeValue, Id}};
 
 #[derive(Error, Debug)]
@@ -8,12 +14,138 @@ pub enum ValidationError {
     #[error("Variable has undeclared type")]
     VariableHasUndeclaredType(Box<str>),
 
+    #[error("{0} isn't declared in this scope")]
+    UndeclaredVariable(Box<str>),
+
     #[error("Variable has weak type")]
     VariableHasWeakType(Box<str>),
 
     #[error("Can't resolve expression type")]
     TypeResolverError(#[from] TypeResolverError),
 
+    #[error("Match is not exhaustive, missing: {0:?}")]
+    NonExhaustiveMatch(Vec<Box<str>>),
+
+    #[error("Match arm {0} is unreachable")]
+    UnreachableMatchArm(Box<str>),
+
+    #[error("Pattern {0} is not a variant of {1}")]
+    PatternNotInUnion(Box<str>, Box<str>),
+
+    #[error("Variant {0} doesn't have field {1}")]
+    PatternFieldNotFound(Box<str>, Box<str>),
+
+    #[error("Can't match {0} against variant patterns")]
+    MatchOnNonUnion(Box<str>),
+
+    #[error("Expected {expected}, found {actual}")]
+    TypeMismatch { expected: Box<str>, actual: Box<str> },
+
+    #[error("Argument {position} of {method} expects {expected}, found {actual}")]
+    ArgumentMismatch { method: Box<str>, position: usize, expected: Box<str>, actual: Box<str> },
+
+    #[error("Call to {method} is ambiguous between {}", .candidates.join(" and "))]
+    AmbiguousCall { method: Box<str>, candidates: Vec<Box<str>> },
+
+    #[error("{method} has no parameter named {name}")]
+    UnknownArgument { method: Box<str>, name: Box<str> },
+
+    #[error("Argument {name} of {method} is passed more than once")]
+    DuplicateArgument { method: Box<str>, name: Box<str> },
+
+    #[error("Call to {method} is missing argument {name}")]
+    MissingArgument { method: Box<str>, name: Box<str> },
+
+    #[error("Positional arguments of {0} have to come before the named ones")]
+    PositionalAfterNamed(Box<str>),
+
+    #[error("Expected {0}, found {1}. Get the value out with `?` or `match` first")]
+    OptionalUsedAsValue(Box<str>, Box<str>),
+
+    #[error("{0} doesn't satisfy bound {1}")]
+    UnsatisfiedBound(Box<str>, Box<str>),
+
+    #[error("{0} can't be instantiated with a type parameter yet")]
+    PreludeOfTypeParameter(Box<str>),
+
+    #[error("{0} can't inherit from {1}")]
+    InvalidBase(Box<str>, Box<str>),
+
+    #[error("{0} inherits from itself")]
+    InheritanceCycle(Box<str>),
+
+    #[error("{1} has no parameterless constructor for {0} to call")]
+    NoBaseConstructor(Box<str>, Box<str>),
+
+    #[error("`base` is used in {0}, which doesn't have a base class")]
+    NoBaseClass(Box<str>),
+
+    #[error("{0} is not a trait")]
+    NotATrait(Box<str>),
+
+    #[error("Method {1} of trait {0} is not implemented")]
+    MissingTraitMethod(Box<str>, Box<str>),
+
+    #[error("{1} is not a method of trait {0}")]
+    NotATraitMethod(Box<str>, Box<str>),
+
+    #[error("Signature of {1} doesn't match its declaration in trait {0}")]
+    TraitMethodMismatch(Box<str>, Box<str>),
+
+    #[error("Fields of {ty} can only be assigned in methods taking `mut this`")]
+    ImmutableThis { ty: Box<str>, binding: Span, write: Span },
+
+    #[error("{name} isn't mutable, declare it with `let mut` to assign to it")]
+    AssignToImmutable { name: Box<str>, binding: Span, write: Span },
+
+    #[error("{method} takes `mut this`, but {name} isn't mutable")]
+    MutatingCallOnImmutable { method: Box<str>, name: Box<str>, binding: Span, write: Span },
+
+    #[error("Can't cast {0} to {1}")]
+    InvalidCast(Box<str>, Box<str>),
+
+    #[error("{ty} has {fields} fields, but {values} values are given")]
+    TooManyFieldValues { ty: Box<str>, fields: usize, values: usize },
+
+    #[error("{0} has no constructor taking ({1})")]
+    NoConstructor(Box<str>, Box<str>),
+
+    #[error("{0} can't be called with ({1})")]
+    NoApplicableMethod(Box<str>, Box<str>),
+
+    #[error("Can't store {1} in an array of {0}")]
+    ArrayElementMismatch(Box<str>, Box<str>),
+
+    #[error("Length of an array can't be assigned")]
+    ArrayLengthAssignment,
+
+    #[error("{1} of {0} is read-only")]
+    ReadOnlyMember(Box<str>, Box<str>),
+
+    #[error("Only tuples can be destructured, found {0}")]
+    NotATuple(Box<str>),
+
+    #[error("Tuple has {0} elements but {1} names are bound")]
+    TupleArityMismatch(usize, usize),
+
+    #[error("`this` and `base` can't be used inside of a lambda")]
+    ThisInLambda,
+
+    #[error("Lambdas can't mention type parameters yet, found {0}")]
+    GenericLambda(Box<str>),
+
+    #[error("{0} doesn't return a value on every path")]
+    MissingReturn(Box<str>),
+
+    #[error("Statement is unreachable")]
+    UnreachableCode(Span),
+
+    #[error("Loop never exits, `break` or `return` out of it")]
+    InfiniteLoop(Span),
+
+    #[error("`break` can only be used inside of a loop")]
+    BreakOutsideLoop(Span),
+
     #[error("")]
     StatementOutsideFunction,
     
@@ -28,6 +160,10 @@ pub trait Validate {
 }
 
 pub fn validate(ast: &Ast) -> Result<(), ValidationError> {
+    debug_assert!(ast.cached_types_in_sync(), "cached expression types are out of sync with the tree");
+
+    type_check::check_types(ast)?;
+
     for node in ast.rec_iter_start_from(ast.get_root_index()).flat_map(|(id, _)| ast.get_weak(id)) {
         match node.get_value() {
             AstNodeValue::Expression(expr) => expr.validate(ast.try_promote(node.get_index()).unwrap(), ast)?,
@@ -36,10 +172,12 @@ pub fn validate(ast: &Ast) -> Result<(), ValidationError> {
             AstNodeValue::Declaration(_) => {},
             AstNodeValue::Structure(_) => {},
             AstNodeValue::Function(_) => {},
-            AstNodeValue::Implementation(_) => {},
-            AstNodeValue::ArpFile(_) => {},
+            AstNodeValue::Implementation(im) => im.validate(ast.try_promote(node.get_index()).unwrap(), ast)?,
+            AstNodeValue::ArpFile(file) => file.validate(ast.try_promote(node.get_index()).unwrap(), ast)?,
             AstNodeValue::Root(_) => {},
         }
     }
-    Ok(())
+
+    control_flow::validate_control_flow(ast)?;
+    mutability::validate_mutability(ast)
 }
\ No newline at end of file
@@ -0,0 +1,128 @@
+use arp_types::span::Span;
+
+use crate::{ast::index::WeakIndex, scope::Binding, type_resolver::TypeResolverError, types::{ast_node_value::{Ast, AstNodeValue, Id}, expression::Expression, function::{Function, FunctionKind}, simple::Identifier, statement::Statement}};
+
+use super::ValidationError;
+
+/// Where a name is bound. Only `let mut` bindings can be written to, parameters,
+/// match and for bindings and captured variables are always immutable.
+struct BindingSite {
+    is_mutable: bool,
+    span: Span,
+}
+
+/// Rejects writes through immutable bindings: reassigning a local, assigning a field
+/// of something rooted in an immutable binding or `this` of a method not taking `mut this`,
+/// and calling a `mut this` method on such a receiver.
+pub fn validate_mutability(ast: &Ast) -> Result<(), ValidationError> {
+    for node in ast.rec_iter_start_from(ast.get_root_index()).flat_map(|(id, _)| ast.get_weak(id)) {
+        match node.get_value() {
+            AstNodeValue::Statement(Statement::Assignment { on, field, .. }) => {
+                let write = node.span;
+
+                match on {
+                    Some(on) => check_receiver(ast, on).map_err(|err| match err {
+                        ReceiverError::Binding { name, binding } => ValidationError::AssignToImmutable { name, binding, write },
+                        ReceiverError::This { ty, binding } => ValidationError::ImmutableThis { ty, binding, write },
+                        ReceiverError::Other(err) => err,
+                    })?,
+                    None => {
+                        if let Some(binding) = find_binding(ast, node.get_index(), field).filter(|binding| !binding.is_mutable) {
+                            return Err(ValidationError::AssignToImmutable { name: field.as_ref().into(), binding: binding.span, write });
+                        }
+                    },
+                }
+            },
+            AstNodeValue::Expression(Expression::Call { on: Some(on), method, args, .. }) => {
+                let Some(index) = ast.try_promote::<Expression>(node.get_index()) else { continue };
+
+                if !calls_mutating_method(ast, &index, on, method, args)? {
+                    continue;
+                }
+
+                check_receiver(ast, on).map_err(|err| match err {
+                    ReceiverError::Binding { name, binding } => ValidationError::MutatingCallOnImmutable { method: method.as_ref().into(), name, binding, write: node.span },
+                    ReceiverError::This { binding, .. } => ValidationError::MutatingCallOnImmutable { method: method.as_ref().into(), name: "this".into(), binding, write: node.span },
+                    ReceiverError::Other(err) => err,
+                })?;
+            },
+            _ => {},
+        }
+    }
+
+    Ok(())
+}
+
+enum ReceiverError {
+    Binding { name: Box<str>, binding: Span },
+    This { ty: Box<str>, binding: Span },
+    Other(ValidationError),
+}
+
+impl From<TypeResolverError> for ReceiverError {
+    fn from(value: TypeResolverError) -> Self {
+        ReceiverError::Other(value.into())
+    }
+}
+
+/// Writes into `on` are allowed if the binding it's rooted in is mutable, `a.b.c` is rooted in `a`.
+/// Receivers which aren't rooted in a binding, like call results, are temporaries and can be written.
+fn check_receiver(ast: &Ast, on: &Id<Expression>) -> Result<(), ReceiverError> {
+    let collection = &ast.get_arp_file_in_parent(on.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+
+    let mut root = *on;
+    while let Expression::GetField { on, .. } = ast.get(&root) {
+        root = *on;
+    }
+
+    match ast.get(&root) {
+        Expression::Variable(ident) => match find_binding(ast, root.as_weak(), ident) {
+            Some(binding) if !binding.is_mutable => Err(ReceiverError::Binding { name: ident.as_ref().into(), binding: binding.span }),
+            _ => Ok(()),
+        },
+        Expression::This(ty) => {
+            let func = ast.get_parent_of_kind::<Function, _>(root.as_weak()).ok_or(ReceiverError::Other(ValidationError::StatementOutsideFunction))?;
+
+            match ast.get(&func).kind {
+                FunctionKind::Method { is_this_mutable: false } => Err(ReceiverError::This {
+                    ty: collection.get_source_name(ty).unwrap_or_default().into(),
+                    binding: ast.get_node(&func).span,
+                }),
+                _ => Ok(()),
+            }
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Whether `on.method(args)` resolves to an arp method taking `mut this`. Managed methods
+/// don't say whether they mutate and are always allowed.
+fn calls_mutating_method(ast: &Ast, index: &Id<Expression>, on: &Id<Expression>, method: &Identifier, args: &[Id<Expression>]) -> Result<bool, ValidationError> {
+    if matches!(ast.get(on), Expression::Type(_)) {
+        return Ok(false);
+    }
+
+    let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+    let arg_types = args.iter().map(|arg| ast.get_type(arg)).collect::<Result<Vec<_>, _>>()?;
+
+    Ok(collection.find_method(&ast.get_type(on)?, method, &arg_types)
+        .and_then(|(mi, _)| mi.definition)
+        .is_some_and(|func| ast.get(&func).kind == FunctionKind::Method { is_this_mutable: true }))
+}
+
+/// Finds the binding `ident` refers to from `index`.
+fn find_binding(ast: &Ast, index: WeakIndex, ident: &Identifier) -> Option<BindingSite> {
+    let site = match ast.lookup_binding(index, ident)? {
+        Binding::Local(stmt) => BindingSite {
+            is_mutable: matches!(ast.get(&stmt), Statement::LocalVariableDeclaration { is_mutable: true, .. } | Statement::Destructure { is_mutable: true, .. }),
+            span: ast.get_node(&stmt).span,
+        },
+        Binding::MatchArm(arm) => BindingSite { is_mutable: false, span: ast.get_weak(ast.get_node(&arm).get_parent())?.span },
+        Binding::ForLoop(stmt) => BindingSite { is_mutable: false, span: ast.get_node(&stmt).span },
+        Binding::Parameter(func, _) => BindingSite { is_mutable: false, span: ast.get_node(&func).span },
+        // Lambdas capture copies, writing to them wouldn't change the variable outside.
+        Binding::Captured(lambda) => BindingSite { is_mutable: false, span: ast.get_node(&lambda).span },
+    };
+
+    Some(site)
+}
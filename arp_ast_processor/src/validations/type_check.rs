@@ -0,0 +1,330 @@
+use crate::{type_resolver::TypeResolverError, types::{ast_node_value::{Ast, AstNodeValue, Id}, expression::Expression, function::Function, statement::{IfKind, Statement}, simple::Identifier, type_collection::{ArgumentError, BoundArgument, MethodInfo, Overload, TypeCollection, TypeId, CONSTRUCTOR}}};
+
+use super::ValidationError;
+
+/// Checks that values fit where they are stored: assigned variables and fields, declared locals,
+/// returned values, arguments of arp methods and constructed fields. Conditions have to be `bool`.
+pub fn check_types(ast: &Ast) -> Result<(), ValidationError> {
+    let nodes = ast.rec_iter_start_from(ast.get_root_index()).flat_map(|(id, _)| ast.get_weak(id)).collect::<Vec<_>>();
+
+    // Children first, so the innermost mismatch is reported.
+    for node in nodes.into_iter().rev() {
+        match node.get_value() {
+            AstNodeValue::Statement(_) => check_statement(ast, &ast.try_promote(node.get_index()).unwrap())?,
+            AstNodeValue::Expression(_) => {
+                let expr = ast.try_promote(node.get_index()).unwrap();
+
+                check_expression(ast, &expr)?;
+                check_resolved(ast, &expr)?;
+            },
+            AstNodeValue::Function(_) => check_defaults(ast, &ast.try_promote(node.get_index()).unwrap())?,
+            _ => {},
+        }
+    }
+
+    Ok(())
+}
+
+fn check_statement(ast: &Ast, index: &Id<Statement>) -> Result<(), ValidationError> {
+    let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+
+    match ast.get(index) {
+        Statement::Assignment { on: None, field, expr } => {
+            if let Some(binding) = ast.lookup_binding(index.as_weak(), field) {
+                let ty = ast.get_binding_type(&binding, field)?;
+
+                check_not_void(ast, &ty, expr)?;
+                ast.check_assignable(&ty, expr)?;
+            }
+        },
+        Statement::Assignment { on: Some(on), field, expr } => {
+            if let Some(field_ty) = ast.get_assigned_member_type(on, field)? {
+                check_not_void(ast, &field_ty, expr)?;
+                ast.check_assignable(&field_ty, expr)?;
+            }
+        },
+        Statement::LocalVariableDeclaration { ty, expr, .. } | Statement::Destructure { ty, expr, .. } => {
+            check_not_void(ast, ty, expr)?;
+            ast.check_assignable(ty, expr)?;
+        },
+        Statement::IfStmt(ifs) => {
+            for if_kind in ifs {
+                if let IfKind::If(expr, _) | IfKind::ElseIf(expr, _) = if_kind {
+                    ast.check_assignable(&collection.get_bool(), expr)?;
+                }
+            }
+        },
+        Statement::WhileStmt { expr, .. } => ast.check_assignable(&collection.get_bool(), expr)?,
+        Statement::Return(expr) => {
+            let func = ast.get_parent_of_kind::<Function, _>(*index).ok_or(ValidationError::StatementOutsideFunction)?;
+
+            ast.check_assignable(&ast.get(&func).return_type, expr)?;
+        },
+        _ => {},
+    }
+
+    Ok(())
+}
+
+fn check_expression(ast: &Ast, index: &Id<Expression>) -> Result<(), ValidationError> {
+    let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+
+    match ast.get(index) {
+        Expression::Call { on: None, method, args, names, .. } => {
+            let top_level = ast.get_top_level_method(index, method);
+
+            match &top_level {
+                Some(mi) => check_arguments(method.as_ref(), &[mi], names, args.len())?,
+                // Function values have no parameter names.
+                None if ast.lookup_binding(index.as_weak(), method).is_some() => if let Some(name) = names.iter().flatten().next() {
+                    return Err(ValidationError::UnknownArgument { method: method.as_ref().into(), name: name.as_ref().into() });
+                },
+                None => {},
+            }
+
+            let callee = ast.get_variable_type(index, method)?;
+            let Some((params, _)) = collection.get_callable_signature(&callee) else { return Ok(()) };
+
+            let bound = match &top_level {
+                Some(mi) => mi.bind_arguments(names, args.len(), false).unwrap_or_default(),
+                None => (0..args.len()).map(BoundArgument::Argument).collect(),
+            };
+
+            for (position, (param, argument)) in params.iter().zip(bound).enumerate() {
+                let BoundArgument::Argument(argument) = argument else { continue };
+                let actual = ast.get_type(&args[argument])?;
+
+                if is_checked(collection, param, &actual) && !collection.is_assignable(&actual, param) {
+                    return Err(ValidationError::ArgumentMismatch {
+                        method: method.as_ref().into(),
+                        position,
+                        expected: collection.get_source_name(param).unwrap_or_default().into(),
+                        actual: collection.get_source_name(&actual).unwrap_or_default().into(),
+                    });
+                }
+            }
+        },
+        Expression::Call { on: Some(on), method, args, names, .. } => {
+            let on_ty = ast.get_type(on)?;
+            let arg_types = args.iter().map(|arg| ast.get_type(arg)).collect::<Result<Vec<_>, _>>()?;
+
+            if arg_types.iter().any(|ty| !ty.is_strong()) {
+                return Ok(());
+            }
+
+            match collection.resolve_overload(&on_ty, method, names, &arg_types) {
+                Overload::Found(..) => return Ok(()),
+                Overload::Ambiguous(candidates) => return Err(ValidationError::AmbiguousCall {
+                    method: method.as_ref().into(),
+                    candidates: candidates.iter().map(|mi| signature(collection, mi)).collect(),
+                }),
+                Overload::NotFound => {},
+            }
+
+            let candidates = std::iter::once(on_ty.clone())
+                .chain(collection.base_chain(&on_ty))
+                .filter_map(|ty| collection.try_get_strong(&ty))
+                .flat_map(|info| info.methods.iter().filter(|mi| &mi.name == method))
+                .collect::<Vec<_>>();
+
+            check_arguments(method.as_ref(), &candidates, names, args.len())?;
+
+            // Managed overloads are picked by the runtime rules, only arp methods have a single candidate to blame.
+            let candidate = std::iter::once(on_ty.clone())
+                .chain(collection.base_chain(&on_ty))
+                .filter_map(|ty| collection.try_get_strong(&ty))
+                .find_map(|info| info.methods.iter().find(|mi| &mi.name == method && mi.args.len() == args.len() && mi.definition.is_some()));
+
+            let Some(candidate) = candidate else { return Ok(()) };
+
+            for (position, ((_, param), actual)) in candidate.args.iter().zip(&arg_types).enumerate() {
+                if is_checked(collection, param, actual) && !collection.is_assignable(actual, param) {
+                    return Err(ValidationError::ArgumentMismatch {
+                        method: method.as_ref().into(),
+                        position,
+                        expected: collection.get_source_name(param).unwrap_or_default().into(),
+                        actual: collection.get_source_name(actual).unwrap_or_default().into(),
+                    });
+                }
+            }
+        },
+        Expression::Construct { ident, args, positional } => {
+            let ty = collection.resolve_name(ident);
+
+            // Positional values are only left when there are more of them than fields.
+            if !positional.is_empty() {
+                return Err(ValidationError::TooManyFieldValues {
+                    ty: ident.as_ref().into(),
+                    fields: collection.get_all_fields(&ty).len(),
+                    values: args.len() + positional.len(),
+                });
+            }
+
+            // Managed types are created by their parameterless constructor, the fields are assigned afterwards.
+            if collection.is_managed(&ty) && !collection.is_value_type(&ty) {
+                check_constructor(collection, &ty, &[], &[])?;
+            }
+
+            for (field, arg) in args {
+                if let Some(field_ty) = collection.get_field_type(&ty, field.as_ref()) {
+                    ast.check_assignable(&field_ty, arg)?;
+                }
+            }
+        },
+        Expression::New { args, names, .. } => {
+            let ty = ast.get_type(index)?;
+            let arg_types = args.iter().map(|arg| ast.get_type(arg)).collect::<Result<Vec<_>, _>>()?;
+
+            if ty.is_strong() && arg_types.iter().all(TypeId::is_strong) {
+                check_constructor(collection, &ty, names, &arg_types)?;
+            }
+        },
+        Expression::ParamsArray { ty, elements } => {
+            if let Some(element) = collection.get_array_element(ty) {
+                for expr in elements {
+                    ast.check_assignable(&element, expr)?;
+                }
+            }
+        },
+        _ => {},
+    }
+
+    Ok(())
+}
+
+/// Calls of `void` functions have no value to store, `let x = log();` would need a `void` local.
+fn check_not_void(ast: &Ast, expected: &TypeId, expr: &Id<Expression>) -> Result<(), ValidationError> {
+    let collection = &ast.get_arp_file_in_parent(expr.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+    let void = collection.get_void();
+
+    if !ast.get_type(expr)?.is_same(&void) {
+        return Ok(());
+    }
+
+    // Inferred locals take the type of their value, there is nothing else to expect.
+    let expected = match expected.is_same(&void) {
+        true => "a value".into(),
+        false => collection.get_source_name(expected).unwrap_or_default().into(),
+    };
+
+    Err(ValidationError::TypeMismatch { expected, actual: "void".into() })
+}
+
+/// Calls left unresolved by now have arguments of types none of the candidates take, or a callee which isn't declared.
+fn check_resolved(ast: &Ast, index: &Id<Expression>) -> Result<(), ValidationError> {
+    if !ast.is_unresolved_call(index) {
+        return Ok(());
+    }
+
+    let Expression::Call { on, method, args, .. } = ast.get(index) else { return Ok(()) };
+    let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+    let name = |ty: &TypeId| collection.get_source_name(ty).unwrap_or_default().to_string();
+
+    let method = match on {
+        Some(on) => format!("{}.{}", name(&ast.get_type(on)?), method.as_ref()),
+        None if ast.lookup_binding(index.as_weak(), method).is_none() && ast.get_top_level_method(index, method).is_none() => {
+            return Err(ValidationError::UndeclaredVariable(method.as_ref().into()));
+        },
+        None => method.as_ref().to_string(),
+    };
+    let arg_types = args.iter().map(|arg| ast.get_type(arg).map(|ty| name(&ty))).collect::<Result<Vec<_>, _>>()?;
+
+    Err(ValidationError::NoApplicableMethod(method.into(), arg_types.join(", ").into()))
+}
+
+/// Checks that a single constructor of `ty` fits the arguments best.
+fn check_constructor(collection: &TypeCollection, ty: &TypeId, names: &[Option<Identifier>], arg_types: &[TypeId]) -> Result<(), ValidationError> {
+    let name = collection.get_source_name(ty).unwrap_or_default();
+
+    match collection.resolve_constructor(ty, names, arg_types) {
+        Overload::Found(..) => Ok(()),
+        Overload::Ambiguous(candidates) => Err(ValidationError::AmbiguousCall {
+            method: format!("new {name}").into(),
+            candidates: candidates.iter().map(|mi| signature(collection, mi)).collect(),
+        }),
+        Overload::NotFound => {
+            let constructors = collection.try_get_strong(ty)
+                .map(|info| info.methods.iter().filter(|mi| mi.name.as_ref() == CONSTRUCTOR).collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            check_arguments(&format!("new {name}"), &constructors, names, arg_types.len())?;
+
+            Err(ValidationError::NoConstructor(
+            name.into(),
+            arg_types.iter().map(|ty| collection.get_source_name(ty).unwrap_or_default()).collect::<Vec<_>>().join(", ").into(),
+            ))
+        },
+    }
+}
+
+/// Reports why named arguments, or arguments left out, don't fit any of the `candidates`. Only reasons
+/// every candidate agrees on are reported, mismatched types are left to the callers.
+fn check_arguments(method: &str, candidates: &[&MethodInfo], names: &[Option<Identifier>], count: usize) -> Result<(), ValidationError> {
+    // The expanded form of a `params` method takes any number of trailing arguments.
+    let errors = candidates.iter().map(|mi| mi.bind_arguments(names, count, true)).collect::<Vec<_>>();
+
+    let Some(Err(error)) = errors.first() else { return Ok(()) };
+    if errors.iter().any(|other| other.as_ref().err() != Some(error)) {
+        return Ok(());
+    }
+
+    let method = method.into();
+
+    Err(match error {
+        ArgumentError::TooMany(expected) => TypeResolverError::ArgumentCountMismatch { expected: *expected, actual: count }.into(),
+        ArgumentError::Unknown(name) => ValidationError::UnknownArgument { method, name: name.as_ref().into() },
+        ArgumentError::Duplicate(name) => ValidationError::DuplicateArgument { method, name: name.as_ref().into() },
+        ArgumentError::Missing(name) => ValidationError::MissingArgument { method, name: name.as_ref().into() },
+        ArgumentError::PositionalAfterNamed => ValidationError::PositionalAfterNamed(method),
+    })
+}
+
+/// Default values have to fit their parameters, `width: int32 = "wide"` doesn't.
+fn check_defaults(ast: &Ast, index: &Id<Function>) -> Result<(), ValidationError> {
+    let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+    let func = ast.get(index);
+
+    for ((_, param), default) in func.parameters.iter().zip(&func.defaults) {
+        let Some(default) = default else { continue };
+        let actual = collection.get_literal_type(default);
+
+        if param.is_strong() && collection.implicit_conversion(&actual, param).is_none() {
+            return Err(ValidationError::TypeMismatch {
+                expected: collection.get_source_name(param).unwrap_or_default().into(),
+                actual: collection.get_source_name(&actual).unwrap_or_default().into(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// `WriteLine(int32)`, how overloads are listed.
+fn signature(collection: &TypeCollection, method: &MethodInfo) -> Box<str> {
+    let params = method.args.iter().map(|(_, ty)| collection.get_source_name(ty).unwrap_or_default()).collect::<Vec<_>>();
+    format!("{}({})", method.name.as_ref(), params.join(", ")).into()
+}
+
+/// Types which aren't resolved, or mention type parameters bound elsewhere, aren't checked.
+fn is_checked(collection: &TypeCollection, expected: &TypeId, actual: &TypeId) -> bool {
+    expected.is_strong() && actual.is_strong() && !collection.mentions_parameter(expected) && !collection.mentions_parameter(actual)
+}
+
+impl Ast {
+    /// Checks that the value of `expr` can be stored where `expected` is expected.
+    pub fn check_assignable(&self, expected: &TypeId, expr: &Id<Expression>) -> Result<(), ValidationError> {
+        self.check_not_optional(expected, expr)?;
+
+        let collection = &self.get_arp_file_in_parent(expr.as_weak()).ok_or(TypeResolverError::ArpFileNotFound)?.type_collection;
+        let actual = self.get_type(expr)?;
+
+        if !is_checked(collection, expected, &actual) || collection.is_assignable(&actual, expected) {
+            return Ok(());
+        }
+
+        Err(ValidationError::TypeMismatch {
+            expected: collection.get_source_name(expected).unwrap_or_default().into(),
+            actual: collection.get_source_name(&actual).unwrap_or_default().into(),
+        })
+    }
+}
@@ -50,7 +50,10 @@ fn default_dotnet_self_contained() -> bool {
 #[derive(Deserialize, Debug)]
 pub struct Dev {
     #[serde(default = "default_dev_clean_build")]
-    pub clean_build: bool
+    pub clean_build: bool,
+    /// Reports lints like lossy casts while building.
+    #[serde(default)]
+    pub lints: bool,
 }
 
 fn default_dev_clean_build() -> bool {
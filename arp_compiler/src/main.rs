@@ -56,7 +56,7 @@ fn main() -> Result<(), CompilerError> {
 
             let sources = project.load_sources()?;
             
-            let output = test_write_and_run_multi_source(&sources, &temp_dir);
+            let output = test_write_and_run_multi_source(&sources, &temp_dir, project.config.dev.lints);
             print!("{}", output);
         },
         Commands::Lsp => {
@@ -67,8 +67,14 @@ fn main() -> Result<(), CompilerError> {
     Ok(())
 }
 
-fn test_write_and_run_multi_source(sources: &[Source], dir: &TempDir) -> String {
+fn test_write_and_run_multi_source(sources: &[Source], dir: &TempDir, lints: bool) -> String {
     let ast = build_multiple_sources(sources).unwrap();
+
+    if lints {
+        for lint in arp_ast_processor::validations::lints::lint(&ast) {
+            eprintln!("warning: {lint}");
+        }
+    }
     let node = ast.get_root_index();
 
     let tokens = Emitter::new().emit_node(&ast, node).unwrap();
@@ -1,22 +1,34 @@
+use std::collections::HashSet;
+
 use arp_ast_processor::types::{
-    ast_node_value::{Ast, AstNodeValue, WId},
+    ast_node_value::{Ast, AstNodeValue, Id, WId},
     expression::Expression,
     file::ArpFile,
     function::Function,
+    simple::Identifier,
     statement::Statement,
-    type_collection::TypeId,
+    type_collection::{TypeCollection, TypeId, TypeInfo, TypeKind, TypeSourceKind},
 };
 
 use crate::{
     il_token::{ILToken, OpCode, ResolvedType},
+    utils::{assembly_name, il_name, type_keyword},
     traits::Visitor,
     EmitError,
 };
 
+/// Assembly of the core library types like `System.String` and `System.ValueType`.
+pub(crate) const RUNTIME_ASSEMBLY: &str = "System.Runtime";
+
 #[derive(Debug)]
 pub struct Emitter {
     tokens: Vec<ILToken>,
     label_index: usize,
+    emitted_prelude: HashSet<String>,
+    /// Labels after the loops being emitted, innermost last. `break` jumps to the last one.
+    pub(crate) loop_ends: Vec<String>,
+    /// Arguments already evaluated into their hidden locals, passing them loads the local.
+    pub(crate) spilled: Vec<Id<Expression>>,
 }
 
 impl Emitter {
@@ -24,9 +36,17 @@ impl Emitter {
         Self {
             tokens: vec![],
             label_index: 0,
+            emitted_prelude: HashSet::new(),
+            loop_ends: vec![],
+            spilled: vec![],
         }
     }
 
+    /// Prelude types are instantiated per file, but have to be emitted only once per assembly.
+    pub(crate) fn mark_prelude_emitted(&mut self, full_name: &str) -> bool {
+        self.emitted_prelude.insert(full_name.to_string())
+    }
+
     pub fn next_label(&mut self) -> String {
         let result = format!("ARP_{}", self.label_index);
         self.label_index += 1;
@@ -93,10 +113,225 @@ impl Emitter {
         ty: &TypeId,
         index: I,
     ) -> Result<ResolvedType, EmitError> {
-        Ok(ast
-            .resolve_type(ty.clone(), &index.into())
+        let collection = &ast
+            .get_arp_file_in_parent(index.into())
             .ok_or(EmitError::CantResolveType)?
-            .into())
+            .type_collection;
+
+        Emitter::resolve_info(collection, collection.try_get_strong(ty).ok_or(EmitError::CantResolveType)?)
+    }
+
+    /// Generic instances are spelled out with their arguments, ``class Box`1<int32>``.
+    /// Instances of managed types carry their assembly, ``class [System.Collections]System.Collections.Generic.List`1<int32>``.
+    /// Arrays are their element type followed by `[]`, `int32[]`.
+    /// Tuples are value tuples of their elements, ``valuetype [System.Runtime]System.ValueTuple`2<int32,string>``.
+    /// Function types are delegates, ``class [System.Runtime]System.Func`2<int32,bool>`` or ``System.Action`1<int32>`` for `void`.
+    pub(crate) fn resolve_info(collection: &TypeCollection, info: &TypeInfo) -> Result<ResolvedType, EmitError> {
+        match &info.kind {
+            TypeKind::Array(element) => {
+                let element = collection.try_get_strong(element).ok_or(EmitError::CantResolveType)?;
+                Ok(ResolvedType(format!("{}[]", Emitter::resolve_info(collection, element)?.0)))
+            }
+            TypeKind::Tuple(elements) => {
+                let elements = elements
+                    .iter()
+                    .map(|element| {
+                        collection
+                            .try_get_strong(element)
+                            .ok_or(EmitError::CantResolveType)
+                            .and_then(|element| Emitter::resolve_info(collection, element))
+                            .map(|element| element.0)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(ResolvedType(format!("valuetype [{RUNTIME_ASSEMBLY}]System.ValueTuple`{}<{}>", elements.len(), elements.join(","))))
+            }
+            TypeKind::Function { params, return_type } => {
+                let is_void = return_type.is_same(&collection.get_void());
+                let args = params
+                    .iter()
+                    .chain((!is_void).then_some(return_type))
+                    .map(|arg| {
+                        collection
+                            .try_get_strong(arg)
+                            .ok_or(EmitError::CantResolveType)
+                            .and_then(|arg| Emitter::resolve_info(collection, arg))
+                            .map(|arg| arg.0)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(ResolvedType(match (is_void, args.is_empty()) {
+                    (true, true) => format!("class [{RUNTIME_ASSEMBLY}]System.Action"),
+                    (true, false) => format!("class [{RUNTIME_ASSEMBLY}]System.Action`{}<{}>", args.len(), args.join(",")),
+                    (false, _) => format!("class [{RUNTIME_ASSEMBLY}]System.Func`{}<{}>", args.len(), args.join(",")),
+                }))
+            }
+            TypeKind::Instance { definition, args } => {
+                let definition = collection.try_get_strong(definition).ok_or(EmitError::CantResolveType)?;
+                let args = args
+                    .iter()
+                    .map(|arg| {
+                        collection
+                            .try_get_strong(arg)
+                            .ok_or(EmitError::CantResolveType)
+                            .and_then(|arg| Emitter::resolve_info(collection, arg))
+                            .map(|arg| arg.0)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let assembly = match &definition.source {
+                    TypeSourceKind::ManagedDll(dll) => format!("[{}]", assembly_name(dll)),
+                    _ => String::new(),
+                };
+
+                Ok(ResolvedType(format!("{} {assembly}{}<{}>", type_keyword(definition), il_name(definition), args.join(","))))
+            }
+            _ => Ok(info.into()),
+        }
+    }
+
+    /// Owner of a member reference. Generic classes need the `class` form with their arguments.
+    pub(crate) fn type_spec(collection: &TypeCollection, info: &TypeInfo) -> Result<String, EmitError> {
+        match &info.kind {
+            TypeKind::Generic(_) | TypeKind::Instance { .. } | TypeKind::Tuple(_) | TypeKind::Function { .. } => Ok(Emitter::resolve_info(collection, info)?.0),
+            _ => Ok(info.full_name.to_string()),
+        }
+    }
+
+    /// Owner of a member reference with the assembly of managed types, `[System.Runtime]System.Math`.
+    pub(crate) fn member_owner(collection: &TypeCollection, info: &TypeInfo) -> Result<String, EmitError> {
+        let spec = Emitter::type_spec(collection, info)?;
+
+        Ok(match Emitter::external_assembly(info) {
+            Some(assembly) => format!("[{assembly}]{spec}"),
+            None => spec,
+        })
+    }
+
+    /// Type parameters of a class or method declaration, `(class Bound) T, U`.
+    pub(crate) fn generic_params_decl(collection: &TypeCollection, params: &[TypeId]) -> Result<String, EmitError> {
+        params
+            .iter()
+            .map(|param| {
+                let info = collection.try_get_strong(param).ok_or(EmitError::CantResolveType)?;
+                let name = info.full_name.rsplit('.').next().unwrap_or_default();
+
+                let bounds = match &info.kind {
+                    TypeKind::Parameter { bounds, .. } => bounds
+                        .iter()
+                        .map(|bound| {
+                            collection
+                                .try_get_strong(bound)
+                                .ok_or(EmitError::CantResolveType)
+                                .and_then(|bound| Emitter::resolve_info(collection, bound))
+                                .map(|bound| bound.0)
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                    _ => vec![],
+                };
+
+                Ok(match bounds.is_empty() {
+                    true => name.to_string(),
+                    false => format!("({}) {name}", bounds.join(", ")),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|params| params.join(", "))
+    }
+
+    /// Declaring type, type and IL name of `field`. Inherited fields belong to the base class, fields of generic
+    /// instances are referenced with the definition's open type, `!0`. Tuple field `0` is `Item1` of type `!0`.
+    pub(crate) fn field_ref(collection: &TypeCollection, ty: &TypeId, field: &Identifier) -> Result<(String, ResolvedType, String), EmitError> {
+        let (owner, fld_ty) = collection.find_field(ty, field.as_ref()).ok_or(EmitError::CantResolveField)?;
+        let owner = collection.try_get_strong(&owner).ok_or(EmitError::CantResolveType)?;
+
+        let fld_ty = match &owner.kind {
+            TypeKind::Tuple(_) => {
+                let position = field.as_ref().parse::<usize>().map_err(|_| EmitError::CantResolveField)?;
+                return Ok((Emitter::type_spec(collection, owner)?, ResolvedType(format!("!{position}")), format!("Item{}", position + 1)));
+            }
+            TypeKind::Instance { definition, .. } => collection.get_field_type(definition, field.as_ref()).ok_or(EmitError::CantResolveField)?,
+            _ => fld_ty,
+        };
+
+        let fld_ty = Emitter::resolve_info(collection, collection.try_get_strong(&fld_ty).ok_or(EmitError::CantResolveType)?)?;
+
+        Ok((Emitter::member_owner(collection, owner)?, fld_ty, field.0.to_string()))
+    }
+
+    /// Declaring type, type and IL name of the static field `field` of a managed type, like [`Emitter::field_ref`].
+    pub(crate) fn static_field_ref(collection: &TypeCollection, ty: &TypeId, field: &Identifier) -> Result<(String, ResolvedType, String), EmitError> {
+        let owner = collection.try_get_strong(ty).ok_or(EmitError::CantResolveType)?;
+
+        let fld = match &owner.kind {
+            TypeKind::Instance { definition, .. } => collection.get_static_field(definition, field.as_ref()),
+            _ => collection.get_static_field(ty, field.as_ref()),
+        };
+        let fld_ty = collection.try_get_strong(&fld.ok_or(EmitError::CantResolveField)?.ty).ok_or(EmitError::CantResolveType)?;
+
+        Ok((Emitter::member_owner(collection, owner)?, Emitter::resolve_info(collection, fld_ty)?, field.0.to_string()))
+    }
+
+    /// Constructor parameters of `ty`, with open types for generic instances.
+    pub(crate) fn ctor_params(collection: &TypeCollection, ty: &TypeId) -> Result<Vec<(Identifier, TypeId)>, EmitError> {
+        match &collection.try_get_strong(ty).ok_or(EmitError::CantResolveType)?.kind {
+            TypeKind::Instance { definition, .. } => Ok(collection.get_all_fields(definition)),
+            _ => Ok(collection.get_all_fields(ty)),
+        }
+    }
+
+    /// Assembly of a managed type, generic instances carry it in their type spec already.
+    /// Standard types like `System.String` live in the runtime assembly.
+    pub(crate) fn external_assembly(info: &TypeInfo) -> Option<String> {
+        match (&info.kind, &info.source) {
+            (TypeKind::Instance { .. } | TypeKind::Array(_) | TypeKind::Tuple(_) | TypeKind::Function { .. }, _) => None,
+            (_, TypeSourceKind::ManagedDll(dll)) => Some(assembly_name(dll).to_string()),
+            (_, TypeSourceKind::Standard) => Some(RUNTIME_ASSEMBLY.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Return type, owner and parameters of `Invoke` of the function type `ty`, in their open form `!0`.
+    pub(crate) fn invoke_ref(collection: &TypeCollection, ty: &TypeId) -> Result<(ResolvedType, String, Vec<ResolvedType>), EmitError> {
+        let (params, return_type) = collection.get_function_signature(ty).ok_or(EmitError::CantResolveType)?;
+        let info = collection.try_get_strong(ty).ok_or(EmitError::CantResolveType)?;
+
+        let return_type = match return_type.is_same(&collection.get_void()) {
+            true => ResolvedType("void".to_string()),
+            false => ResolvedType(format!("!{}", params.len())),
+        };
+        let args = (0..params.len()).map(|position| ResolvedType(format!("!{position}"))).collect();
+
+        Ok((return_type, Emitter::type_spec(collection, info)?, args))
+    }
+
+    /// Static class holding the extension methods of a file for `extended`, `StringExtensions` for `impl string`.
+    pub(crate) fn extension_class(collection: &TypeCollection, extended: &TypeId) -> Result<String, EmitError> {
+        let info = collection.try_get_strong(extended).ok_or(EmitError::CantResolveType)?;
+        Ok(format!("{}Extensions", info.full_name.rsplit('.').next().unwrap_or_default()))
+    }
+
+    /// First parameter of an extension method. Structs are passed by reference, so `this` stays an address.
+    pub(crate) fn extension_receiver(collection: &TypeCollection, extended: &TypeId) -> Result<ResolvedType, EmitError> {
+        let info = collection.try_get_strong(extended).ok_or(EmitError::CantResolveType)?;
+
+        let receiver = match (&info.source, Emitter::external_assembly(info)) {
+            (TypeSourceKind::ManagedDll(_), Some(assembly)) => format!("{} [{assembly}]{}", type_keyword(info), info.full_name),
+            _ => Emitter::resolve_info(collection, info)?.0,
+        };
+
+        Ok(ResolvedType(match info.is_value_type {
+            true => format!("{receiver}&"),
+            false => receiver,
+        }))
+    }
+
+    /// Base class as written in the `extends` clause of a class header.
+    pub(crate) fn extends_spec(collection: &TypeCollection, info: &TypeInfo) -> Result<String, EmitError> {
+        match Emitter::external_assembly(info) {
+            Some(assembly) => Ok(format!("[{assembly}]{}", info.full_name)),
+            None => Emitter::type_spec(collection, info),
+        }
     }
 }
 
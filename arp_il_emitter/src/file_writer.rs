@@ -4,13 +4,14 @@ use std::{
     path::Path,
 };
 
-use crate::il_token::{FunctionFlags, ILToken, OpCode, StructureFlags};
+use crate::il_token::{FunctionFlags, ILToken, OpCode, ResolvedType, StructureFlags};
+
+const EXTENSION_ATTRIBUTE: &str = ".custom instance void [System.Runtime]System.Runtime.CompilerServices.ExtensionAttribute::.ctor() = ( 01 00 00 00 )";
 
 pub fn write_tokens_to_file<P: AsRef<Path>>(path: &P, tokens: &[ILToken]) -> Result<(), io::Error> {
     let path = path.as_ref();
     if path.is_dir() {
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
+        return Err(io::Error::other(
             "The path is a directory, cannot write tokens.",
         ));
     }
@@ -21,8 +22,12 @@ pub fn write_tokens_to_file<P: AsRef<Path>>(path: &P, tokens: &[ILToken]) -> Res
     let file = File::create(path)?;
     let mut writer = BufWriter::new(file);
 
-    // Prelude
-    writeln!(writer, ".assembly 'Main' {{}}")?;
+    // Prelude. C# only looks for extension methods in assemblies marked as having some.
+    let has_extensions = tokens.iter().any(|token| matches!(token, ILToken::StartStructure(flags, ..) if flags.contains(&StructureFlags::Extension)));
+    match has_extensions {
+        true => writeln!(writer, ".assembly 'Main' {{ {EXTENSION_ATTRIBUTE} }}")?,
+        false => writeln!(writer, ".assembly 'Main' {{}}")?,
+    }
 
     write_tokens(&mut writer, tokens)?;
 
@@ -32,7 +37,7 @@ pub fn write_tokens_to_file<P: AsRef<Path>>(path: &P, tokens: &[ILToken]) -> Res
 }
 
 fn write_tokens(writer: &mut BufWriter<File>, tokens: &[ILToken]) -> Result<(), io::Error> {
-    for token in tokens {
+    for (position, token) in tokens.iter().enumerate() {
         match token {
             ILToken::OpCode(opcode) => {
                 write!(writer, "\t\t")?;
@@ -42,36 +47,82 @@ fn write_tokens(writer: &mut BufWriter<File>, tokens: &[ILToken]) -> Result<(),
                 write_method(writer, method)?;
             }
             ILToken::EndMethod(mtd_name) => {
-                writeln!(writer, "\t\tret")?;
+                // A `ret` after the last `return` would run with nothing to return on the stack.
+                if !position.checked_sub(1).is_some_and(|previous| ends_flow(&tokens[previous])) {
+                    writeln!(writer, "\t\tret")?;
+                }
+
                 writeln!(writer, "\t}} // {mtd_name}\n")?
             }
             ILToken::Empty => writeln!(writer)?,
 
-            ILToken::StartStructure(flags, name) => {
+            ILToken::StartStructure(flags, name, extends, implements) => {
                 write!(writer, "\n.class ")?;
+                if flags.contains(&StructureFlags::Interface) {
+                    write!(writer, "interface ")?;
+                }
+                if flags.contains(&StructureFlags::Abstract) {
+                    write!(writer, "abstract ")?;
+                }
                 if flags.contains(&StructureFlags::Auto) {
                     write!(writer, "auto ")?;
                 }
+                if flags.contains(&StructureFlags::Sealed) {
+                    write!(writer, "sealed ")?;
+                }
+                if flags.contains(&StructureFlags::Sequential) {
+                    write!(writer, "sequential ")?;
+                }
+
+                write!(writer, "{name}")?;
+
+                if let Some(extends) = extends {
+                    write!(writer, " extends {extends}")?;
+                }
+
+                if !implements.is_empty() {
+                    write!(writer, " implements {}", implements.join(", "))?;
+                }
 
-                writeln!(writer, "{name}")?;
+                writeln!(writer)?;
 
                 writeln!(writer, "{{")?;
 
+                if flags.contains(&StructureFlags::Extension) {
+                    writeln!(writer, "\t{EXTENSION_ATTRIBUTE}")?;
+                }
             }
             ILToken::EndStructure(name) => writeln!(writer, "}} // {name}\n")?,
             ILToken::Field(name, ty) => writeln!(writer, "\t.field public {} {name}", ty.0)?,
+            ILToken::EnumValue(ty) => writeln!(writer, "\t.field public specialname rtspecialname {} value__", ty.0)?,
+            ILToken::EnumMember(name, ty, underlying, value) => {
+                writeln!(writer, "\t.field public static literal {} {name} = {}({value})", ty.0, underlying.0)?
+            }
         }
     }
 
     Ok(())
 }
 
+/// Whether control never falls through `token` into the next instruction.
+fn ends_flow(token: &ILToken) -> bool {
+    match token {
+        ILToken::OpCode(OpCode::LabeledOpCode(_, opcode)) => ends_flow(&ILToken::OpCode(*opcode.clone())),
+        ILToken::OpCode(opcode) => matches!(opcode, OpCode::Return | OpCode::Throw | OpCode::BranchTo(_)),
+        _ => false,
+    }
+}
+
 fn write_op_code(writer: &mut BufWriter<File>, opcode: &OpCode) -> Result<(), io::Error> {
     
 
     match opcode {
-        OpCode::LoadInt(i) => writeln!(writer, "ldc.i4.s {}", i)?,
+        OpCode::LoadInt(i) => match i8::try_from(*i) {
+            Ok(_) => writeln!(writer, "ldc.i4.s {}", i)?,
+            Err(_) => writeln!(writer, "ldc.i4 {}", i)?,
+        },
         OpCode::LoadFloat(f) => writeln!(writer, "ldc.r4 {}", f)?,
+        OpCode::LoadDouble(f) => writeln!(writer, "ldc.r8 {}", f)?,
         OpCode::LoadString(s) => writeln!(writer, "ldstr \"{}\"", s)?,
         OpCode::LoadBool(b) => writeln!(writer, "ldc.i4.{}", {
             if *b {
@@ -93,31 +144,37 @@ fn write_op_code(writer: &mut BufWriter<File>, opcode: &OpCode) -> Result<(), io
         OpCode::And => writeln!(writer, "and")?,
         OpCode::Call {
             is_instance,
+            is_virtual,
             return_type,
             external,
             ty,
             method_name,
             args,
         } => {
-            write!(writer, "call ")?;
-            if *is_instance {
-                write!(writer, "instance ")?
+            if *is_virtual {
+                write!(writer, "callvirt ")?;
+            } else {
+                write!(writer, "call ")?;
             }
-            write!(writer, "{} ", return_type.0)?;
-            if let Some(external) = external {
-                write!(writer, "[{}]", external)?
+            write_method_ref(writer, *is_instance, return_type, external, ty, method_name, args)?;
+        }
+        OpCode::LoadFunction {
+            is_instance,
+            is_virtual,
+            return_type,
+            external,
+            ty,
+            method_name,
+            args,
+        } => {
+            if *is_virtual {
+                write!(writer, "ldvirtftn ")?;
+            } else {
+                write!(writer, "ldftn ")?;
             }
-            write!(writer, "{}::{}", ty, method_name)?;
-            
-            writeln!(
-                writer,
-                "({})",
-                args.iter()
-                .map(|arg| arg.0.clone())
-                .collect::<Vec<_>>()
-                .join(",")
-            )?;
+            write_method_ref(writer, *is_instance, return_type, external, ty, method_name, args)?;
         }
+        OpCode::LoadNull => writeln!(writer, "ldnull")?,
         OpCode::NoOperation => writeln!(writer, "nop")?,
         OpCode::NewObject(ty, args) => writeln!(
             writer,
@@ -134,8 +191,34 @@ fn write_op_code(writer: &mut BufWriter<File>, opcode: &OpCode) -> Result<(), io
         OpCode::GetField(fld_ty, class_name, fld_name) => { 
             writeln!(writer, "ldfld {} {}::{}", fld_ty.0, class_name, fld_name)?;
         }
+        OpCode::SetStaticField(fld_ty, class_name, fld_name) => {
+            writeln!(writer, "stsfld {} {}::{}", fld_ty.0, class_name, fld_name)?;
+        }
+        OpCode::GetStaticField(fld_ty, class_name, fld_name) => {
+            writeln!(writer, "ldsfld {} {}::{}", fld_ty.0, class_name, fld_name)?;
+        }
+        OpCode::LoadFieldAddress(fld_ty, class_name, fld_name) => {
+            writeln!(writer, "ldflda {} {}::{}", fld_ty.0, class_name, fld_name)?;
+        }
+        OpCode::LoadObject(ty) => writeln!(writer, "ldobj {}", ty.0)?,
+        OpCode::NewArray(ty) => writeln!(writer, "newarr {}", ty.0)?,
+        OpCode::LoadElement(ty) => writeln!(writer, "ldelem {}", ty.0)?,
+        OpCode::StoreElement(ty) => writeln!(writer, "stelem {}", ty.0)?,
+        OpCode::LoadElementAddress(ty) => writeln!(writer, "ldelema {}", ty.0)?,
+        OpCode::LoadLength => writeln!(writer, "ldlen")?,
+        OpCode::ConvertToInt32 => writeln!(writer, "conv.i4")?,
+        OpCode::ConvertToFloat32 => writeln!(writer, "conv.r4")?,
+        OpCode::ConvertToFloat64 => writeln!(writer, "conv.r8")?,
+        OpCode::Duplicate => writeln!(writer, "dup")?,
+        OpCode::Pop => writeln!(writer, "pop")?,
+        OpCode::Box(ty) => writeln!(writer, "box {}", ty.0)?,
+        OpCode::UnboxAny(ty) => writeln!(writer, "unbox.any {}", ty.0)?,
+        OpCode::IsInstance(ty) => writeln!(writer, "isinst {}", ty)?,
+        OpCode::CastClass(ty) => writeln!(writer, "castclass {}", ty)?,
         OpCode::LoadArgument(index) => writeln!(writer, "ldarg.s {}", index)?,
         OpCode::StoreArgument(index) => writeln!(writer, "starg.s {}", index)?,
+        OpCode::LoadLocalAddress(index) => writeln!(writer, "ldloca.s {}", index)?,
+        OpCode::LoadArgumentAddress(index) => writeln!(writer, "ldarga.s {}", index)?,
 
         // IL_0009: brfalse.s IL_0016
         OpCode::BranchIfFalse(label) => writeln!(writer, "brfalse.s {}", label)?,
@@ -146,16 +229,60 @@ fn write_op_code(writer: &mut BufWriter<File>, opcode: &OpCode) -> Result<(), io
             write_op_code(writer, opcode)?;
         },
         OpCode::Return => writeln!(writer, "ret")?,
+        OpCode::Throw => writeln!(writer, "throw")?,
     };
 
     Ok(())
 }
 
+/// Method reference after `call` or `ldftn`. Global functions have no owning type.
+fn write_method_ref(
+    writer: &mut BufWriter<File>,
+    is_instance: bool,
+    return_type: &ResolvedType,
+    external: &Option<String>,
+    ty: &str,
+    method_name: &str,
+    args: &[ResolvedType],
+) -> Result<(), io::Error> {
+    if is_instance {
+        write!(writer, "instance ")?
+    }
+    write!(writer, "{} ", return_type.0)?;
+    if let Some(external) = external {
+        write!(writer, "[{}]", external)?
+    }
+    if !ty.is_empty() {
+        write!(writer, "{}::", ty)?;
+    }
+    write!(writer, "{}", method_name)?;
+
+    writeln!(
+        writer,
+        "({})",
+        args.iter()
+        .map(|arg| arg.0.clone())
+        .collect::<Vec<_>>()
+        .join(",")
+    )
+}
+
 fn write_method(
     writer: &mut BufWriter<File>,
     method: &crate::il_token::Method,
 ) -> Result<(), io::Error> {
     write!(writer, "\n\t.method ")?;
+    for (flag, keyword) in [
+        (FunctionFlags::Public, "public"),
+        (FunctionFlags::NewSlot, "hidebysig newslot"),
+        (FunctionFlags::Abstract, "abstract"),
+        (FunctionFlags::Virtual, "virtual"),
+        (FunctionFlags::Final, "final"),
+    ] {
+        if method.flags.contains(&flag) {
+            write!(writer, "{keyword} ")?;
+        }
+    }
     if method.flags.contains(&FunctionFlags::IsStatic(true)) {
         write!(writer, "static ")?;
     } else if method.flags.contains(&FunctionFlags::IsStatic(false)) {
@@ -182,6 +309,19 @@ fn write_method(
     }
     writeln!(writer)?;
     writeln!(writer, "\t{{")?;
+
+    // Abstract methods have no body, so there is no `EndMethod` for them either.
+    if method.flags.contains(&FunctionFlags::Abstract) {
+        writeln!(writer, "\t}} // {}\n", method.name)?;
+        return Ok(());
+    }
+
+    if method.flags.contains(&FunctionFlags::Extension) {
+        writeln!(writer, "\t\t{EXTENSION_ATTRIBUTE}")?;
+    }
+    if let Some(overrides) = &method.overrides {
+        writeln!(writer, "\t\t.override {overrides}")?;
+    }
     if method.flags.contains(&FunctionFlags::EntryPoint) {
         writeln!(writer, "\t\t.entrypoint")?;
     }
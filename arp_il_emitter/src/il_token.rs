@@ -7,10 +7,15 @@ pub enum ILToken {
     StartMethod(Method),
     EndMethod(String),
 
-    StartStructure(HashSet<StructureFlags>, String),
+    /// Flags, name, base class and implemented interfaces.
+    StartStructure(HashSet<StructureFlags>, String, Option<String>, Vec<String>),
     EndStructure(String),
 
     Field(String, ResolvedType),
+    /// `value__` of an enum, holding the underlying value.
+    EnumValue(ResolvedType),
+    /// Name, enum type, underlying type and value of an enum member.
+    EnumMember(String, ResolvedType, ResolvedType, i64),
 
     #[default]
     Empty,
@@ -22,14 +27,20 @@ pub enum OpCode {
 
     LoadInt(i64),
     LoadFloat(f64),
+    /// `ldc.r8`, constants of managed `float64` fields.
+    LoadDouble(f64),
     LoadString(String),
     LoadBool(bool),
     LoadLocalVariable(usize),
     StoreLocalVariable(usize),
     LoadArgument(usize),
     StoreArgument(usize),
+    LoadLocalAddress(usize),
+    LoadArgumentAddress(usize),
     Call { 
         is_instance: bool,
+        /// `callvirt`, used for instance methods of reference types.
+        is_virtual: bool,
         return_type: ResolvedType,
         external: Option<String>,
         ty: String,
@@ -37,9 +48,46 @@ pub enum OpCode {
         args: Vec<ResolvedType>
     },
     NewObject(ResolvedType, Vec<ResolvedType>),
+    /// `ldftn`, or `ldvirtftn` when virtual, pushes the method pointer a delegate is constructed from.
+    LoadFunction {
+        is_instance: bool,
+        is_virtual: bool,
+        return_type: ResolvedType,
+        external: Option<String>,
+        /// Empty for global functions.
+        ty: String,
+        method_name: String,
+        args: Vec<ResolvedType>
+    },
+    LoadNull,
 
     SetField(ResolvedType, String, String),
     GetField(ResolvedType, String, String),
+    /// `stsfld` and `ldsfld` of a static field of a managed type.
+    SetStaticField(ResolvedType, String, String),
+    GetStaticField(ResolvedType, String, String),
+    LoadFieldAddress(ResolvedType, String, String),
+    /// Copies the struct behind an address onto the stack.
+    LoadObject(ResolvedType),
+
+    /// Element type of the new array, its length is on the stack.
+    NewArray(ResolvedType),
+    LoadElement(ResolvedType),
+    StoreElement(ResolvedType),
+    LoadElementAddress(ResolvedType),
+    /// Length of an array as a native int, `ConvertToInt32` narrows it.
+    LoadLength,
+    ConvertToInt32,
+    ConvertToFloat32,
+    ConvertToFloat64,
+    Duplicate,
+    Pop,
+
+    Box(ResolvedType),
+    /// Unboxes the value type, or casts for reference types.
+    UnboxAny(ResolvedType),
+    IsInstance(String),
+    CastClass(String),
 
     BranchIfFalse(String),
     BranchIfTrue(String),
@@ -59,6 +107,8 @@ pub enum OpCode {
     Or,
     And,
     Return,
+    /// Throws the exception on the stack.
+    Throw,
     
     
     
@@ -77,6 +127,8 @@ pub struct Method {
     pub registers: Vec<ResolvedType>,
     pub return_ty: ResolvedType,
     pub name: String,
+    /// Interface method implemented by this one, `Printable::print`.
+    pub overrides: Option<String>,
 }
 
 impl From<Method> for ILToken {
@@ -94,6 +146,13 @@ pub enum FunctionFlags {
     Managed,
     IsStatic(bool),
     EntryPoint,
+    Public,
+    NewSlot,
+    Abstract,
+    Virtual,
+    Final,
+    /// Marked with `ExtensionAttribute`, so C# sees it as an extension method.
+    Extension,
 
     #[default]
     Nothing,
@@ -101,7 +160,13 @@ pub enum FunctionFlags {
 
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
 pub enum StructureFlags {
+    Interface,
     Auto,
+    Abstract,
+    Sealed,
+    Sequential,
+    /// Static class holding extension methods, marked with `ExtensionAttribute`.
+    Extension,
 
 
     #[default]
@@ -32,6 +32,9 @@ pub enum EmitError {
     
     #[error("")]
     UnsupportedIfChain,
+
+    #[error("")]
+    BreakOutsideLoop,
 }
 
 pub fn emit_tokens(ast: Ast) -> Result<Vec<ILToken>, EmitError> {
@@ -2,8 +2,11 @@ use arp_ast_processor::types::{
     expression::Expression, file::ArpFile, function::Function, statement::Statement,
 };
 
+use tempfile::TempDir;
+
 use crate::{
-    il_token::{ILToken, OpCode},
+    file_writer::write_tokens_to_file,
+    il_token::{FunctionFlags, ILToken, OpCode, StructureFlags},
     tests::test_emit,
 };
 
@@ -48,3 +51,756 @@ fn arp_file() {
     assert!(matches!(tokens[2], ILToken::OpCode(OpCode::StoreLocalVariable(0))));
     assert!(matches!(tokens[3], ILToken::EndMethod(_)));
 }
+
+#[test]
+fn union_structure() {
+    let tokens = test_emit::<ArpFile, _>("union Shape { Circle { r: int32 }, Empty }").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartStructure(flags, name, None, _) 
+        if name == "Shape" && flags.contains(&StructureFlags::Abstract))));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartStructure(flags, name, Some(extends), _) 
+        if name == "Shape.Circle" && extends == "Shape" && flags.contains(&StructureFlags::Sealed))));
+}
+
+#[test]
+fn match_expression() {
+    let tokens = test_emit::<Function, _>("
+union Shape { Circle { r: int32 }, Empty }
+
+fn main() {
+    let shape = Shape.Empty { };
+    let x = match shape { Shape.Circle { r } => r, Shape.Empty => 0 };
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::IsInstance(ty)) if ty == "Shape.Circle")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::CastClass(ty)) if ty == "Shape.Circle")));
+    assert!(!tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::IsInstance(ty)) if ty == "Shape.Empty")));
+}
+
+#[test]
+fn try_expression() {
+    let tokens = test_emit::<Function, _>("
+class Parser { }
+
+impl Parser {
+    fn twice(x: Option<int32>) -> Option<int32> {
+        let value = x?;
+        return Option<int32>.Some { value: value * 2 };
+    }
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::IsInstance(ty)) if ty == "Prelude.Option_int32.Some")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::NewObject(ty, args)) if ty.0 == "class Prelude.Option_int32.None" && args.is_empty())));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::Return))));
+}
+
+#[test]
+fn prelude_types_emitted_once() {
+    let tokens = test_emit::<ArpFile, _>("
+fn main() {
+    let a = Option<int32>.None { };
+    let b: Option<int32> = Option<int32>.Some { value: 1 };
+    let c = Result<int32, string>.Err { error: \"oops\" };
+}").unwrap();
+
+    let option_count = tokens.iter().filter(|token| matches!(token, ILToken::StartStructure(_, name, None, _) if name == "Prelude.Option_int32")).count();
+    assert_eq!(option_count, 1);
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartStructure(_, name, Some(extends), _) 
+        if name == "Prelude.Result_int32_string.Err" && extends == "Prelude.Result_int32_string")));
+}
+
+#[test]
+fn generic_structure() {
+    let tokens = test_emit::<ArpFile, _>("
+class Box<T> {
+    value: T
+}
+
+impl Box<T> {
+    fn get(this) -> T {
+        return this.value;
+    }
+}
+
+fn main() {
+    let boxed = Box<int32> { value: 1 };
+    let value = boxed.get();
+    let field = boxed.value;
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartStructure(_, name, None, _) if name == "Box`1<T>")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::Field(name, ty) if name == "value" && ty.0 == "!0")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::NewObject(ty, args)) 
+        if ty.0 == "class Box`1<int32>" && args.len() == 1 && args[0].0 == "!0")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::Call { ty, method_name, return_type, .. }) 
+        if ty == "class Box`1<int32>" && method_name == "get" && return_type.0 == "!0")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::GetField(fld_ty, owner, name)) 
+        if fld_ty.0 == "!0" && owner == "class Box`1<int32>" && name == "value")));
+}
+
+#[test]
+fn generic_method() {
+    let tokens = test_emit::<ArpFile, _>("
+class Boxes { }
+
+impl Boxes {
+    fn identity<U>(x: U) -> U {
+        return x;
+    }
+}
+
+fn main() {
+    let number = Boxes.identity(1);
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartMethod(method) 
+        if method.name == "identity<U>" && method.return_ty.0 == "!!0")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::Call { method_name, return_type, args, .. }) 
+        if method_name == "identity<int32>" && return_type.0 == "!!0" && args[0].0 == "!!0")));
+}
+
+#[test]
+fn generic_function() {
+    let tokens = test_emit::<ArpFile, _>("
+fn id<T>(x: T) -> T {
+    return x;
+}
+
+fn main() {
+    let number = id(5);
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartMethod(method) 
+        if method.name == "id<T>" && method.return_ty.0 == "!!0")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::Call { method_name, return_type, args, is_instance: false, .. }) 
+        if method_name == "id<int32>" && return_type.0 == "!!0" && args[0].0 == "!!0")));
+}
+
+#[test]
+fn inherited_class() {
+    let tokens = test_emit::<ArpFile, _>("
+class Shape {
+    sides: int32
+}
+
+impl Shape {
+    fn count(this) -> int32 {
+        return this.sides;
+    }
+}
+
+class Square : Shape {
+    size: int32
+}
+
+impl Square {
+    fn count(this) -> int32 {
+        return base.count() + this.sides;
+    }
+}
+
+fn main() {
+    let square = Square { sides: 4, size: 2 };
+    let sides = square.count();
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartStructure(_, name, Some(extends), _) 
+        if name == "Square" && extends == "Shape")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::Call { ty, method_name, is_virtual: false, .. }) 
+        if ty == "Shape" && method_name == "count")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::Call { ty, method_name, is_virtual: true, .. }) 
+        if ty == "Square" && method_name == "count")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartMethod(method) 
+        if method.name == ".ctor" && method.params.len() == 2 && method.params[0].0 == "sides")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::Call { ty, method_name, args, .. }) 
+        if ty == "Shape" && method_name == ".ctor" && args.len() == 1)));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::Call { ty, method_name, .. }) 
+        if ty == "Shape" && method_name == "count")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::GetField(_, owner, name)) 
+        if owner == "Shape" && name == "sides")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::NewObject(ty, args)) 
+        if ty.0 == "class Square" && args.len() == 2)));
+}
+
+#[test]
+fn overridden_method() {
+    let tokens = test_emit::<ArpFile, _>("
+class Animal {
+    legs: int32
+}
+
+impl Animal {
+    fn speak(this) -> int32 {
+        return 0;
+    }
+
+    fn walk(this, steps: int32) -> int32 {
+        return steps;
+    }
+}
+
+class Dog : Animal {
+    tail: bool
+}
+
+impl Dog {
+    fn speak(this) -> int32 {
+        return 1;
+    }
+
+    fn walk(this, steps: int32, fast: bool) -> int32 {
+        return steps * 2;
+    }
+}
+
+fn main() {
+    let animal: Animal = Dog { legs: 4, tail: true };
+    let sound = animal.speak();
+}").unwrap();
+
+    let mut owner = String::new();
+    let methods = tokens.iter().filter_map(|token| match token {
+        ILToken::StartStructure(_, name, _, _) => {
+            owner = name.clone();
+            None
+        },
+        ILToken::StartMethod(method) if method.name == "speak" || method.name == "walk" => Some((format!("{owner}::{}", method.name), method.flags.clone())),
+        _ => None,
+    }).collect::<Vec<_>>();
+
+    let is_virtual = |name: &str, new_slot: bool| methods.iter().any(|(method, flags)| method == name
+        && flags.contains(&FunctionFlags::Public) && flags.contains(&FunctionFlags::Virtual) && flags.contains(&FunctionFlags::NewSlot) == new_slot);
+
+    assert!(is_virtual("Animal::speak", true));
+    assert!(is_virtual("Animal::walk", true));
+    assert!(is_virtual("Dog::speak", false));
+    assert!(is_virtual("Dog::walk", true));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::Call { ty, method_name, is_virtual: true, .. })
+        if ty == "Animal" && method_name == "speak")));
+}
+
+#[test]
+fn generic_base_class() {
+    let tokens = test_emit::<ArpFile, _>("
+class Box<T> {
+    value: T
+}
+
+class Labeled<U> : Box<U> {
+    label: string
+}
+
+fn main() {
+    let labeled = Labeled<int32> { value: 1, label: \"one\" };
+    let value = labeled.value;
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartStructure(_, name, Some(extends), _) 
+        if name == "Labeled`1<U>" && extends == "class Box`1<!0>")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::GetField(fld_ty, owner, name)) 
+        if fld_ty.0 == "!0" && owner == "class Box`1<int32>" && name == "value")));
+}
+
+#[test]
+fn trait_interface() {
+    let tokens = test_emit::<ArpFile, _>("
+trait Named {
+    fn name(this) -> string;
+
+    fn greeting(this) -> string {
+        return this.name();
+    }
+}
+
+class Dog {
+    age: int32
+}
+
+impl Named for Dog {
+    fn name(this) -> string {
+        return \"dog\";
+    }
+}
+
+fn main() {
+    let dog = Dog { age: 3 };
+    let greeting = dog.greeting();
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartStructure(flags, name, None, _) 
+        if name == "Named" && flags.contains(&StructureFlags::Interface))));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartMethod(method) 
+        if method.name == "name" && method.flags.contains(&FunctionFlags::Abstract))));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartStructure(_, name, None, implements) 
+        if name == "Dog" && implements == &["Named"])));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartMethod(method) 
+        if method.name == "name" && method.overrides.as_deref() == Some("Named::name"))));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::Call { ty, method_name, is_virtual: true, .. }) 
+        if ty == "Named" && method_name == "greeting")));
+}
+
+#[test]
+fn operator_overload() {
+    let tokens = test_emit::<ArpFile, _>("
+class Version {
+    major: int32
+}
+
+impl Version {
+    fn add(this, other: Version) -> Version {
+        return Version { major: this.major + other.major };
+    }
+
+    fn cmp(this, other: Version) -> int32 {
+        return this.major - other.major;
+    }
+}
+
+fn main() {
+    let a = Version { major: 1 };
+    let b = a + a;
+    let older = a < b;
+    let text = \"v\" + \"1\";
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::Call { ty, method_name, .. }) 
+        if ty == "Version" && method_name == "add")));
+    assert!(tokens.windows(3).any(|window| matches!(window, [
+        ILToken::OpCode(OpCode::Call { method_name, .. }),
+        ILToken::OpCode(OpCode::LoadInt(0)),
+        ILToken::OpCode(OpCode::LessThen),
+    ] if method_name == "cmp")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::Call { ty, method_name, is_instance: false, .. }) 
+        if ty == "System.String" && method_name == "Concat")));
+}
+
+#[test]
+fn value_type_struct() {
+    let tokens = test_emit::<ArpFile, _>("
+struct Point {
+    x: int32,
+    y: int32
+}
+
+impl Point {
+    fn move_by(mut this, dx: int32) {
+        this.x = this.x + dx;
+    }
+}
+
+fn main() {
+    let mut point = Point { x: 1, y: 2 };
+    point.move_by(3);
+    let x = point.x;
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartStructure(flags, name, Some(extends), _) 
+        if name == "Point" && extends == "[System.Runtime]System.ValueType" && flags.contains(&StructureFlags::Sequential))));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::NewObject(ty, _)) 
+        if ty.0 == "valuetype Point")));
+    assert!(tokens.windows(3).any(|window| matches!(window, [
+        ILToken::OpCode(OpCode::LoadLocalAddress(0)),
+        ILToken::OpCode(OpCode::LoadInt(3)),
+        ILToken::OpCode(OpCode::Call { method_name, .. }),
+    ] if method_name == "move_by")));
+    assert!(tokens.windows(2).any(|window| matches!(window, [
+        ILToken::OpCode(OpCode::LoadLocalAddress(0)),
+        ILToken::OpCode(OpCode::GetField(_, owner, field)),
+    ] if owner == "Point" && field == "x")));
+}
+
+#[test]
+fn enum_declaration() {
+    let tokens = test_emit::<ArpFile, _>("
+enum Color {
+    Red,
+    Green = 5,
+    Blue
+}
+
+fn main() {
+    let color = Color.Blue;
+    let is_red = color == Color.Red;
+    let value = color.value;
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartStructure(_, name, Some(extends), _) 
+        if name == "Color" && extends == "[System.Runtime]System.Enum")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::EnumValue(ty) if ty.0 == "int32")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::EnumMember(name, ty, _, 6) 
+        if name == "Blue" && ty.0 == "valuetype Color")));
+    assert!(tokens.windows(2).any(|window| matches!(window, [
+        ILToken::OpCode(OpCode::LoadInt(6)),
+        ILToken::OpCode(OpCode::StoreLocalVariable(0)),
+    ])));
+    assert!(!tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::GetField(..)))));
+}
+
+#[test]
+fn array_elements() {
+    let tokens = test_emit::<ArpFile, _>("
+fn main() {
+    let items = [1, 2];
+    items[0] = 3;
+    let count = items.len + items[1];
+}").unwrap();
+
+    assert!(tokens.windows(7).any(|window| matches!(window, [
+        ILToken::OpCode(OpCode::LoadInt(2)),
+        ILToken::OpCode(OpCode::NewArray(ty)),
+        ILToken::OpCode(OpCode::Duplicate),
+        ILToken::OpCode(OpCode::LoadInt(0)),
+        ILToken::OpCode(OpCode::LoadInt(1)),
+        ILToken::OpCode(OpCode::StoreElement(_)),
+        ILToken::OpCode(OpCode::Duplicate),
+    ] if ty.0 == "int32")));
+    assert!(tokens.windows(2).any(|window| matches!(window, [
+        ILToken::OpCode(OpCode::LoadInt(3)),
+        ILToken::OpCode(OpCode::StoreElement(ty)),
+    ] if ty.0 == "int32")));
+    assert!(tokens.windows(2).any(|window| matches!(window, [
+        ILToken::OpCode(OpCode::LoadLength),
+        ILToken::OpCode(OpCode::ConvertToInt32),
+    ])));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::LoadElement(ty)) if ty.0 == "int32")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartMethod(method) if method.registers.iter().any(|ty| ty.0 == "int32[]"))));
+}
+
+#[test]
+fn tuple_fields() {
+    let tokens = test_emit::<ArpFile, _>("
+fn main() {
+    let pair = (1, \"one\");
+    let (number, _) = pair;
+    let name = pair.1;
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::NewObject(ty, params))
+        if ty.0 == "valuetype [System.Runtime]System.ValueTuple`2<int32,string>" && params.iter().map(|param| param.0.as_str()).eq(["!0", "!1"]))));
+    assert!(tokens.windows(4).any(|window| matches!(window, [
+        ILToken::OpCode(OpCode::Duplicate),
+        ILToken::OpCode(OpCode::GetField(fld_ty, _, name)),
+        ILToken::OpCode(OpCode::StoreLocalVariable(_)),
+        ILToken::OpCode(OpCode::Pop),
+    ] if fld_ty.0 == "!0" && name == "Item1")));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::GetField(fld_ty, _, name)) if fld_ty.0 == "!1" && name == "Item2")));
+}
+
+#[test]
+fn closures() {
+    let tokens = test_emit::<ArpFile, _>("
+class Numbers {
+    count: int32
+}
+
+impl Numbers {
+    fn square(x: int32) -> int32 {
+        return x * x;
+    }
+}
+
+fn main() {
+    let offset = 10;
+    let shift = fn(x: int32) => x + offset;
+    let y = shift(1);
+    let square = Numbers.square;
+    let z = square(y);
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartStructure(flags, name, None, _)
+        if name.starts_with("$Closure_") && flags.contains(&StructureFlags::Sealed))));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::Field(name, ty) if name == "offset" && ty.0 == "int32")));
+    assert!(tokens.windows(2).any(|window| matches!(window, [
+        ILToken::OpCode(OpCode::LoadArgument(0)),
+        ILToken::OpCode(OpCode::GetField(_, owner, name)),
+    ] if owner.starts_with("$Closure_") && name == "offset")));
+
+    // The closure is created with its captures, then wrapped into a `Func` pointing at `Invoke`.
+    assert!(tokens.windows(4).any(|window| matches!(window, [
+        ILToken::OpCode(OpCode::LoadLocalVariable(0)),
+        ILToken::OpCode(OpCode::NewObject(closure, _)),
+        ILToken::OpCode(OpCode::LoadFunction { is_instance: true, method_name, .. }),
+        ILToken::OpCode(OpCode::NewObject(func, params)),
+    ] if closure.0.starts_with("$Closure_") && method_name == "Invoke"
+        && func.0 == "class [System.Runtime]System.Func`2<int32,int32>" && params.iter().map(|param| param.0.as_str()).eq(["object", "native int"]))));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::Call { is_virtual: true, return_type, ty, method_name, .. })
+        if return_type.0 == "!1" && ty == "class [System.Runtime]System.Func`2<int32,int32>" && method_name == "Invoke")));
+
+    // Static methods are loaded without a target.
+    assert!(tokens.windows(2).any(|window| matches!(window, [
+        ILToken::OpCode(OpCode::LoadNull),
+        ILToken::OpCode(OpCode::LoadFunction { is_instance: false, ty, method_name, .. }),
+    ] if ty == "Numbers" && method_name == "square")));
+}
+
+#[test]
+fn extension_methods() {
+    let tokens = test_emit::<ArpFile, _>("
+impl string {
+    fn shout(this) -> string {
+        return this + \"!\";
+    }
+}
+
+impl int32 {
+    fn twice(this) -> int32 {
+        return this * 2;
+    }
+}
+
+fn main() {
+    let greeting = \"hi\";
+    let loud = greeting.shout();
+    let two = 1;
+    let four = two.twice();
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartStructure(flags, name, None, _)
+        if name == "StringExtensions" && flags.contains(&StructureFlags::Extension) && flags.contains(&StructureFlags::Abstract))));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartMethod(method)
+        if method.name == "shout" && method.flags.contains(&FunctionFlags::IsStatic(true)) && method.flags.contains(&FunctionFlags::Extension)
+            && method.params.iter().map(|(_, ty)| ty.0.as_str()).eq(["string"]))));
+
+    // Structs are passed by reference, the receiver is the address of the local.
+    assert!(tokens.windows(2).any(|window| matches!(window, [
+        ILToken::OpCode(OpCode::LoadLocalAddress(_)),
+        ILToken::OpCode(OpCode::Call { is_instance: false, ty, method_name, args, .. }),
+    ] if ty == "Int32Extensions" && method_name == "twice" && args.iter().map(|arg| arg.0.as_str()).eq(["int32&"]))));
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::OpCode(OpCode::Call { is_instance: false, ty, method_name, .. })
+        if ty == "StringExtensions" && method_name == "shout")));
+}
+
+#[test]
+fn shadowed_locals() {
+    let tokens = test_emit::<ArpFile, _>("
+fn main() {
+    let x = 1;
+    {
+        let x = \"one\";
+        let y = x;
+    }
+    let z = x;
+}").unwrap();
+
+    assert!(tokens.iter().any(|token| matches!(token, ILToken::StartMethod(method)
+        if method.name == "main" && method.registers.iter().map(|ty| ty.0.as_str()).eq(["int32", "string", "string", "int32"]))));
+    assert!(tokens.windows(2).any(|window| matches!(window, [
+        ILToken::OpCode(OpCode::LoadLocalVariable(1)),
+        ILToken::OpCode(OpCode::StoreLocalVariable(2)),
+    ])));
+    assert!(tokens.windows(2).any(|window| matches!(window, [
+        ILToken::OpCode(OpCode::LoadLocalVariable(0)),
+        ILToken::OpCode(OpCode::StoreLocalVariable(3)),
+    ])));
+}
+
+#[test]
+fn break_out_of_loop() {
+    let tokens = test_emit::<ArpFile, _>("
+fn main() {
+    let mut i = 0;
+    while true {
+        i = i + 1;
+        if i > 10 {
+            break;
+        }
+    }
+}").unwrap();
+
+    let Some(ILToken::OpCode(OpCode::BranchTo(loop_end))) = tokens.iter().rev().find(|token| matches!(token, ILToken::OpCode(OpCode::BranchTo(_)))) else {
+        panic!("no branch out of the loop");
+    };
+
+    assert!(matches!(&tokens[tokens.len() - 2], ILToken::OpCode(OpCode::LabeledOpCode(label, _)) if label == loop_end));
+}
+
+#[test]
+fn overload_arguments_are_converted() {
+    let tokens = test_emit::<ArpFile, _>("
+class Printer {}
+
+impl Printer {
+    fn show(value: float64) {}
+
+    fn show(value: object) {}
+}
+
+fn main() {
+    Printer.show(1);
+    Printer.show(true);
+}").unwrap();
+
+    let widened = tokens.iter().position(|token| matches!(token, ILToken::OpCode(OpCode::ConvertToFloat64))).expect("no widening");
+    assert!(matches!(tokens[widened - 1], ILToken::OpCode(OpCode::LoadInt(1))));
+
+    let boxed = tokens.iter().position(|token| matches!(token, ILToken::OpCode(OpCode::Box(ty)) if ty.0 == "bool")).expect("no boxing");
+    assert!(matches!(tokens[boxed - 1], ILToken::OpCode(OpCode::LoadBool(true))));
+}
+
+#[test]
+fn assigned_numbers_are_widened() {
+    let tokens = test_emit::<ArpFile, _>("
+fn half() -> float64 {
+    return 1;
+}
+
+fn main() {
+    let single: float32 = 2;
+}").unwrap();
+
+    let returned = tokens.iter().position(|token| matches!(token, ILToken::OpCode(OpCode::ConvertToFloat64))).expect("return not widened");
+    assert!(matches!(tokens[returned - 1], ILToken::OpCode(OpCode::LoadInt(1))));
+
+    let declared = tokens.iter().position(|token| matches!(token, ILToken::OpCode(OpCode::ConvertToFloat32))).expect("let not widened");
+    assert!(matches!(tokens[declared - 1], ILToken::OpCode(OpCode::LoadInt(2))));
+    assert!(matches!(tokens[declared + 1], ILToken::OpCode(OpCode::StoreLocalVariable(0))));
+}
+
+#[test]
+fn casts_emit_conversions() {
+    let tokens = test_emit::<ArpFile, _>("
+class Shape {
+    sides: int32
+}
+
+class Square : Shape {
+    size: int32
+}
+
+fn main() {
+    let half = 3 as float32 / 2.0;
+    let shape = Square { sides: 4, size: 2 } as Shape;
+    let square = shape as Square;
+    let boxed = 1 as object;
+    let unboxed = boxed as int32;
+}").unwrap();
+
+    let opcodes = tokens.iter().filter_map(|token| match token {
+        ILToken::OpCode(opcode) => Some(opcode),
+        _ => None,
+    }).collect::<Vec<_>>();
+
+    assert!(opcodes.iter().any(|opcode| matches!(opcode, OpCode::ConvertToFloat32)));
+    assert!(opcodes.iter().any(|opcode| matches!(opcode, OpCode::CastClass(ty) if ty.ends_with("Square"))));
+    assert!(opcodes.iter().any(|opcode| matches!(opcode, OpCode::Box(ty) if ty.0 == "int32")));
+    assert!(opcodes.iter().any(|opcode| matches!(opcode, OpCode::UnboxAny(ty) if ty.0 == "int32")));
+}
+
+#[test]
+fn value_types_are_boxed() {
+    let tokens = test_emit::<ArpFile, _>("
+trait Printable {
+    fn print(this) -> string;
+}
+
+struct Point {
+    x: int32
+}
+
+impl Printable for Point {
+    fn print(this) -> string {
+        return \"point\";
+    }
+}
+
+fn describe(value: object, printable: Printable) -> object {
+    return 1;
+}
+
+fn main() {
+    let mut any: object = 1;
+    any = true;
+    let result = describe(2.5, Point { x: 2 });
+}").unwrap();
+
+    let boxed = tokens.iter().filter_map(|token| match token {
+        ILToken::OpCode(OpCode::Box(ty)) => Some(ty.0.as_str()),
+        _ => None,
+    }).collect::<Vec<_>>();
+
+    assert_eq!(boxed.len(), 5);
+    assert_eq!(boxed.iter().filter(|ty| **ty == "int32").count(), 2);
+    assert!(boxed.contains(&"bool"));
+    assert!(boxed.contains(&"float32"));
+    assert!(boxed.iter().any(|ty| ty.ends_with("Point")));
+}
+
+#[test]
+fn named_and_default_arguments() {
+    let tokens = test_emit::<ArpFile, _>("
+fn scale(value: float32, factor: float32 = 2.0) -> float32 {
+    return value * factor;
+}
+
+fn main() {
+    let half = scale(factor: 0.5, value: 4.0);
+    let twice = scale(1.0);
+}").unwrap();
+
+    // Arguments are passed in parameter order, the left out ones get their default.
+    let floats = tokens.iter().filter_map(|token| match token {
+        ILToken::OpCode(OpCode::LoadFloat(value)) => Some(*value),
+        _ => None,
+    }).collect::<Vec<_>>();
+
+    assert_eq!(floats, [4.0, 0.5, 1.0, 2.0]);
+    assert_eq!(tokens.iter().filter(|token| matches!(token, ILToken::OpCode(OpCode::Call { method_name, args, .. }) 
+        if method_name == "scale" && args.len() == 2)).count(), 2);
+}
+
+#[test]
+fn named_arguments_keep_source_order() {
+    let tokens = test_emit::<ArpFile, _>("
+fn first() -> int32 {
+    return 1;
+}
+
+fn second() -> int32 {
+    return 2;
+}
+
+fn subtract(a: int32, b: int32) -> int32 {
+    return a - b;
+}
+
+fn main() {
+    let difference = subtract(b: first(), a: second());
+}").unwrap();
+
+    let steps = tokens.iter().filter_map(|token| match token {
+        ILToken::OpCode(OpCode::Call { method_name, .. }) => Some(format!("call {method_name}")),
+        ILToken::OpCode(OpCode::StoreLocalVariable(register)) => Some(format!("stloc {register}")),
+        ILToken::OpCode(OpCode::LoadLocalVariable(register)) => Some(format!("ldloc {register}")),
+        _ => None,
+    }).collect::<Vec<_>>();
+
+    // `first` is written first, so it's called first and held until `a` is passed.
+    assert_eq!(steps, ["call first", "stloc 0", "call second", "stloc 1", "ldloc 1", "ldloc 0", "call subtract", "stloc 0"]);
+}
+
+#[test]
+fn trailing_expression_is_returned() {
+    let tokens = test_emit::<ArpFile, _>("
+fn answer() -> int32 {
+    42
+}
+
+fn parse() -> int32 {}").unwrap();
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("main.il");
+    write_tokens_to_file(&path, &tokens).unwrap();
+    let il = std::fs::read_to_string(&path).unwrap();
+
+    let body = |name: &str| {
+        let start = il.find(&format!("{name} (")).unwrap();
+        let end = il.find(&format!("}} // {name}")).unwrap();
+
+        il[start..end].lines().map(str::trim).skip_while(|line| *line != ")").skip(1).filter(|line| !line.is_empty()).collect::<Vec<_>>()
+    };
+
+    // The value is left on the stack for the only `ret` of the method.
+    assert_eq!(body("answer"), ["ldc.i4.s 42", "ret"]);
+    assert_eq!(body("parse"), ["newobj instance void [System.Runtime]System.NotImplementedException::.ctor()", "throw"]);
+}
@@ -192,8 +192,8 @@ pub fn test_assignment() {
         my_field: int32
     }
     fn main() { 
-        let inst = MyClass { my_field: 1 };
-        let x = 2;
+        let mut inst = MyClass { my_field: 1 };
+        let mut x = 2;
         x = 3;
         inst.my_field = x;
 
@@ -232,7 +232,7 @@ pub fn test_control_statements() {
             Console.Write(4);
         }
 
-        let i = 5;
+        let mut i = 5;
         while i < 10 {
             Console.Write(1); // TODO type resolver failed to resolve Write type
             i = i + 1;
@@ -243,3 +243,518 @@ pub fn test_control_statements() {
     assert_eq!(output, "411111");
 }
 
+
+
+#[test]
+pub fn test_union_match() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console 
+
+    union Shape {
+        Circle { r: int32 },
+        Rect { w: int32, h: int32 },
+        Empty,
+    }
+
+    fn main() { 
+        let shape = Shape.Rect { w: 2, h: 3 };
+        
+        let area = match shape {
+            Shape.Circle { r } => r * r * 3,
+            Shape.Rect { w, h } => w * h,
+            Shape.Empty => 0,
+        };
+
+        Console.Write(area);
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "6");
+}
+
+
+#[test]
+pub fn test_option_propagation() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console 
+
+    class Parser { }
+
+    impl Parser {
+        fn half(x: int32) -> Option<int32> {
+            if x > 1 {
+                return Option<int32>.Some { value: x / 2 };
+            }
+
+            return Option<int32>.None { };
+        }
+
+        fn quarter(x: int32) -> Option<int32> {
+            let half = Parser.half(x)?;
+            return Parser.half(half);
+        }
+    }
+
+    fn main() { 
+        let first = match Parser.quarter(8) {
+            Option<int32>.Some { value } => value,
+            Option<int32>.None => 0,
+        };
+        Console.Write(first);
+
+        let second = match Parser.quarter(2) {
+            Option<int32>.Some { value } => value,
+            Option<int32>.None => 0,
+        };
+        Console.Write(second);
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "20");
+}
+
+
+#[test]
+pub fn test_generic_box() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console 
+
+    class Box<T> {
+        value: T
+    }
+
+    impl Box<T> {
+        fn get(this) -> T {
+            return this.value;
+        }
+    }
+
+    class Boxes { }
+
+    impl Boxes {
+        fn wrap<U>(x: U) -> Box<U> {
+            return Box<U> { value: x };
+        }
+    }
+
+    fn id<T>(x: T) -> T {
+        return x;
+    }
+
+    fn main() { 
+        let number = Boxes.wrap(4);
+        Console.Write(number.get());
+
+        let text = Box<string> { value: \"two\" };
+        Console.Write(text.value);
+        Console.Write(id(5));
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "4two5");
+}
+
+
+#[test]
+pub fn test_managed_list() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console 
+    from extern System.Collections.dll import System.Collections.Generic.List
+
+    fn main() { 
+        let numbers = List<int32> { };
+        numbers.Add(3);
+        numbers.Add(4);
+
+        Console.Write(numbers.get_Item(1));
+        Console.Write(numbers.get_Count());
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "42");
+}
+
+
+#[test]
+pub fn test_inheritance() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console 
+
+    class Shape {
+        sides: int32
+    }
+
+    impl Shape {
+        fn describe(this) -> int32 {
+            return this.sides;
+        }
+    }
+
+    class Square : Shape {
+        size: int32
+    }
+
+    impl Square {
+        fn describe(this) -> int32 {
+            return base.describe() * 10 + this.size;
+        }
+    }
+
+    fn main() { 
+        let square = Square { sides: 4, size: 2 };
+        Console.Write(square.describe());
+        Console.Write(square.sides);
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "424");
+}
+
+#[test]
+pub fn test_traits() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console 
+
+    trait Named {
+        fn name(this) -> string;
+
+        fn greeting(this) -> string {
+            return this.name();
+        }
+    }
+
+    class Dog {
+        age: int32
+    }
+
+    impl Named for Dog {
+        fn name(this) -> string {
+            return \"dog\";
+        }
+    }
+
+    class Printer<T: Named> {
+        value: T
+    }
+
+    impl Printer<T> {
+        fn run(this) -> string {
+            return this.value.name();
+        }
+    }
+
+    fn main() { 
+        let dog = Dog { age: 3 };
+        Console.Write(dog.greeting());
+        let printer = Printer<Dog> { value: dog };
+        Console.Write(printer.run());
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "dogdog");
+}
+
+#[test]
+pub fn test_operators() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console 
+
+    class Vector {
+        x: int32,
+        y: int32
+    }
+
+    impl Vector {
+        fn add(this, other: Vector) -> Vector {
+            return Vector { x: this.x + other.x, y: this.y + other.y };
+        }
+
+        fn eq(this, other: Vector) -> bool {
+            return this.x == other.x and this.y == other.y;
+        }
+    }
+
+    fn main() { 
+        let a = Vector { x: 1, y: 2 };
+        let sum = a + Vector { x: 3, y: 4 };
+        Console.Write(sum.x);
+        Console.Write(sum.y);
+        Console.Write(\"-\" + \"ok\");
+        if sum != a {
+            Console.Write(\"!\");
+        }
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "46-ok!");
+}
+
+#[test]
+pub fn test_structs() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console 
+
+    struct Point {
+        x: int32,
+        y: int32
+    }
+
+    struct Line {
+        start: Point,
+        finish: Point
+    }
+
+    impl Point {
+        fn move_by(mut this, dx: int32) {
+            this.x = this.x + dx;
+        }
+
+        fn sum(this) -> int32 {
+            return this.x + this.y;
+        }
+    }
+
+    impl Line {
+        fn unit() -> Line {
+            return Line { start: Point { x: 0, y: 0 }, finish: Point { x: 1, y: 1 } };
+        }
+    }
+
+    fn main() { 
+        let mut a = Point { x: 1, y: 2 };
+        let b = a;
+        a.move_by(3);
+        a.y = 5;
+        Console.Write(a.sum());
+        Console.Write(b.sum());
+
+        let mut line = Line.unit();
+        line.start.x = 7;
+        Console.Write(line.start.x);
+        Console.Write(Line.unit().finish.sum());
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "9372");
+}
+
+#[test]
+pub fn test_enums() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console, System.ConsoleColor
+
+    enum Size {
+        Small,
+        Medium = 10,
+        Large
+    }
+
+    fn main() { 
+        let size = Size.Large;
+        Console.Write(size.value);
+        if size > Size.Medium {
+            Console.Write(\"-\");
+        }
+        if size != Size.Small {
+            Console.Write(ConsoleColor.Red.value);
+        }
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "11-12");
+}
+
+#[test]
+pub fn test_arrays() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console
+    from extern System.Runtime.dll import System.String
+
+    struct Point {
+        x: int32,
+        y: int32
+    }
+
+    class Name {
+        text: string
+    }
+
+    fn main() { 
+        let numbers = [1, 2, 3];
+        numbers[0] = numbers[1] + numbers[2];
+        Console.Write(numbers[0]);
+        Console.Write(numbers.len);
+
+        let points = [Point { x: 1, y: 2 }];
+        points[0].x = 7;
+        Console.Write(points[0].x);
+
+        let names = [Name { text: \"a\" }, Name { text: \"b\" }];
+        Console.Write(names[1].text);
+        Console.Write(System.String.Join(\",\", [\"x\", \"y\"]));
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "537bx,y");
+}
+
+#[test]
+pub fn test_tuples() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console
+
+    class Pairs {
+        count: int32
+    }
+
+    impl Pairs {
+        fn swap((a, b): (int32, string)) -> (string, int32) {
+            return (b, a);
+        }
+    }
+
+    fn main() { 
+        let pair = Pairs.swap((4, \"x\"));
+        Console.Write(pair.0);
+        Console.Write(pair.1);
+
+        let (name, _) = pair;
+        let nested = ((1, 2), name);
+        Console.Write(nested.0.1);
+        Console.Write(nested.1);
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "x42x");
+}
+
+#[test]
+pub fn test_closures() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console 
+    from extern System.Runtime.dll import System.Comparison
+    from extern System.Collections.dll import System.Collections.Generic.List
+
+    fn twice(f: fn(int32) -> int32, x: int32) -> int32 {
+        return f(f(x));
+    }
+
+    fn main() { 
+        let offset = 10;
+        let shift = fn(x: int32) => x + offset;
+        Console.Write(twice(shift, 1));
+
+        let make = fn(x: int32) => fn(y: int32) => x * y;
+        let triple = make(3);
+        Console.Write(triple(2));
+
+        let numbers = List<int32> { };
+        numbers.Add(3);
+        numbers.Add(1);
+        numbers.Add(2);
+        numbers.Sort(fn(a: int32, b: int32) => b - a);
+        numbers.ForEach(fn(x: int32) => Console.Write(x));
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "216321");
+}
+
+#[test]
+pub fn test_extension_methods() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console 
+
+    impl string {
+        fn shout(this) -> string {
+            return this + \"!\";
+        }
+    }
+
+    impl int32 {
+        fn twice(this) -> int32 {
+            return this * 2;
+        }
+    }
+
+    fn main() { 
+        let greeting = \"hi\";
+        Console.Write(greeting.shout());
+
+        let two = 2;
+        Console.Write(two.twice());
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "hi!4");
+}
+
+#[test]
+pub fn test_managed_runtime_types() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console 
+    from extern System.Runtime.dll import System.Text.StringBuilder
+
+    fn main() { 
+        let builder = new StringBuilder(\"a\");
+        let appended = builder.Append(\"b\");
+
+        Console.Write(appended.ToString());
+        Console.Write(builder.Length);
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "ab2");
+}
+
+#[test]
+pub fn test_named_arguments() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = test_write_and_compile("
+    from extern System.Console.dll import System.Console 
+
+    fn show(text: string) -> string {
+        Console.Write(text);
+        return text;
+    }
+
+    fn pair(a: string, b: string, sep: string = \"-\") -> string {
+        return a + sep + b;
+    }
+
+    fn main() { 
+        Console.Write(pair(b: show(\"b\"), a: show(\"a\")));
+    }
+    ", &temp_dir);
+
+    assert_eq!(output, "baa-b");
+}
@@ -1,12 +1,44 @@
-use arp_ast_processor::types::type_collection::TypeInfo;
+use std::path::Path;
+
+use arp_ast_processor::types::type_collection::{GenericOwner, TypeInfo, TypeKind, TypeSourceKind};
 
 use crate::il_token::ResolvedType;
 
 impl From<&TypeInfo> for ResolvedType {
     fn from(value: &TypeInfo) -> Self {
-        match &value.short_name {
-            Some(short) => Self(short.clone()),
-            None => Self(format!("class {}", value.full_name).to_string()),
+        match (&value.source, &value.short_name, &value.kind) {
+            (_, _, TypeKind::Parameter { owner: GenericOwner::Type, index, .. }) => Self(format!("!{index}")),
+            (_, _, TypeKind::Parameter { owner: GenericOwner::Method, index, .. }) => Self(format!("!!{index}")),
+            // Within its own declaration a generic class is instantiated with its own parameters.
+            (_, _, TypeKind::Generic(params)) => Self(format!(
+                "{} {}<{}>",
+                type_keyword(value),
+                il_name(value),
+                (0..params.len()).map(|index| format!("!{index}")).collect::<Vec<_>>().join(","),
+            )),
+            (TypeSourceKind::Standard, Some(short), _) => Self(short.clone()),
+            _ => Self(format!("{} {}", type_keyword(value), value.full_name).to_string()),
         }
     }
 }
+
+/// Structs are referenced as `valuetype`, everything else as `class`.
+pub(crate) fn type_keyword(value: &TypeInfo) -> &'static str {
+    match value.is_value_type {
+        true => "valuetype",
+        false => "class",
+    }
+}
+
+/// Name of the assembly a dll import refers to, `[System.Collections]` for `libs/System.Collections.dll`.
+pub(crate) fn assembly_name(dll: &str) -> &str {
+    Path::new(dll).file_stem().and_then(|stem| stem.to_str()).unwrap_or(dll)
+}
+
+/// Name of the class in IL, generic classes carry their arity, like ``Box`1``.
+pub(crate) fn il_name(value: &TypeInfo) -> String {
+    match &value.kind {
+        TypeKind::Generic(params) => format!("{}`{}", value.full_name, params.len()),
+        _ => value.full_name.to_string(),
+    }
+}
@@ -1,9 +1,20 @@
+use std::collections::HashSet;
+
 use arp_ast_processor::types::{
     ast_node_value::{Ast, Id},
+    expression::Expression,
     file::ArpFile,
+    function::Function,
+    simple::Identifier,
+    type_collection::{TypeId, TypeKind, TypeSourceKind},
 };
 
-use crate::{emitter::Emitter, traits::Visitor};
+use crate::{
+    emitter::Emitter,
+    il_token::{FunctionFlags, ILToken, Method, OpCode, ResolvedType, StructureFlags},
+    traits::Visitor,
+    EmitError,
+};
 
 impl Visitor<ArpFile> for Emitter {
     fn visit(&mut self, index: &Id<ArpFile>, ast: &Ast) -> Result<(), crate::EmitError> {
@@ -17,8 +28,84 @@ impl Visitor<ArpFile> for Emitter {
             self.visit(structure, ast)?;
         }
 
-        
+        // Extension methods of the file are grouped into one static class per extended type.
+        let mut extensions: Vec<(TypeId, Vec<Id<Function>>)> = vec![];
+        for implementation in file.implementations.iter().map(|im| ast.get(im)).filter(|im| im.is_extension) {
+            match extensions.iter_mut().find(|(extended, _)| extended.is_same(&implementation.impl_type)) {
+                Some((_, functions)) => functions.extend(implementation.functions.iter().copied()),
+                None => extensions.push((implementation.impl_type.clone(), implementation.functions.clone())),
+            }
+        }
+
+        for (extended, functions) in extensions {
+            let name = Emitter::extension_class(&file.type_collection, &extended)?;
+            let flags = HashSet::from_iter(vec![StructureFlags::Abstract, StructureFlags::Sealed, StructureFlags::Extension]);
+
+            self.write(ILToken::StartStructure(flags, name.clone(), None, vec![]));
+            for func in &functions {
+                self.visit(func, ast)?;
+            }
+            self.write(ILToken::EndStructure(name));
+        }
+
+        for expr in ast.get_children_of_kind::<Expression, _>(*index) {
+            self.write_closure(ast, &expr)?;
+        }
+
+        for (id, ty) in file.type_collection.iter_resolved() {
+            if let (TypeSourceKind::Prelude(_), TypeKind::Union(variants)) = (&ty.source, &ty.kind) {
+                if self.mark_prelude_emitted(&ty.full_name) {
+                    self.write_union(index.as_weak(), ast, &id, ty, variants)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Emitter {
+    /// Class of lambda `expr`, its captured variables are fields set by the constructor and its body is `Invoke`.
+    fn write_closure(&mut self, ast: &Ast, expr: &Id<Expression>) -> Result<(), EmitError> {
+        let Expression::Lambda { func, captures } = ast.get(expr) else { return Ok(()) };
+        let name = Identifier::closure(expr).0.to_string();
+
+        let flags = HashSet::from_iter(vec![StructureFlags::Auto, StructureFlags::Sealed]);
+        self.write(ILToken::StartStructure(flags, name.clone(), None, vec![]));
+
+        let fields = captures
+            .iter()
+            .map(|(ident, ty)| Emitter::resolve_ty(ast, ty, *expr).map(|ty| (ident.0.to_string(), ty)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (fld, fld_ty) in &fields {
+            self.write(ILToken::Field(fld.clone(), fld_ty.clone()));
+        }
+
+        self.write(ILToken::StartMethod(Method {
+            flags: HashSet::from_iter(vec![
+                FunctionFlags::Cil,
+                FunctionFlags::Managed,
+                FunctionFlags::IsStatic(false),
+            ]),
+            params: fields.clone(),
+            registers: vec![],
+            return_ty: ResolvedType("void".to_string()),
+            name: ".ctor".to_string(),
+            overrides: None,
+        }));
+
+        for (fld_index, (fld, fld_ty)) in fields.into_iter().enumerate() {
+            self.write(OpCode::LoadArgument(0));
+            self.write(OpCode::LoadArgument(fld_index + 1));
+            self.write(OpCode::SetField(fld_ty, name.clone(), fld));
+        }
+
+        self.write(ILToken::EndMethod(".ctor".to_string()));
+
+        self.visit(func, ast)?;
 
+        self.write(ILToken::EndStructure(name));
 
         Ok(())
     }
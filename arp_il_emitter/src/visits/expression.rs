@@ -1,16 +1,23 @@
 use arp_ast_processor::{
     types::{
-        ast_node_value::{Ast, Id},
-        expression::{BinaryOperator, Expression, Literal, UnaryOperator},
-        type_collection::TypeSourceKind,
+        ast_node_value::{Ast, Id, WId},
+        expression::{BinaryOperator, Expression, Literal, OperatorOverload, Pattern, UnaryOperator},
+        function::{Function, FunctionKind},
+        simple::Identifier,
+        type_collection::{CastKind, Conversion, Overload, PreludeType, PropertyInfo, StaticFieldInfo, TypeId, TypeKind},
     },
     utils::VariableSource,
 };
 
-use crate::{emitter::Emitter, il_token::OpCode, traits::Visitor, EmitError};
+use crate::{emitter::{Emitter, RUNTIME_ASSEMBLY}, il_token::{OpCode, ResolvedType}, traits::Visitor, EmitError};
 
 impl Visitor<Expression> for Emitter {
     fn visit(&mut self, index: &Id<Expression>, ast: &Ast) -> Result<(), EmitError> {
+        if self.spilled.contains(index) {
+            self.write(OpCode::LoadLocalVariable(Emitter::temporary_register(ast, index)?));
+            return Ok(());
+        }
+
         match ast.get(index) {
             Expression::Literal(lit) => match lit {
                 Literal::Integer(i) => self.write(OpCode::LoadInt(*i)),
@@ -21,9 +28,22 @@ impl Visitor<Expression> for Emitter {
                 Literal::Bool(b) => self.write(OpCode::LoadBool(*b)),
             },
             Expression::Binary { lhs, op, rhs } => {
-                self.visit(lhs, ast)?;
+                let overload = ast.get_operator_overload(index).map_err(|_| EmitError::CantResolveType)?;
+
+                // Operator methods of a type parameter belong to its bound, the value is boxed first.
+                if let Some((OperatorOverload::Method(_), _)) = &overload {
+                    self.visit_receiver(ast, lhs)?;
+                    self.write_box_parameter(ast, index, lhs)?;
+                } else {
+                    self.visit(lhs, ast)?;
+                }
+
                 self.visit(rhs, ast)?;
 
+                if let Some((overload, _)) = overload {
+                    return self.write_operator_overload(ast, index, *lhs, *op, *rhs, overload);
+                }
+
                 match op {
                     BinaryOperator::Or => self.write(OpCode::Or),
                     BinaryOperator::And => self.write(OpCode::And),
@@ -51,130 +71,116 @@ impl Visitor<Expression> for Emitter {
                     BinaryOperator::Divide => self.write(OpCode::Divide),
                 }
             }
-            Expression::Call { on, method, args } => {
+            Expression::Call { on, method, args, .. } => {
                 if let Some(on) = on {
-                    match ast.get(on) {
-                        Expression::Type(ty) => {
-                            for arg in args {
-                                self.visit(arg, ast)?;
-                            }
+                    let on_ty = match ast.get(on) {
+                        Expression::Type(ty) => ty.clone(),
+                        _ => {
+                            self.visit_receiver(ast, on)?;
 
-                            let arp_file = ast
-                                .get_arp_file_in_parent(index.as_weak())
-                                .ok_or(EmitError::ArpFileNotFound)?;
+                            // Methods of a type parameter belong to its bound, the value is boxed first.
+                            self.write_box_parameter(ast, index, on)?;
 
-                            let type_info = arp_file
-                                .type_collection
-                                .try_get_strong(ty)
-                                .ok_or(EmitError::CantResolveType)?;
+                            ast.get_type(on).map_err(|_| EmitError::CantResolveType)?
+                        }
+                    };
 
-                            let args = args
-                                .iter()
-                                .map(|index| ast.get_type(index))
-                                .collect::<Result<Vec<_>, _>>()
-                                .map_err(|_| EmitError::CantResolveType)?;
+                    self.write_spills(ast, index)?;
 
-                            let method_info = type_info
-                                .find_method(method, args)
-                                .ok_or(EmitError::CantResolveMethod)?;
+                    for (arg, param) in args.iter().zip(Emitter::call_params(ast, index, &on_ty, method, args)?) {
+                        self.visit_argument(ast, arg, &param)?;
+                    }
 
-                            let opcode = OpCode::Call {
-                                is_instance: false,
-                                return_type: ast
-                                    .resolve_type(method_info.return_type.clone(), &index.as_weak())
-                                    .ok_or(EmitError::CantResolveType)
-                                    .map(|ty| ty.into())?,
-                                external: match &type_info.source {
-                                    TypeSourceKind::LocalArp
-                                    | TypeSourceKind::ExternalArp(_)
-                                    | TypeSourceKind::Standard => None,
-                                    TypeSourceKind::ManagedDll(dll) => Some(
-                                        dll.clone()
-                                            .strip_suffix(".dll")
-                                            .unwrap_or_default()
-                                            .to_string(),
-                                    ),
-                                },
-                                ty: type_info.clone().full_name.into(),
-                                method_name: method_info.name.0.to_string(),
-                                args: method_info
-                                    .args
-                                    .iter()
-                                    .map(|(_, ty)| {
-                                        ast.resolve_type(ty.clone(), &index.as_weak())
-                                            .ok_or(EmitError::CantResolveType)
-                                            .map(|ty| ty.into())
-                                    })
-                                    .collect::<Result<Vec<_>, _>>()?,
-                            };
-
-                            self.write(opcode);
-                        }
-                        _ => {
-                            self.visit(on, ast)?;
+                    let opcode = Emitter::call_opcode(ast, index, &on_ty, method, args, Receiver::of(ast, on))?;
 
-                            for arg in args {
-                                self.visit(arg, ast)?;
-                            }
+                    self.write(opcode);
+                } else if ast.get_identifier_source(method, &index.as_weak()).is_some() {
+                    // Function values and delegates are invoked.
+                    self.write_load(ast, method, index)?;
+                    self.write_spills(ast, index)?;
 
-                            let ty = ast.get_type(on).map_err(|_| EmitError::CantResolveType)?;
+                    let callee = ast.get_variable_type(index, method).map_err(|_| EmitError::CantResolveType)?;
+                    let (params, _) = ast
+                        .get_arp_file_in_parent(index.as_weak())
+                        .and_then(|file| file.type_collection.get_callable_signature(&callee))
+                        .ok_or(EmitError::CantResolveType)?;
 
-                            let arp_file = ast
-                                .get_arp_file_in_parent(index.as_weak())
-                                .ok_or(EmitError::ArpFileNotFound)?;
+                    for (arg, param) in args.iter().zip(&params) {
+                        self.visit_converted(ast, arg, param)?;
+                    }
 
-                            let type_info = arp_file
-                                .type_collection
-                                .try_get_strong(&ty)
-                                .ok_or(EmitError::CantResolveType)?;
+                    let collection = &ast
+                        .get_arp_file_in_parent(index.as_weak())
+                        .ok_or(EmitError::ArpFileNotFound)?
+                        .type_collection;
+
+                    let opcode = match collection.get_function_signature(&callee) {
+                        Some(_) => {
+                            let (return_type, ty, args) = Emitter::invoke_ref(collection, &callee)?;
+
+                            OpCode::Call {
+                                is_instance: true,
+                                is_virtual: true,
+                                return_type,
+                                external: None,
+                                ty,
+                                method_name: "Invoke".to_string(),
+                                args,
+                            }
+                        }
+                        None => Emitter::call_opcode(ast, index, &callee, &Identifier::from("Invoke"), args, Receiver::Value)?,
+                    };
+
+                    self.write(opcode);
+                } else {
+                    // Top level functions are global methods, generic ones are instantiated with the inferred arguments.
+                    let method_info = ast.get_top_level_method(index, method).ok_or(EmitError::CantResolveMethod)?;
+                    let func = method_info.definition.ok_or(EmitError::CantResolveMethod)?;
+                    let definition = ast.get(&func);
 
-                            
+                    self.write_spills(ast, index)?;
+
+                    for (arg, (_, param)) in args.iter().zip(&definition.parameters) {
+                        self.visit_argument(ast, arg, param)?;
+                    }
 
-                            let args = args
+                    let method_name = match method_info.generics.is_empty() {
+                        true => definition.name.0.to_string(),
+                        false => {
+                            let collection = &ast
+                                .get_arp_file_in_parent(index.as_weak())
+                                .ok_or(EmitError::ArpFileNotFound)?
+                                .type_collection;
+                            let arg_types = args
                                 .iter()
-                                .map(|index| ast.get_type(index))
+                                .map(|arg| ast.get_type(arg))
                                 .collect::<Result<Vec<_>, _>>()
                                 .map_err(|_| EmitError::CantResolveType)?;
-
-                            let method_info = type_info
-                                .find_method(method, args)
+                            let bindings = collection
+                                .bind_generics(&method_info, &[], &arg_types)
                                 .ok_or(EmitError::CantResolveMethod)?;
 
-                            let opcode = OpCode::Call {
-                                is_instance: true,
-                                return_type: ast
-                                    .resolve_type(method_info.return_type.clone(), &index.as_weak())
-                                    .ok_or(EmitError::CantResolveType)
-                                    .map(|ty| ty.into())?,
-                                external: match &type_info.source {
-                                    TypeSourceKind::LocalArp
-                                    | TypeSourceKind::ExternalArp(_)
-                                    | TypeSourceKind::Standard => None,
-                                    TypeSourceKind::ManagedDll(dll) => Some(
-                                        dll.clone()
-                                            .strip_suffix(".dll")
-                                            .unwrap_or_default()
-                                            .to_string(),
-                                    ),
-                                },
-                                ty: type_info.clone().full_name.into(),
-                                method_name: method_info.name.0.to_string(),
-                                args: method_info
-                                    .args
+                            format!(
+                                "{}<{}>",
+                                definition.name.0,
+                                bindings
                                     .iter()
-                                    .map(|(_, ty)| {
-                                        ast.resolve_type(ty.clone(), &index.as_weak())
-                                            .ok_or(EmitError::CantResolveType)
-                                            .map(|ty| ty.into())
-                                    })
-                                    .collect::<Result<Vec<_>, _>>()?,
-                            };
-
-                            self.write(opcode);
+                                    .map(|ty| Emitter::resolve_ty(ast, ty, *index).map(|ty| ty.0))
+                                    .collect::<Result<Vec<_>, _>>()?
+                                    .join(",")
+                            )
                         }
-                    }
-                } else {
-                    unreachable!("Don't know why this happened");
+                    };
+
+                    self.write(OpCode::Call {
+                        is_instance: false,
+                        is_virtual: false,
+                        return_type: Emitter::resolve_ty(ast, &definition.return_type, func)?,
+                        external: None,
+                        ty: String::new(),
+                        method_name,
+                        args: Emitter::param_types(ast, &func)?,
+                    });
                 }
             }
             Expression::Unary { op, expr } => {
@@ -190,69 +196,350 @@ impl Visitor<Expression> for Emitter {
                     }
                 };
             }
-            Expression::Variable(ident) => {
-                let source = ast
-                    .get_identifier_source(ident, &index.as_weak())
-                    .ok_or(EmitError::VariableHasNoSource)?;
-
-                match source {
-                    VariableSource::Local(loc) => self.write(OpCode::LoadLocalVariable(loc)),
-                    VariableSource::Argument(loc) => self.write(OpCode::LoadArgument(loc)),
+            Expression::Variable(ident) => match ast.get_identifier_source(ident, &index.as_weak()) {
+                Some(_) => self.write_load(ast, ident, index)?,
+                None => {
+                    let func = ast.get_function_value(index, None, ident).ok_or(EmitError::VariableHasNoSource)?;
+                    self.write_function_value(ast, index, &func, None)?;
                 }
-            }
-            Expression::Construct { ident, args } => {
+            },
+            Expression::Construct { ident, args, .. } => {
                 let file = ast
                     .get_arp_file_in_parent(index.as_weak())
                     .ok_or(EmitError::ArpFileNotFound)?;
 
-                let ty = file.type_collection.resolve_name(ident);
+                let ty_id = file.type_collection.resolve_name(ident);
                 let ty = file
                     .type_collection
-                    .try_get_strong(&ty)
+                    .try_get_strong(&ty_id)
                     .ok_or(EmitError::CantResolveType)?;
 
+                // Managed objects are created by their parameterless constructor, then the given fields are set.
+                if file.type_collection.is_managed(&ty_id) && !ty.is_value_type {
+                    let (opcode, _) = Emitter::ctor_ref(ast, index, &ty_id, &[])?;
+                    self.write(opcode);
+
+                    for (fld, arg) in args {
+                        let target = file.type_collection.get_field_type(&ty_id, fld.as_ref()).ok_or(EmitError::CantResolveField)?;
+                        let (owner, fld_ty, fld_name) = Emitter::field_ref(&file.type_collection, &ty_id, fld)?;
+
+                        self.write(OpCode::Duplicate);
+                        self.visit_converted(ast, arg, &target)?;
+                        self.write(OpCode::SetField(fld_ty, owner, fld_name));
+                    }
+
+                    return Ok(());
+                }
+
                 let mut resolved_types = vec![];
-                for fld in &ty.fields {
+                for (fld, fld_ty) in Emitter::ctor_params(&file.type_collection, &ty_id)? {
                     let arg = args
                         .iter()
-                        .find(|(ident, _)| ident == &fld.0)
+                        .find(|(ident, _)| ident == &fld)
                         .ok_or(EmitError::CantResolveField)?;
-                    self.visit(&arg.1, ast)?;
+                    self.visit_converted(ast, &arg.1, &fld_ty)?;
 
-                    let fld_ty = Emitter::resolve_ty(ast, &fld.1, *index)?;
-                    resolved_types.push(fld_ty);
+                    resolved_types.push(Emitter::resolve_ty(ast, &fld_ty, *index)?);
                 }
 
-                self.write(OpCode::NewObject(ty.into(), resolved_types))
+                self.write(OpCode::NewObject(
+                    Emitter::resolve_info(&file.type_collection, ty)?,
+                    resolved_types,
+                ))
             }
 
-            Expression::GetField { on, ident } => {
-                self.visit(on, ast)?;
+            Expression::New { args, .. } => {
+                let ty = ast.get_type(index).map_err(|_| EmitError::CantResolveType)?;
+                let (opcode, params) = Emitter::ctor_ref(ast, index, &ty, args)?;
+
+                self.write_spills(ast, index)?;
+
+                for (arg, param) in args.iter().zip(&params) {
+                    self.visit_argument(ast, arg, param)?;
+                }
+
+                self.write(opcode);
+            }
 
+            Expression::GetField { on, ident } => {
                 let file = ast
                     .get_arp_file_in_parent(index.as_weak())
                     .ok_or(EmitError::ArpFileNotFound)?;
                 let ty = ast.get_type(on).map_err(|_| EmitError::CantResolveType)?;
-                let on_ty = file
-                    .type_collection
-                    .try_get_strong(&ty)
+
+                // Enum members are constants, and an enum value already is its underlying integer.
+                if file.type_collection.is_enum(&ty) {
+                    match ast.get(on) {
+                        Expression::Type(_) => self.write(OpCode::LoadInt(
+                            file.type_collection.get_enum_value(&ty, ident.as_ref()).ok_or(EmitError::CantResolveField)?,
+                        )),
+                        _ => self.visit(on, ast)?,
+                    }
+
+                    return Ok(());
+                }
+
+                // `len` is the only field of an array.
+                if file.type_collection.get_array_element(&ty).is_some() {
+                    self.visit(on, ast)?;
+                    self.write(OpCode::LoadLength);
+                    self.write(OpCode::ConvertToInt32);
+
+                    return Ok(());
+                }
+
+                // Static methods used as values.
+                if let Expression::Type(_) = ast.get(on) {
+                    if let Some(func) = ast.get_function_value(index, Some(&ty), ident) {
+                        let owner = file.type_collection.try_get_strong(&ty).ok_or(EmitError::CantResolveType)?;
+                        return self.write_function_value(ast, index, &func, Some(Emitter::type_spec(&file.type_collection, owner)?));
+                    }
+                }
+
+                // `Math.PI` is inlined, `String.Empty` is loaded from its static field.
+                if let Expression::Type(_) = ast.get(on) {
+                    if let Some(fld) = file.type_collection.get_static_field(&ty, ident.as_ref()) {
+                        return self.write_static_field(ast, index, &ty, ident, fld);
+                    }
+                }
+
+                // Properties are read through their getter, `Console.Out` calls `get_Out`.
+                if let Some(property) = Emitter::find_property(ast, on, ident)? {
+                    if let Receiver::Value | Receiver::Base = Receiver::of(ast, on) {
+                        self.visit_receiver(ast, on)?;
+                    }
+
+                    self.write(Emitter::accessor_opcode(ast, index, on, &property.getter(), &[])?);
+                    return Ok(());
+                }
+
+                self.visit_receiver(ast, on)?;
+                let (owner, fld_ty, fld_name) = Emitter::field_ref(&file.type_collection, &ty, ident)?;
+
+                self.write(OpCode::GetField(fld_ty, owner, fld_name))
+            }
+
+            Expression::This(ty) | Expression::Base(ty) => {
+                self.write(OpCode::LoadArgument(0));
+
+                // `this` of a struct method is an address, used as a value it is copied.
+                if Emitter::is_value_type(ast, index)? {
+                    self.write(OpCode::LoadObject(Emitter::resolve_ty(ast, ty, *index)?));
+                }
+            }
+
+            Expression::Match { on, arms } => {
+                self.visit(on, ast)?;
+
+                let temporary = Emitter::temporary_register(ast, index)?;
+                self.write(OpCode::StoreLocalVariable(temporary));
+
+                let end_label = self.next_label();
+
+                for (arm_index, arm) in arms.iter().enumerate() {
+                    // Validation guarantees exhaustiveness, so the last arm needs no test.
+                    let is_last = arm_index + 1 == arms.len();
+                    let next_arm_label = self.next_label();
+
+                    match &arm.pattern {
+                        Pattern::Variant { ty, fields } => {
+                            let variant = ast
+                                .resolve_type(ty.clone(), &index.as_weak())
+                                .ok_or(EmitError::CantResolveType)?;
+
+                            if !is_last {
+                                self.write(OpCode::LoadLocalVariable(temporary));
+                                self.write(OpCode::IsInstance(variant.full_name.to_string()));
+                                self.write(OpCode::BranchIfFalse(next_arm_label.clone()));
+                            }
+
+                            for (field, binding) in fields.iter().filter(|(_, binding)| binding.as_ref() != "_") {
+                                let (_, fld_ty) = variant
+                                    .fields
+                                    .iter()
+                                    .find(|(ident, _)| ident == field)
+                                    .ok_or(EmitError::CantResolveField)?;
+
+                                self.write(OpCode::LoadLocalVariable(temporary));
+                                self.write(OpCode::CastClass(variant.full_name.to_string()));
+                                self.write(OpCode::GetField(
+                                    Emitter::resolve_ty(ast, fld_ty, *index)?,
+                                    variant.full_name.to_string(),
+                                    field.0.to_string(),
+                                ));
+                                self.write_store(ast, binding, arm.expr.as_weak())?;
+                            }
+                        }
+                        Pattern::Binding(binding) => {
+                            self.write(OpCode::LoadLocalVariable(temporary));
+                            self.write_store(ast, binding, arm.expr.as_weak())?;
+                        }
+                        Pattern::Wildcard => {}
+                    }
+
+                    self.visit(&arm.expr, ast)?;
+                    self.write(OpCode::BranchTo(end_label.clone()));
+
+                    if !is_last {
+                        self.write_labeled_opcode(OpCode::NoOperation, next_arm_label);
+                    }
+                }
+
+                self.write_labeled_opcode(OpCode::NoOperation, end_label);
+            }
+
+            Expression::Cast { expr, .. } => {
+                self.visit(expr, ast)?;
+
+                let collection = &ast
+                    .get_arp_file_in_parent(index.as_weak())
+                    .ok_or(EmitError::ArpFileNotFound)?
+                    .type_collection;
+                let from = ast.get_type(expr).map_err(|_| EmitError::CantResolveType)?;
+                let to = ast.get_type(index).map_err(|_| EmitError::CantResolveType)?;
+
+                match collection.explicit_conversion(&from, &to).ok_or(EmitError::CantResolveType)? {
+                    CastKind::Identity | CastKind::Enum | CastKind::Upcast => {}
+                    CastKind::Numeric if to.is_same(&collection.get_int()) => self.write(OpCode::ConvertToInt32),
+                    CastKind::Numeric if to.is_same(&collection.get_float()) => self.write(OpCode::ConvertToFloat32),
+                    CastKind::Numeric => self.write(OpCode::ConvertToFloat64),
+                    CastKind::Box => self.write(OpCode::Box(Emitter::resolve_ty(ast, &from, *index)?)),
+                    CastKind::Downcast => self.write(OpCode::CastClass(Emitter::resolve_ty(ast, &to, *index)?.0)),
+                    CastKind::Unbox => self.write(OpCode::UnboxAny(Emitter::resolve_ty(ast, &to, *index)?)),
+                }
+            }
+
+            Expression::Try { expr } => {
+                self.visit(expr, ast)?;
+
+                let temporary = Emitter::temporary_register(ast, index)?;
+                self.write(OpCode::StoreLocalVariable(temporary));
+
+                let collection = &ast
+                    .get_arp_file_in_parent(index.as_weak())
+                    .ok_or(EmitError::ArpFileNotFound)?
+                    .type_collection;
+
+                let ty = ast.get_type(expr).map_err(|_| EmitError::CantResolveType)?;
+                let return_type = ast
+                    .get_parent_of_kind::<Function, _>(*index)
+                    .map(|func| ast.get(&func).return_type.clone())
                     .ok_or(EmitError::CantResolveType)?;
-                let (_, fld_ty_id) = on_ty
-                    .fields
-                    .iter()
-                    .find(|fld| fld.0 == *ident)
-                    .ok_or(EmitError::CantResolveField)?;
 
-                let fld_ty = Emitter::resolve_ty(ast, fld_ty_id, *index)?;
+                let (value_variant, residual_variant) = match collection.get_prelude(&ty) {
+                    Some(PreludeType::Option) => ("Some", "None"),
+                    Some(PreludeType::Result) => ("Ok", "Err"),
+                    None => return Err(EmitError::CantResolveType),
+                };
+
+                let variant = |ty: &TypeId, name: &str| {
+                    collection
+                        .get_variant(ty, name)
+                        .and_then(|variant| collection.try_get_strong(&variant))
+                        .ok_or(EmitError::CantResolveType)
+                };
+
+                let value = variant(&ty, value_variant)?;
+                let residual = variant(&ty, residual_variant)?;
+                let returned = variant(&return_type, residual_variant)?;
+
+                let continue_label = self.next_label();
+
+                self.write(OpCode::LoadLocalVariable(temporary));
+                self.write(OpCode::IsInstance(value.full_name.to_string()));
+                self.write(OpCode::BranchIfTrue(continue_label.clone()));
+
+                // `None` or `Err` is re-wrapped into the function's own return type.
+                let mut resolved_types = vec![];
+                for (fld, fld_ty) in &residual.fields {
+                    let fld_ty = Emitter::resolve_ty(ast, fld_ty, *index)?;
+
+                    self.write(OpCode::LoadLocalVariable(temporary));
+                    self.write(OpCode::CastClass(residual.full_name.to_string()));
+                    self.write(OpCode::GetField(fld_ty.clone(), residual.full_name.to_string(), fld.0.to_string()));
 
+                    resolved_types.push(fld_ty);
+                }
+
+                self.write(OpCode::NewObject(returned.into(), resolved_types));
+                self.write(OpCode::Return);
+
+                let (fld, fld_ty) = value.fields.first().ok_or(EmitError::CantResolveField)?;
+
+                self.write_labeled_opcode(OpCode::LoadLocalVariable(temporary), continue_label);
+                self.write(OpCode::CastClass(value.full_name.to_string()));
                 self.write(OpCode::GetField(
-                    fld_ty,
-                    on_ty.full_name.to_string(),
-                    ident.0.to_string(),
-                ))
+                    Emitter::resolve_ty(ast, fld_ty, *index)?,
+                    value.full_name.to_string(),
+                    fld.0.to_string(),
+                ));
+            }
+
+            // Arguments of a `params` array may still need boxing, `Console.WriteLine("{0}", 1)`.
+            Expression::Array(elements) | Expression::ParamsArray { elements, .. } => {
+                let element = Emitter::element_type(ast, index)?;
+                let element_ty = ast
+                    .get_type(index)
+                    .ok()
+                    .and_then(|ty| ast.get_arp_file_in_parent(index.as_weak())?.type_collection.get_array_element(&ty))
+                    .ok_or(EmitError::CantResolveType)?;
+
+                self.write(OpCode::LoadInt(elements.len() as i64));
+                self.write(OpCode::NewArray(element.clone()));
+
+                for (position, expr) in elements.iter().enumerate() {
+                    self.write(OpCode::Duplicate);
+                    self.write(OpCode::LoadInt(position as i64));
+                    self.visit_converted(ast, expr, &element_ty)?;
+                    self.write(OpCode::StoreElement(element.clone()));
+                }
+            }
+
+            Expression::Index { on, index: position } => {
+                self.visit(on, ast)?;
+                self.visit(position, ast)?;
+
+                let element = Emitter::element_type(ast, on)?;
+                self.write(OpCode::LoadElement(element));
+            }
+
+            Expression::Tuple(elements) => {
+                for expr in elements {
+                    self.visit(expr, ast)?;
+                }
+
+                let ty = Emitter::resolve_ty(ast, &ast.get_type(index).map_err(|_| EmitError::CantResolveType)?, *index)?;
+                let params = (0..elements.len()).map(|position| ResolvedType(format!("!{position}"))).collect();
+
+                self.write(OpCode::NewObject(ty, params));
             }
 
-            Expression::This(_) => self.write(OpCode::LoadArgument(0)),
+            Expression::Lambda { func, captures } => {
+                let closure = Identifier::closure(index).0.to_string();
+
+                // Captured variables are copied into the closure when the lambda is created.
+                let mut capture_types = vec![];
+                for (ident, ty) in captures {
+                    self.write_load(ast, ident, index)?;
+                    capture_types.push(Emitter::resolve_ty(ast, ty, *index)?);
+                }
+
+                self.write(OpCode::NewObject(ResolvedType(closure.clone()), capture_types));
+
+                let invoke = ast.get(func);
+                self.write(OpCode::LoadFunction {
+                    is_instance: true,
+                    is_virtual: false,
+                    return_type: Emitter::resolve_ty(ast, &invoke.return_type, *func)?,
+                    external: None,
+                    ty: closure,
+                    method_name: invoke.name.0.to_string(),
+                    args: Emitter::param_types(ast, func)?,
+                });
+
+                let ty = ast.get_type(index).map_err(|_| EmitError::CantResolveType)?;
+                self.write(OpCode::NewObject(Emitter::resolve_ty(ast, &ty, *index)?, Emitter::delegate_ctor_params()));
+            }
 
             val => unreachable!("Reached {:?}", val),
             // Expression::Type(_) => todo!(),
@@ -261,3 +548,565 @@ impl Visitor<Expression> for Emitter {
         Ok(())
     }
 }
+
+impl Emitter {
+    /// Calls reference the declared signature, so generic members keep their open types (`!0`, `!!0`)
+    /// while the owner and method instantiation carry the arguments.
+    fn call_opcode(
+        ast: &Ast,
+        index: &Id<Expression>,
+        on_ty: &TypeId,
+        method: &Identifier,
+        args: &[Id<Expression>],
+        receiver: Receiver,
+    ) -> Result<OpCode, EmitError> {
+        let collection = &ast
+            .get_arp_file_in_parent(index.as_weak())
+            .ok_or(EmitError::ArpFileNotFound)?
+            .type_collection;
+
+        let args = args
+            .iter()
+            .map(|index| ast.get_type(index))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| EmitError::CantResolveType)?;
+
+        // Methods of type parameters belong to a bound, inherited ones to a base class and default ones to a trait.
+        let (owner, method_info, bindings) = collection
+            .find_method_with_owner(on_ty, method, &args)
+            .ok_or(EmitError::CantResolveMethod)?;
+        let type_info = collection.try_get_strong(&owner).ok_or(EmitError::CantResolveType)?;
+
+        // Managed generic instances take the open signature from their definition.
+        let open_method = match (&type_info.kind, &method_info.definition) {
+            (TypeKind::Instance { definition, .. }, None) => type_info
+                .methods
+                .iter()
+                .position(|mi| std::ptr::eq(mi, method_info))
+                .and_then(|position| collection.try_get_strong(definition)?.methods.get(position))
+                .ok_or(EmitError::CantResolveMethod)?,
+            _ => method_info,
+        };
+
+        let (return_type, arg_types) = match &method_info.definition {
+            Some(definition) => {
+                let func = ast.get(definition);
+                (
+                    Emitter::resolve_ty(ast, &func.return_type, *definition)?,
+                    func.parameters
+                        .iter()
+                        .map(|(_, ty)| Emitter::resolve_ty(ast, ty, *definition))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )
+            }
+            None => (
+                Emitter::resolve_ty(ast, &open_method.return_type, *index)?,
+                open_method
+                    .args
+                    .iter()
+                    .map(|(_, ty)| Emitter::resolve_ty(ast, ty, *index))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+        };
+
+        let method_name = match bindings.is_empty() {
+            true => method_info.name.0.to_string(),
+            false => format!(
+                "{}<{}>",
+                method_info.name.0,
+                bindings
+                    .iter()
+                    .map(|ty| Emitter::resolve_ty(ast, ty, *index).map(|ty| ty.0))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join(",")
+            ),
+        };
+
+        // Extension methods are static methods of the extension class taking the receiver first.
+        if let Some((definition, extended)) = method_info.definition.and_then(|definition| Some((definition, ast.get_extended_type(&definition)?))) {
+            let mut args = arg_types;
+
+            if let FunctionKind::Method { .. } = ast.get(&definition).kind {
+                args.insert(0, Emitter::extension_receiver(collection, &extended)?);
+            }
+
+            return Ok(OpCode::Call {
+                is_instance: false,
+                is_virtual: false,
+                return_type,
+                external: None,
+                ty: Emitter::extension_class(collection, &extended)?,
+                method_name,
+                args,
+            });
+        }
+
+        // Methods of reference types are called virtually, which also checks the receiver for null.
+        // `base.show()` has to call the base implementation itself.
+        Ok(OpCode::Call {
+            is_instance: receiver != Receiver::Type,
+            is_virtual: receiver == Receiver::Value && !type_info.is_value_type,
+            return_type,
+            external: Emitter::external_assembly(type_info),
+            ty: Emitter::type_spec(collection, type_info)?,
+            method_name,
+            args: arg_types,
+        })
+    }
+
+    /// Parameter types of the method a call resolves to, with the arguments of generic instances filled in.
+    fn call_params(
+        ast: &Ast,
+        index: &Id<Expression>,
+        on_ty: &TypeId,
+        method: &Identifier,
+        args: &[Id<Expression>],
+    ) -> Result<Vec<TypeId>, EmitError> {
+        let collection = &ast
+            .get_arp_file_in_parent(index.as_weak())
+            .ok_or(EmitError::ArpFileNotFound)?
+            .type_collection;
+
+        let args = args
+            .iter()
+            .map(|index| ast.get_type(index))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| EmitError::CantResolveType)?;
+
+        let (_, method_info, _) = collection
+            .find_method_with_owner(on_ty, method, &args)
+            .ok_or(EmitError::CantResolveMethod)?;
+
+        Ok(method_info.args.iter().map(|(_, ty)| ty.clone()).collect())
+    }
+
+    /// Property `ident` of `on` which isn't shadowed by a field, static ones are read from types and the others from values.
+    pub(crate) fn find_property(ast: &Ast, on: &Id<Expression>, ident: &Identifier) -> Result<Option<PropertyInfo>, EmitError> {
+        let collection = &ast
+            .get_arp_file_in_parent(on.as_weak())
+            .ok_or(EmitError::ArpFileNotFound)?
+            .type_collection;
+        let ty = ast.get_type(on).map_err(|_| EmitError::CantResolveType)?;
+
+        if collection.find_field(&ty, ident.as_ref()).is_some() {
+            return Ok(None);
+        }
+
+        let is_static = Receiver::of(ast, on) == Receiver::Type;
+        Ok(collection.find_property(&ty, ident.as_ref()).filter(|property| property.is_static == is_static).cloned())
+    }
+
+    /// Call of the property accessor `accessor` on `on`, with the value passed to a setter as `args`.
+    pub(crate) fn accessor_opcode(ast: &Ast, index: &Id<Expression>, on: &Id<Expression>, accessor: &Identifier, args: &[Id<Expression>]) -> Result<OpCode, EmitError> {
+        let ty = ast.get_type(on).map_err(|_| EmitError::CantResolveType)?;
+
+        Emitter::call_opcode(ast, index, &ty, accessor, args, Receiver::of(ast, on))
+    }
+
+    /// A literal field is inlined, other static fields are loaded.
+    fn write_static_field(&mut self, ast: &Ast, index: &Id<Expression>, ty: &TypeId, ident: &Identifier, fld: &StaticFieldInfo) -> Result<(), EmitError> {
+        let collection = &ast
+            .get_arp_file_in_parent(index.as_weak())
+            .ok_or(EmitError::ArpFileNotFound)?
+            .type_collection;
+
+        match &fld.constant {
+            Some(Literal::Integer(value)) => self.write(OpCode::LoadInt(*value)),
+            Some(Literal::Float(value)) if fld.ty.is_same(&collection.get_double()) => self.write(OpCode::LoadDouble(*value)),
+            Some(Literal::Float(value)) => self.write(OpCode::LoadFloat(*value)),
+            Some(Literal::String(value)) => self.write(OpCode::LoadString(value.to_string())),
+            Some(Literal::Bool(value)) => self.write(OpCode::LoadBool(*value)),
+            None => {
+                let (owner, fld_ty, fld_name) = Emitter::static_field_ref(collection, ty, ident)?;
+                self.write(OpCode::GetStaticField(fld_ty, owner, fld_name));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `newobj` of the constructor `new ty(args)` resolves to, and the parameters the arguments are converted to.
+    /// Constructors of managed generic instances are referenced with the open parameters of their definition, `!0`.
+    fn ctor_ref(ast: &Ast, index: &Id<Expression>, ty: &TypeId, args: &[Id<Expression>]) -> Result<(OpCode, Vec<TypeId>), EmitError> {
+        let collection = &ast
+            .get_arp_file_in_parent(index.as_weak())
+            .ok_or(EmitError::ArpFileNotFound)?
+            .type_collection;
+
+        let arg_types = args
+            .iter()
+            .map(|index| ast.get_type(index))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| EmitError::CantResolveType)?;
+
+        let Overload::Found(_, ctor, _) = collection.resolve_constructor(ty, &[], &arg_types) else {
+            return Err(EmitError::CantResolveMethod);
+        };
+        let type_info = collection.try_get_strong(ty).ok_or(EmitError::CantResolveType)?;
+
+        let open_ctor = match &type_info.kind {
+            TypeKind::Instance { definition, .. } => type_info
+                .methods
+                .iter()
+                .position(|mi| std::ptr::eq(mi, ctor))
+                .and_then(|position| collection.try_get_strong(definition)?.methods.get(position))
+                .ok_or(EmitError::CantResolveMethod)?,
+            _ => ctor,
+        };
+
+        let params = open_ctor
+            .args
+            .iter()
+            .map(|(_, ty)| Emitter::resolve_ty(ast, ty, *index))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let owner = Emitter::member_owner(collection, type_info)?;
+
+        Ok((OpCode::NewObject(ResolvedType(owner), params), ctor.args.iter().map(|(_, ty)| ty.clone()).collect()))
+    }
+
+    fn param_types(ast: &Ast, func: &Id<Function>) -> Result<Vec<ResolvedType>, EmitError> {
+        ast.get(func)
+            .parameters
+            .iter()
+            .map(|(_, ty)| Emitter::resolve_ty(ast, ty, *func))
+            .collect()
+    }
+
+    /// Delegates are constructed from a target object and a method pointer.
+    fn delegate_ctor_params() -> Vec<ResolvedType> {
+        vec![ResolvedType("object".to_string()), ResolvedType("native int".to_string())]
+    }
+
+    /// Visits `expr` stored where a `target` is expected, converting its value on the way.
+    pub(crate) fn visit_converted(&mut self, ast: &Ast, expr: &Id<Expression>, target: &TypeId) -> Result<(), EmitError> {
+        self.visit(expr, ast)?;
+        self.write_implicit_conversion(ast, expr, target)
+    }
+
+    /// The value of `expr` is on the stack. Numbers passed for wider parameters are converted,
+    /// value types flowing into `object` or a trait are boxed.
+    fn write_implicit_conversion(&mut self, ast: &Ast, expr: &Id<Expression>, target: &TypeId) -> Result<(), EmitError> {
+        let collection = &ast
+            .get_arp_file_in_parent(expr.as_weak())
+            .ok_or(EmitError::ArpFileNotFound)?
+            .type_collection;
+        let ty = ast.get_type(expr).map_err(|_| EmitError::CantResolveType)?;
+
+        match collection.implicit_conversion(&ty, target) {
+            Some(Conversion::Widening(_)) if target.is_same(&collection.get_float()) => self.write(OpCode::ConvertToFloat32),
+            Some(Conversion::Widening(_)) => self.write(OpCode::ConvertToFloat64),
+            Some(Conversion::Boxing) => self.write(OpCode::Box(Emitter::resolve_ty(ast, &ty, *expr)?)),
+            _ => {},
+        }
+
+        Ok(())
+    }
+
+    /// Arguments of `call` which were put out of source order are evaluated first, in source order, into hidden locals.
+    /// Passing them afterwards loads them back.
+    fn write_spills(&mut self, ast: &Ast, call: &Id<Expression>) -> Result<(), EmitError> {
+        let (Expression::Call { spilled, .. } | Expression::New { spilled, .. }) = ast.get(call) else { return Ok(()) };
+
+        for arg in spilled {
+            self.visit(arg, ast)?;
+            self.write(OpCode::StoreLocalVariable(Emitter::temporary_register(ast, arg)?));
+            self.spilled.push(*arg);
+        }
+
+        Ok(())
+    }
+
+    /// A function value passed where a managed delegate is expected is wrapped into one invoking it.
+    fn visit_argument(&mut self, ast: &Ast, arg: &Id<Expression>, param: &TypeId) -> Result<(), EmitError> {
+        self.visit_converted(ast, arg, param)?;
+
+        let collection = &ast
+            .get_arp_file_in_parent(arg.as_weak())
+            .ok_or(EmitError::ArpFileNotFound)?
+            .type_collection;
+        let arg_ty = ast.get_type(arg).map_err(|_| EmitError::CantResolveType)?;
+
+        if !collection.is_delegate(param) || collection.get_function_signature(&arg_ty).is_none() {
+            return Ok(());
+        }
+
+        let (return_type, ty, args) = Emitter::invoke_ref(collection, &arg_ty)?;
+
+        self.write(OpCode::Duplicate);
+        self.write(OpCode::LoadFunction {
+            is_instance: true,
+            is_virtual: true,
+            return_type,
+            external: None,
+            ty,
+            method_name: "Invoke".to_string(),
+            args,
+        });
+        self.write(OpCode::NewObject(Emitter::resolve_ty(ast, param, *arg)?, Emitter::delegate_ctor_params()));
+
+        Ok(())
+    }
+
+    /// Static method `func` of `owner`, or a top level function, as a delegate without a target.
+    fn write_function_value(&mut self, ast: &Ast, index: &Id<Expression>, func: &Id<Function>, owner: Option<String>) -> Result<(), EmitError> {
+        let definition = ast.get(func);
+
+        self.write(OpCode::LoadNull);
+        self.write(OpCode::LoadFunction {
+            is_instance: false,
+            is_virtual: false,
+            return_type: Emitter::resolve_ty(ast, &definition.return_type, *func)?,
+            external: None,
+            ty: owner.unwrap_or_default(),
+            method_name: definition.name.0.to_string(),
+            args: Emitter::param_types(ast, func)?,
+        });
+
+        let ty = ast.get_type(index).map_err(|_| EmitError::CantResolveType)?;
+        self.write(OpCode::NewObject(Emitter::resolve_ty(ast, &ty, *index)?, Emitter::delegate_ctor_params()));
+
+        Ok(())
+    }
+
+    /// Field of the closure class holding the captured `ident`, `this` of `Invoke` is the closure.
+    fn capture_field(ast: &Ast, lambda: &Id<Expression>, ident: &Identifier) -> Result<(ResolvedType, String, String), EmitError> {
+        let Expression::Lambda { captures, .. } = ast.get(lambda) else { return Err(EmitError::VariableHasNoSource) };
+        let (_, ty) = captures.iter().find(|(captured, _)| captured == ident).ok_or(EmitError::VariableHasNoSource)?;
+
+        Ok((Emitter::resolve_ty(ast, ty, *lambda)?, Identifier::closure(lambda).0.to_string(), ident.0.to_string()))
+    }
+
+    fn write_load(&mut self, ast: &Ast, ident: &Identifier, scope: &Id<Expression>) -> Result<(), EmitError> {
+        match ast
+            .get_identifier_source(ident, &scope.as_weak())
+            .ok_or(EmitError::VariableHasNoSource)?
+        {
+            VariableSource::Local(loc) => self.write(OpCode::LoadLocalVariable(loc)),
+            VariableSource::Argument(loc) => self.write(OpCode::LoadArgument(loc)),
+            VariableSource::Captured(lambda) => {
+                let (fld_ty, owner, fld_name) = Emitter::capture_field(ast, &lambda, ident)?;
+
+                self.write(OpCode::LoadArgument(0));
+                self.write(OpCode::GetField(fld_ty, owner, fld_name));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_box_parameter(&mut self, ast: &Ast, index: &Id<Expression>, on: &Id<Expression>) -> Result<(), EmitError> {
+        let ty = ast.get_type(on).map_err(|_| EmitError::CantResolveType)?;
+
+        if let Some(TypeKind::Parameter { .. }) = ast
+            .get_arp_file_in_parent(index.as_weak())
+            .and_then(|file| file.type_collection.try_get_strong(&ty))
+            .map(|info| &info.kind)
+        {
+            self.write(OpCode::Box(Emitter::resolve_ty(ast, &ty, *index)?));
+        }
+
+        Ok(())
+    }
+
+    /// Both operands are on the stack already. `eq` results are negated for `!=`,
+    /// `cmp` results are compared with zero.
+    fn write_operator_overload(
+        &mut self,
+        ast: &Ast,
+        index: &Id<Expression>,
+        lhs: Id<Expression>,
+        op: BinaryOperator,
+        rhs: Id<Expression>,
+        overload: OperatorOverload,
+    ) -> Result<(), EmitError> {
+        match overload {
+            OperatorOverload::Method(method) => {
+                let lty = ast.get_type(&lhs).map_err(|_| EmitError::CantResolveType)?;
+                self.write(Emitter::call_opcode(ast, index, &lty, &method, &[rhs], Receiver::Value)?);
+
+                match (method.as_ref(), op) {
+                    ("eq", BinaryOperator::NotEqual) => {
+                        self.write(OpCode::LoadBool(false));
+                        self.write(OpCode::Equal);
+                    }
+                    ("cmp", _) => {
+                        self.write(OpCode::LoadInt(0));
+
+                        match op {
+                            BinaryOperator::Less => self.write(OpCode::LessThen),
+                            BinaryOperator::Greater => self.write(OpCode::GreaterThen),
+                            BinaryOperator::LessOrEqual => {
+                                self.write(OpCode::GreaterThen);
+                                self.write(OpCode::LoadBool(false));
+                                self.write(OpCode::Equal);
+                            }
+                            _ => {
+                                self.write(OpCode::LessThen);
+                                self.write(OpCode::LoadBool(false));
+                                self.write(OpCode::Equal);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            OperatorOverload::Managed(owner, method) => {
+                self.write(Emitter::call_opcode(ast, index, &owner, &method, &[lhs, rhs], Receiver::Type)?);
+            }
+            OperatorOverload::Concat => {
+                let string = Emitter::resolve_ty(ast, &ast.get_type(index).map_err(|_| EmitError::CantResolveType)?, *index)?;
+
+                self.write(OpCode::Call {
+                    is_instance: false,
+                    is_virtual: false,
+                    return_type: string.clone(),
+                    external: Some(RUNTIME_ASSEMBLY.to_string()),
+                    ty: "System.String".to_string(),
+                    method_name: "Concat".to_string(),
+                    args: vec![string.clone(), string],
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_value_type(ast: &Ast, expr: &Id<Expression>) -> Result<bool, EmitError> {
+        let collection = &ast
+            .get_arp_file_in_parent(expr.as_weak())
+            .ok_or(EmitError::ArpFileNotFound)?
+            .type_collection;
+
+        Ok(collection.is_value_type(&ast.get_type(expr).map_err(|_| EmitError::CantResolveType)?))
+    }
+
+    /// Fields and methods of structs are reached through the address of the value, anything else is loaded as is.
+    pub(crate) fn visit_receiver(&mut self, ast: &Ast, on: &Id<Expression>) -> Result<(), EmitError> {
+        match Emitter::is_value_type(ast, on)? {
+            true => self.visit_address(ast, on),
+            false => self.visit(on, ast),
+        }
+    }
+
+    /// Values without a storage location of their own are spilled into a hidden local first.
+    fn visit_address(&mut self, ast: &Ast, expr: &Id<Expression>) -> Result<(), EmitError> {
+        match ast.get(expr) {
+            Expression::Variable(ident) => match ast
+                .get_identifier_source(ident, &expr.as_weak())
+                .ok_or(EmitError::VariableHasNoSource)?
+            {
+                VariableSource::Local(register_index) => self.write(OpCode::LoadLocalAddress(register_index)),
+                VariableSource::Argument(arg_index) => self.write(OpCode::LoadArgumentAddress(arg_index)),
+                VariableSource::Captured(lambda) => {
+                    let (fld_ty, owner, fld_name) = Emitter::capture_field(ast, &lambda, ident)?;
+
+                    self.write(OpCode::LoadArgument(0));
+                    self.write(OpCode::LoadFieldAddress(fld_ty, owner, fld_name));
+                }
+            },
+            Expression::This(_) | Expression::Base(_) => self.write(OpCode::LoadArgument(0)),
+            Expression::Index { on, index } => {
+                self.visit(on, ast)?;
+                self.visit(index, ast)?;
+
+                let element = Emitter::element_type(ast, on)?;
+                self.write(OpCode::LoadElementAddress(element));
+            }
+            Expression::GetField { on, ident } if Emitter::has_field(ast, on, ident) => {
+                self.visit_receiver(ast, on)?;
+
+                let collection = &ast
+                    .get_arp_file_in_parent(expr.as_weak())
+                    .ok_or(EmitError::ArpFileNotFound)?
+                    .type_collection;
+                let ty = ast.get_type(on).map_err(|_| EmitError::CantResolveType)?;
+                let (owner, fld_ty, fld_name) = Emitter::field_ref(collection, &ty, ident)?;
+
+                self.write(OpCode::LoadFieldAddress(fld_ty, owner, fld_name))
+            }
+            _ => {
+                self.visit(expr, ast)?;
+
+                let spilled = match ast
+                    .get_identifier_source(&Identifier::spilled(expr), &expr.as_weak())
+                    .ok_or(EmitError::VariableHasNoSource)?
+                {
+                    VariableSource::Local(register_index) => register_index,
+                    VariableSource::Argument(_) | VariableSource::Captured(_) => return Err(EmitError::VariableHasNoSource),
+                };
+
+                self.write(OpCode::StoreLocalVariable(spilled));
+                self.write(OpCode::LoadLocalAddress(spilled));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether `on.ident` is a field with an address, properties and static fields are read into temporaries.
+    fn has_field(ast: &Ast, on: &Id<Expression>, ident: &Identifier) -> bool {
+        let ty = ast.get_type(on).unwrap_or_default();
+
+        ast.get_arp_file_in_parent(on.as_weak())
+            .is_some_and(|file| file.type_collection.find_field(&ty, ident.as_ref()).is_some())
+    }
+
+    /// Element type of the array `expr` evaluates to.
+    pub(crate) fn element_type(ast: &Ast, expr: &Id<Expression>) -> Result<ResolvedType, EmitError> {
+        let collection = &ast
+            .get_arp_file_in_parent(expr.as_weak())
+            .ok_or(EmitError::ArpFileNotFound)?
+            .type_collection;
+
+        let ty = ast.get_type(expr).map_err(|_| EmitError::CantResolveType)?;
+        let element = collection.get_array_element(&ty).ok_or(EmitError::CantResolveType)?;
+
+        Emitter::resolve_ty(ast, &element, *expr)
+    }
+
+    fn temporary_register(ast: &Ast, index: &Id<Expression>) -> Result<usize, EmitError> {
+        match ast
+            .get_identifier_source(&Identifier::temporary(index), &index.as_weak())
+            .ok_or(EmitError::VariableHasNoSource)?
+        {
+            VariableSource::Local(register_index) => Ok(register_index),
+            VariableSource::Argument(_) | VariableSource::Captured(_) => Err(EmitError::VariableHasNoSource),
+        }
+    }
+
+    /// Stores into `ident` as declared at `scope`, a `let` statement or the body of a match arm.
+    pub(super) fn write_store(&mut self, ast: &Ast, ident: &Identifier, scope: WId) -> Result<(), EmitError> {
+        let source = ast
+            .get_identifier_source(ident, &scope)
+            .ok_or(EmitError::VariableHasNoSource)?;
+
+        match source {
+            VariableSource::Local(register_index) => self.write(OpCode::StoreLocalVariable(register_index)),
+            VariableSource::Argument(arg_index) => self.write(OpCode::StoreArgument(arg_index)),
+            // Captures are copies, lambdas can't assign to them.
+            VariableSource::Captured(_) => return Err(EmitError::VariableHasNoSource),
+        }
+
+        Ok(())
+    }
+}
+
+/// What a method is called on. `Math.max(a, b)` is called on a type, `base.show()` calls the base
+/// implementation without dispatching on the value.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Receiver {
+    Type,
+    Value,
+    Base,
+}
+
+impl Receiver {
+    fn of(ast: &Ast, on: &Id<Expression>) -> Self {
+        match ast.get(on) {
+            Expression::Type(_) => Receiver::Type,
+            Expression::Base(_) => Receiver::Base,
+            _ => Receiver::Value,
+        }
+    }
+}
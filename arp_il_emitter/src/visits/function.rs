@@ -3,11 +3,13 @@ use std::collections::HashSet;
 use arp_ast_processor::types::{
     ast_node_value::{Ast, Id},
     function::{Function, FunctionKind},
+    implementation::Implementation,
+    type_collection::TypeKind,
 };
 
 use crate::{
-    emitter::Emitter,
-    il_token::{FunctionFlags, ILToken, Method},
+    emitter::{Emitter, RUNTIME_ASSEMBLY},
+    il_token::{FunctionFlags, ILToken, Method, OpCode, ResolvedType},
     traits::Visitor,
     EmitError,
 };
@@ -24,43 +26,72 @@ impl Visitor<Function> for Emitter {
             file.arp_path.0.to_lowercase() == "main" && func.name.0.to_lowercase() == "main"
         };
 
+        let collection = &ast
+            .get_arp_file_in_parent(index.as_weak())
+            .ok_or(EmitError::ArpFileNotFound)?
+            .type_collection;
+
+        let extended = ast.get_extended_type(index);
+
+        // Extension methods are static, an instance one takes its receiver as the first parameter.
         let mut flags = vec![
             FunctionFlags::Cil,
             FunctionFlags::Managed,
-            match func.kind {
-                FunctionKind::Method { .. } => FunctionFlags::IsStatic(false),
-                FunctionKind::Static => FunctionFlags::IsStatic(true),
+            match (&func.kind, &extended) {
+                (FunctionKind::Method { .. }, None) => FunctionFlags::IsStatic(false),
+                _ => FunctionFlags::IsStatic(true),
             },
         ];
 
+        let receiver = match (&func.kind, &extended) {
+            (FunctionKind::Method { .. }, Some(extended)) => {
+                flags.extend([FunctionFlags::Public, FunctionFlags::Extension]);
+                Some(("'this'".to_string(), Emitter::extension_receiver(collection, extended)?))
+            }
+            _ => None,
+        };
+
         if is_entrypoint {
             flags.push(FunctionFlags::EntryPoint);
         }
 
+        let overrides = Emitter::trait_method(ast, index)?;
+
+        // Default methods of a trait are virtual, implementations of trait methods override them.
+        match &overrides {
+            Some(TraitMethod::Default) => flags.extend([FunctionFlags::Public, FunctionFlags::NewSlot, FunctionFlags::Virtual]),
+            Some(TraitMethod::Implementation(_)) => flags.extend([FunctionFlags::Public, FunctionFlags::NewSlot, FunctionFlags::Virtual, FunctionFlags::Final]),
+            None => match Emitter::class_method(ast, index)? {
+                Some(ClassMethod::Introduced) => flags.extend([FunctionFlags::Public, FunctionFlags::NewSlot, FunctionFlags::Virtual]),
+                Some(ClassMethod::Override) => flags.extend([FunctionFlags::Public, FunctionFlags::Virtual]),
+                None => {},
+            },
+        }
+
+        let name = match func.generics.is_empty() {
+            true => func.name.0.to_string(),
+            false => {
+                format!("{}<{}>", func.name.0, Emitter::generic_params_decl(collection, &func.generics)?)
+            }
+        };
+
         let method = Method {
             flags: HashSet::from_iter(flags),
-            params: func
-                .parameters
-                .iter()
-                .map(|(ident, ty)| {
-                    ast.resolve_type(ty.clone(), &index.as_weak())
-                        .ok_or(EmitError::CantResolveType)
-                        .map(|ty| (ident.0.to_string(), ty.into()))
-                })
+            params: receiver
+                .into_iter()
+                .map(Ok)
+                .chain(func.parameters.iter().map(|(ident, ty)| Emitter::resolve_ty(ast, ty, *index).map(|ty| (ident.0.to_string(), ty))))
                 .collect::<Result<Vec<_>, _>>()?,
-            return_ty: ast
-                .resolve_type(func.return_type.clone(), &index.as_weak())
-                .ok_or(EmitError::CantResolveType)?
-                .into(),
-            name: func.name.0.to_string(),
+            return_ty: Emitter::resolve_ty(ast, &func.return_type, *index)?,
+            name,
+            overrides: match overrides {
+                Some(TraitMethod::Implementation(overrides)) => Some(overrides),
+                _ => None,
+            },
             registers: func
                 .registers
                 .iter()
-                .map(|(_, ty)| {
-                    ast.resolve_type(ty.clone(), &index.as_weak())
-                        .ok_or(EmitError::CantResolveType)
-                        .map(|ty| ty.into())
-                })
+                .map(|(_, ty)| Emitter::resolve_ty(ast, ty, *index))
                 .collect::<Result<Vec<_>, _>>()?,
         };
 
@@ -68,8 +99,91 @@ impl Visitor<Function> for Emitter {
 
         self.visit(&func.block, ast)?;
 
+        if ast.is_stub(index) {
+            self.write(OpCode::NewObject(ResolvedType(format!("[{RUNTIME_ASSEMBLY}]System.NotImplementedException")), vec![]));
+            self.write(OpCode::Throw);
+        }
+
         self.write(ILToken::EndMethod(func.name.0.to_string()));
 
         Ok(())
     }
 }
+
+enum TraitMethod {
+    Default,
+    /// Method of the trait being implemented, `Printable::print`.
+    Implementation(String),
+}
+
+/// Instance methods of classes are virtual, so a call through a base class reaches the method of the derived one.
+enum ClassMethod {
+    Introduced,
+    /// Has the name and parameters of a base class method, it takes over its slot.
+    Override,
+}
+
+impl Emitter {
+    fn class_method(ast: &Ast, index: &Id<Function>) -> Result<Option<ClassMethod>, EmitError> {
+        let func = ast.get(index);
+
+        if ast.get_enclosing_lambda(index).is_some() || func.kind == FunctionKind::Static || ast.get_extended_type(index).is_some() {
+            return Ok(None);
+        }
+
+        let Some(implementation) = ast.get_parent_of_kind::<Implementation, _>(index.as_weak()) else { return Ok(None) };
+        let implementation = ast.get(&implementation);
+
+        let collection = &ast
+            .get_arp_file_in_parent(index.as_weak())
+            .ok_or(EmitError::ArpFileNotFound)?
+            .type_collection;
+
+        let info = collection.try_get_strong(&implementation.impl_type).ok_or(EmitError::CantResolveType)?;
+        if implementation.trait_type.is_some() || info.is_value_type || !matches!(info.kind, TypeKind::Class | TypeKind::Generic(_)) {
+            return Ok(None);
+        }
+
+        let is_override = collection.base_chain(&implementation.impl_type).iter()
+            .filter_map(|base| collection.try_get_strong(base))
+            .flat_map(|base| &base.methods)
+            .filter(|mi| mi.definition.is_none_or(|definition| ast.get(&definition).kind != FunctionKind::Static))
+            .any(|mi| {
+                mi.name == func.name
+                    && mi.args.len() == func.parameters.len()
+                    && mi.args.iter().zip(&func.parameters).all(|((_, base_ty), (_, ty))| base_ty.is_same(ty))
+            });
+
+        Ok(Some(if is_override { ClassMethod::Override } else { ClassMethod::Introduced }))
+    }
+
+    fn trait_method(ast: &Ast, index: &Id<Function>) -> Result<Option<TraitMethod>, EmitError> {
+        // `Invoke` of a lambda belongs to its closure class, even inside an implementation.
+        if ast.get_enclosing_lambda(index).is_some() {
+            return Ok(None);
+        }
+
+        let Some(implementation) = ast.get_parent_of_kind::<Implementation, _>(index.as_weak()) else { return Ok(None) };
+        let implementation = ast.get(&implementation);
+        let func = ast.get(index);
+
+        let collection = &ast
+            .get_arp_file_in_parent(index.as_weak())
+            .ok_or(EmitError::ArpFileNotFound)?
+            .type_collection;
+
+        if collection.try_get_strong(&implementation.impl_type).is_some_and(|info| info.kind == TypeKind::Trait) {
+            return Ok(Some(TraitMethod::Default));
+        }
+
+        let Some(trait_type) = &implementation.trait_type else { return Ok(None) };
+        let trait_info = collection.try_get_strong(trait_type).ok_or(EmitError::CantResolveType)?;
+
+        Ok(trait_info.methods
+            .iter()
+            .any(|mi| mi.name == func.name)
+            .then(|| Emitter::extends_spec(collection, trait_info))
+            .transpose()?
+            .map(|spec| TraitMethod::Implementation(format!("{spec}::{}", func.name.0))))
+    }
+}
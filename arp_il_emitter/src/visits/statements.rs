@@ -1,6 +1,9 @@
 use arp_ast_processor::{
     types::{
         ast_node_value::{Ast, Id},
+        expression::Expression,
+        function::Function,
+        simple::Identifier,
         statement::{IfKind, Statement},
     },
     utils::VariableSource,
@@ -13,8 +16,8 @@ impl Visitor<Statement> for Emitter {
         match ast.get(index) {
             Statement::Expression(expr) => self.visit(expr, ast)?,
 
-            Statement::LocalVariableDeclaration { ident, expr, .. } => {
-                self.visit(expr, ast)?;
+            Statement::LocalVariableDeclaration { ident, ty, expr, .. } => {
+                self.visit_converted(ast, expr, ty)?;
 
                 let source = ast
                     .get_identifier_source(ident, &index.as_weak())
@@ -27,33 +30,74 @@ impl Visitor<Statement> for Emitter {
                     VariableSource::Argument(arg_index) => {
                         self.write(OpCode::StoreArgument(arg_index))
                     }
+                    VariableSource::Captured(_) => return Err(EmitError::VariableHasNoSource),
+                }
+            }
+            Statement::Destructure { bindings, ty, expr, .. } => {
+                self.visit(expr, ast)?;
+
+                let collection = &ast
+                    .get_arp_file_in_parent(index.as_weak())
+                    .ok_or(EmitError::ArpFileNotFound)?
+                    .type_collection;
+
+                for (position, binding) in bindings.iter().enumerate().filter(|(_, binding)| binding.as_ref() != "_") {
+                    let (owner, fld_ty, fld_name) = Emitter::field_ref(collection, ty, &Identifier::from(position.to_string().as_str()))?;
+
+                    self.write(OpCode::Duplicate);
+                    self.write(OpCode::GetField(fld_ty, owner, fld_name));
+                    self.write_store(ast, binding, index.as_weak())?;
                 }
+
+                self.write(OpCode::Pop);
             }
             Statement::Block(bs) => self.visit(bs, ast)?,
 
             Statement::Assignment { on, field, expr } => {
                 if let Some(on) = on {
-                    self.visit(on, ast)?;
-                    self.visit(expr, ast)?;
-
-                    let expr_ty = Emitter::resolve_ty(
-                        ast,
-                        &ast.get_type(expr).map_err(|_| EmitError::CantResolveType)?,
-                        *index,
-                    )?;
-                    let ty = Emitter::resolve_ty(
-                        ast,
-                        &ast.get_type(on).map_err(|_| EmitError::CantResolveType)?,
-                        *index,
-                    )?;
-
-                    self.write(OpCode::SetField(
-                        expr_ty,
-                        ty.0.to_string(),
-                        field.0.to_string(),
-                    ))
+                    let on_ty = ast.get_type(on).map_err(|_| EmitError::CantResolveType)?;
+                    let collection = &ast
+                        .get_arp_file_in_parent(index.as_weak())
+                        .ok_or(EmitError::ArpFileNotFound)?
+                        .type_collection;
+
+                    // `Console.ForegroundColor = color` calls the setter, static fields of managed types are stored directly.
+                    if let (Expression::Type(_), Some(fld)) = (ast.get(on), collection.get_static_field(&on_ty, field.as_ref())) {
+                        self.visit_converted(ast, expr, &fld.ty)?;
+
+                        let (owner, fld_ty, fld_name) = Emitter::static_field_ref(collection, &on_ty, field)?;
+                        self.write(OpCode::SetStaticField(fld_ty, owner, fld_name));
+
+                        return Ok(());
+                    }
+
+                    if let Some(property) = Emitter::find_property(ast, on, field)? {
+                        if !matches!(ast.get(on), Expression::Type(_)) {
+                            self.visit_receiver(ast, on)?;
+                        }
+
+                        self.visit_converted(ast, expr, &property.ty)?;
+                        self.write(Emitter::accessor_opcode(ast, expr, on, &property.setter(), &[*expr])?);
+
+                        return Ok(());
+                    }
+
+                    self.visit_receiver(ast, on)?;
+
+                    let target = collection.get_field_type(&on_ty, field.as_ref()).ok_or(EmitError::CantResolveField)?;
+                    self.visit_converted(ast, expr, &target)?;
+
+                    let (owner, fld_ty, fld_name) = Emitter::field_ref(collection, &on_ty, field)?;
+
+                    self.write(OpCode::SetField(fld_ty, owner, fld_name))
                 } else {
-                    self.visit(expr, ast)?;
+                    let target = ast
+                        .lookup_binding(index.as_weak(), field)
+                        .map(|binding| ast.get_binding_type(&binding, field))
+                        .ok_or(EmitError::VariableHasNoSource)?
+                        .map_err(|_| EmitError::CantResolveType)?;
+
+                    self.visit_converted(ast, expr, &target)?;
 
                     let source = ast
                         .get_identifier_source(field, &index.as_weak())
@@ -66,9 +110,24 @@ impl Visitor<Statement> for Emitter {
                         VariableSource::Argument(arg_index) => {
                             self.write(OpCode::StoreArgument(arg_index))
                         }
+                        VariableSource::Captured(_) => return Err(EmitError::VariableHasNoSource),
                     }
                 }
             }
+            Statement::ElementAssignment { on, index: position, expr } => {
+                self.visit(on, ast)?;
+                self.visit(position, ast)?;
+
+                let target = ast
+                    .get_arp_file_in_parent(index.as_weak())
+                    .zip(ast.get_type(on).ok())
+                    .and_then(|(file, ty)| file.type_collection.get_array_element(&ty))
+                    .ok_or(EmitError::CantResolveType)?;
+                self.visit_converted(ast, expr, &target)?;
+
+                let element = Emitter::element_type(ast, on)?;
+                self.write(OpCode::StoreElement(element));
+            }
             Statement::IfStmt(if_kinds) => {
                 if if_kinds.len() > 2 {
                     return Err(EmitError::UnsupportedIfChain);
@@ -117,7 +176,9 @@ impl Visitor<Statement> for Emitter {
 
 
             Statement::Return(expr) => {
-                self.visit(expr, ast)?;
+                let func = ast.get_parent_of_kind::<Function, _>(*index).ok_or(EmitError::CantResolveType)?;
+
+                self.visit_converted(ast, expr, &ast.get(&func).return_type)?;
                 self.write(OpCode::Return);
             },
 
@@ -125,13 +186,24 @@ impl Visitor<Statement> for Emitter {
 
                 let loop_start = self.next_label();
                 let condition = self.next_label();
+                let loop_end = self.next_label();
 
                 self.write(OpCode::BranchTo(condition.clone()));
                 self.write_labeled_opcode(OpCode::NoOperation, loop_start.clone());
+
+                self.loop_ends.push(loop_end.clone());
                 self.visit(block, ast)?;
+                self.loop_ends.pop();
+
                 self.write_labeled_opcode(OpCode::NoOperation, condition);
                 self.visit(expr, ast)?;
-                self.write(OpCode::BranchIfTrue(loop_start))
+                self.write(OpCode::BranchIfTrue(loop_start));
+                self.write_labeled_opcode(OpCode::NoOperation, loop_end);
+            },
+
+            Statement::Break => {
+                let loop_end = self.loop_ends.last().ok_or(EmitError::BreakOutsideLoop)?.clone();
+                self.write(OpCode::BranchTo(loop_end));
             },
             
             // Statement::ForStmt { .. } => todo!(),
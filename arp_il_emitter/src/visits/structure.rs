@@ -1,14 +1,17 @@
 use std::{collections::HashSet, vec};
 
 use arp_ast_processor::types::{
-    ast_node_value::{Ast, Id},
+    ast_node_value::{Ast, Id, WId},
+    simple::Identifier,
     structure::Structure,
+    type_collection::{Overload, TypeId, TypeInfo, TypeKind},
 };
 
 use crate::{
-    emitter::Emitter,
-    il_token::{FunctionFlags, ILToken, Method, OpCode, StructureFlags},
+    emitter::{Emitter, RUNTIME_ASSEMBLY},
+    il_token::{FunctionFlags, ILToken, Method, OpCode, ResolvedType, StructureFlags},
     traits::Visitor,
+    utils::il_name,
     EmitError,
 };
 
@@ -19,17 +22,137 @@ impl Visitor<Structure> for Emitter {
             .resolve_type(structure.self_type.clone(), &index.as_weak())
             .ok_or(EmitError::CantResolveType)?;
 
-        let flags = HashSet::from_iter(vec![StructureFlags::Auto]);
-        self.write(ILToken::StartStructure(flags, ty.full_name.to_string()));
+        match &ty.kind {
+            TypeKind::Union(variants) => {
+                self.write_union(index.as_weak(), ast, &structure.self_type, ty, variants)?;
+            }
+            TypeKind::Trait => {
+                self.write_trait(index.as_weak(), ast, ty)?;
+            }
+            TypeKind::Enum(members) => {
+                self.write_enum(index.as_weak(), ast, ty, members)?;
+            }
+            _ if ty.is_value_type => {
+                let flags = HashSet::from_iter(vec![StructureFlags::Sealed, StructureFlags::Sequential]);
+                let extends = format!("[{RUNTIME_ASSEMBLY}]System.ValueType");
+                self.write_class(index.as_weak(), ast, &structure.self_type, ty, flags, Some(extends))?;
+            }
+            _ => {
+                let collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(EmitError::ArpFileNotFound)?.type_collection;
+                let extends = match &ty.base {
+                    Some(base) => Some(Emitter::extends_spec(collection, collection.try_get_strong(base).ok_or(EmitError::CantResolveType)?)?),
+                    None => None,
+                };
+
+                let flags = HashSet::from_iter(vec![StructureFlags::Auto]);
+                self.write_class(index.as_weak(), ast, &structure.self_type, ty, flags, extends)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Emitter {
+    pub(crate) fn write_union(&mut self, index: WId, ast: &Ast, self_type: &TypeId, ty: &TypeInfo, variants: &[TypeId]) -> Result<(), EmitError> {
+        let flags = HashSet::from_iter(vec![StructureFlags::Auto, StructureFlags::Abstract]);
+        self.write_class(index, ast, self_type, ty, flags, None)?;
+
+        for variant_type in variants {
+            let variant = ast
+                .resolve_type(variant_type.clone(), &index)
+                .ok_or(EmitError::CantResolveType)?;
+
+            let flags = HashSet::from_iter(vec![StructureFlags::Auto, StructureFlags::Sealed]);
+            self.write_class(index, ast, variant_type, variant, flags, Some(ty.full_name.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Traits are interfaces. Methods without a body are abstract, default methods are virtual.
+    fn write_trait(&mut self, index: WId, ast: &Ast, ty: &TypeInfo) -> Result<(), EmitError> {
+        let flags = HashSet::from_iter(vec![StructureFlags::Interface, StructureFlags::Abstract, StructureFlags::Auto]);
+        self.write(ILToken::StartStructure(flags, ty.full_name.to_string(), None, vec![]));
+
+        for method in &ty.methods {
+            match method.definition {
+                Some(definition) => self.visit(&definition, ast)?,
+                None => self.write(ILToken::StartMethod(Method {
+                    flags: HashSet::from_iter(vec![
+                        FunctionFlags::Public,
+                        FunctionFlags::NewSlot,
+                        FunctionFlags::Abstract,
+                        FunctionFlags::Virtual,
+                        FunctionFlags::IsStatic(false),
+                        FunctionFlags::Cil,
+                        FunctionFlags::Managed,
+                    ]),
+                    params: method.args.iter().map(|(ident, ty)| Emitter::resolve_ty(ast, ty, index).map(|t| (ident.0.to_string(), t))).collect::<Result<Vec<_>, _>>()?,
+                    registers: vec![],
+                    return_ty: Emitter::resolve_ty(ast, &method.return_type, index)?,
+                    name: method.name.0.to_string(),
+                    overrides: None,
+                })),
+            }
+        }
+
+        self.write(ILToken::EndStructure(ty.full_name.to_string()));
+
+        Ok(())
+    }
+
+    /// Enums have no constructor, their members are literal fields of the enum type itself.
+    fn write_enum(&mut self, index: WId, ast: &Ast, ty: &TypeInfo, members: &[(Identifier, i64)]) -> Result<(), EmitError> {
+        let (_, underlying) = ty.fields.first().ok_or(EmitError::CantResolveField)?;
+        let underlying = Emitter::resolve_ty(ast, underlying, index)?;
+        let enum_type = ResolvedType::from(ty);
+
+        let flags = HashSet::from_iter(vec![StructureFlags::Sealed, StructureFlags::Auto]);
+        self.write(ILToken::StartStructure(flags, ty.full_name.to_string(), Some(format!("[{RUNTIME_ASSEMBLY}]System.Enum")), vec![]));
+        self.write(ILToken::EnumValue(underlying.clone()));
+
+        for (member, value) in members {
+            self.write(ILToken::EnumMember(member.0.to_string(), enum_type.clone(), underlying.clone(), *value));
+        }
+
+        self.write(ILToken::EndStructure(ty.full_name.to_string()));
+
+        Ok(())
+    }
+
+    fn write_class(
+        &mut self,
+        index: WId,
+        ast: &Ast,
+        self_type: &TypeId,
+        ty: &TypeInfo,
+        flags: HashSet<StructureFlags>,
+        extends: Option<String>,
+    ) -> Result<(), EmitError> {
+        let ty_collection = &ast.get_arp_file_in_parent(index).ok_or(EmitError::ArpFileNotFound)?.type_collection;
+
+        let name = match &ty.kind {
+            TypeKind::Generic(params) => format!("{}<{}>", il_name(ty), Emitter::generic_params_decl(ty_collection, params)?),
+            _ => ty.full_name.to_string(),
+        };
+
+        let implements = ty.traits
+            .iter()
+            .map(|implemented| Emitter::extends_spec(ty_collection, ty_collection.try_get_strong(implemented).ok_or(EmitError::CantResolveType)?))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.write(ILToken::StartStructure(flags, name, extends, implements));
 
         for (fld, fld_ty) in &ty.fields {
             self.write(ILToken::Field(
                 fld.0.to_string(),
-                Emitter::resolve_ty(ast, fld_ty, *index)?,
+                Emitter::resolve_ty(ast, fld_ty, index)?,
             ))
         }
 
-        let ty_collection = &ast.get_arp_file_in_parent(index.as_weak()).ok_or(EmitError::ArpFileNotFound)?.type_collection;
+        let type_spec = Emitter::type_spec(ty_collection, ty)?;
+        let void: ResolvedType = ty_collection.try_get_strong(&ty_collection.get_void()).ok_or(EmitError::CantResolveType)?.into();
 
         self.write(ILToken::StartMethod(Method {
             flags: HashSet::from_iter(vec![
@@ -37,16 +160,47 @@ impl Visitor<Structure> for Emitter {
                 FunctionFlags::Managed,
                 FunctionFlags::IsStatic(false),
             ]),
-            params: ty.fields.iter().map(|(ident, ty)| Emitter::resolve_ty(ast, ty, *index).map(|t| (ident.0.to_string(), t))).collect::<Result<Vec<_>, _>>()?,
+            params: ty_collection.get_all_fields(self_type).iter().map(|(ident, ty)| Emitter::resolve_ty(ast, ty, index).map(|t| (ident.0.to_string(), t))).collect::<Result<Vec<_>, _>>()?,
             registers: vec![],
-            return_ty: ty_collection.try_get_strong(&ty_collection.get_void()).ok_or(EmitError::CantResolveType)?.into(),
+            return_ty: void.clone(),
             name: ".ctor".to_string(),
+            overrides: None,
         }));
 
+        // Inherited fields come first and are passed on to the base constructor.
+        let mut inherited = 0;
+        if let Some(base) = &ty.base {
+            let base_info = ty_collection.try_get_strong(base).ok_or(EmitError::CantResolveType)?;
+            // Managed bases are constructed with their parameterless constructor, their fields aren't passed on.
+            let base_params = match ty_collection.is_managed(base) {
+                true => match ty_collection.resolve_constructor(base, &[], &[]) {
+                    Overload::Found(_, ctor, _) if ctor.args.is_empty() => vec![],
+                    _ => return Err(EmitError::CantResolveMethod),
+                },
+                false => Emitter::ctor_params(ty_collection, base)?,
+            };
+            inherited = base_params.len();
+
+            self.write(OpCode::LoadArgument(0));
+            for arg_index in 0..inherited {
+                self.write(OpCode::LoadArgument(arg_index + 1));
+            }
+
+            self.write(OpCode::Call {
+                is_instance: true,
+                is_virtual: false,
+                return_type: void,
+                external: Emitter::external_assembly(base_info),
+                ty: Emitter::type_spec(ty_collection, base_info)?,
+                method_name: ".ctor".to_string(),
+                args: base_params.iter().map(|(_, ty)| Emitter::resolve_ty(ast, ty, index)).collect::<Result<Vec<_>, _>>()?,
+            });
+        }
+
         for (fld_index, (fld, fld_ty)) in ty.fields.iter().enumerate() {
             self.write(OpCode::LoadArgument(0));
-            self.write(OpCode::LoadArgument(fld_index + 1));
-            self.write(OpCode::SetField(Emitter::resolve_ty(ast, fld_ty, *index)?, ty.full_name.to_string(), fld.0.to_string()))
+            self.write(OpCode::LoadArgument(inherited + fld_index + 1));
+            self.write(OpCode::SetField(Emitter::resolve_ty(ast, fld_ty, index)?, type_spec.clone(), fld.0.to_string()))
         }
 
         self.write(ILToken::EndMethod(".ctor".to_string()));
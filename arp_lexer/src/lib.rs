@@ -7,7 +7,7 @@ use logos::Logos;
 use tokens::ArpToken;
 
 #[inline]
-pub fn lex_tokens(source: &Source) -> Result<Vec<Spanned<ArpToken>>, Vec<LexerError>> {
+pub fn lex_tokens(source: &Source) -> Result<Vec<Spanned<ArpToken<'_>>>, Vec<LexerError>> {
     let lex = ArpToken::lexer(source.content());
 
     let mut tokens = vec![];
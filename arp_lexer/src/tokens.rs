@@ -19,7 +19,9 @@ pub enum ArpToken<'source> {
     #[token("fn")] Function,
     #[token("union")] Union,
     #[token("struct")] Struct,
+    #[token("enum")] Enum,
     #[token("impl")] Implementation,
+    #[token("trait")] Trait,
 
     #[token("let")] Let,
     #[token("mut")] Mut,
@@ -31,6 +33,9 @@ pub enum ArpToken<'source> {
     #[token("in")] In,
     #[token("break")] Break,
     #[token("return")] Return,
+    #[token("match")] Match,
+    #[token("as")] As,
+    #[token("new")] New,
     
 
     #[token("this")] This,
@@ -54,6 +59,7 @@ pub enum ArpToken<'source> {
     #[token(",")] Comma,
 
     #[token(":")] Colon,
+    #[token("?")] Question,
     #[token(";")] SemiColon,
 
     #[token("{")] BraceOpen,
@@ -71,6 +77,7 @@ pub enum ArpToken<'source> {
     #[token("/")] Slash,
 
     #[token("->")] ThinArrow,
+    #[token("=>")] FatArrow,
 
     #[regex(r#"[0-9][_0-9]*"#, |inp| inp.slice().parse().map_err(|err| (err, inp.span())))]
     Integer(i64),
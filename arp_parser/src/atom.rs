@@ -8,6 +8,8 @@ pub fn atom_parser<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Erro
     atom().then_ignore(end())
 }
 
+// `select!` returns chumsky's `Simple` error by value, which isn't ours to box.
+#[allow(clippy::result_large_err)]
 pub(crate) fn atom<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Error = Simple<ArpToken<'a>>> + Clone {
     let atom = select! {
         ArpToken::This => ChumskyNode::This,
@@ -21,6 +23,7 @@ pub(crate) fn atom<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Erro
     atom.map_with_span(Spanned::new)
 }
 
+#[allow(clippy::result_large_err)]
 pub(crate) fn ident<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Error = Simple<ArpToken<'a>>> + Clone {
     select! {ArpToken::Identifier(ident) => ChumskyNode::Identifier(Box::from(ident))}
         .map(|i| i)
@@ -28,10 +31,99 @@ pub(crate) fn ident<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Err
 }
 
 pub(crate) fn ty<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Error = Simple<ArpToken<'a>>> + Clone {
-    let ident = ident();
+    recursive(|ty| {
+        let generic_args = ty.clone()
+            .separated_by(just(ArpToken::Comma))
+            .at_least(1)
+            .delimited_by(just(ArpToken::Lt), just(ArpToken::Gt));
 
-    ident
-        .separated_by(just(ArpToken::Dot))
-        .map(ChumskyNode::Type)
+        let segment = ident()
+            .then(generic_args.or_not())
+            .map_with_span(|(ident, args), span| match args {
+                Some(args) => Spanned::new(ChumskyNode::GenericIdentifier(Box::new(ident), args), span),
+                None => ident,
+            });
+
+        // `[int32]`, an array of the inner type.
+        let array = ty.clone()
+            .delimited_by(just(ArpToken::BracketOpen), just(ArpToken::BracketClose))
+            .map(|element: Spanned<ChumskyNode>| ChumskyNode::ArrayType(Box::new(element)))
+            .map_with_span(Spanned::new);
+
+        // `fn(int32, int32) -> bool`, a function taking the inner types. Without a return type it returns `void`.
+        let function = just(ArpToken::Function)
+            .ignore_then(ty.clone()
+                .separated_by(just(ArpToken::Comma))
+                .allow_trailing()
+                .delimited_by(just(ArpToken::ParenthesisOpen), just(ArpToken::ParenthesisClose)))
+            .then(just(ArpToken::ThinArrow).ignore_then(ty.clone()).or_not())
+            .map(|(params, return_type)| ChumskyNode::FunctionType(params, return_type.map(Box::new)))
+            .map_with_span(Spanned::new);
+
+        // `(int32, string)`, a tuple of the inner types.
+        let tuple = ty
+            .separated_by(just(ArpToken::Comma))
+            .at_least(2)
+            .allow_trailing()
+            .delimited_by(just(ArpToken::ParenthesisOpen), just(ArpToken::ParenthesisClose))
+            .map(ChumskyNode::TupleType)
+            .map_with_span(Spanned::new);
+
+        segment
+            .separated_by(just(ArpToken::Dot))
+            .at_least(1)
+            .map(ChumskyNode::Type)
+            .map_with_span(Spanned::new)
+            .or(array)
+            .or(tuple)
+            .or(function)
+    })
+}
+
+/// Names bound by destructuring a tuple, `(a, _, c)`.
+pub(crate) fn tuple_pattern<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Error = Simple<ArpToken<'a>>> + Clone {
+    ident()
+        .separated_by(just(ArpToken::Comma))
+        .at_least(2)
+        .allow_trailing()
+        .delimited_by(just(ArpToken::ParenthesisOpen), just(ArpToken::ParenthesisClose))
+        .map(ChumskyNode::TuplePattern)
         .map_with_span(Spanned::new)
-}
\ No newline at end of file
+}
+
+/// Declared type parameters with optional bounds, `<T, U: IComparable + IEquatable>`.
+pub(crate) fn generic_params<'a>() -> impl Parser<ArpToken<'a>, Vec<Spanned<ChumskyNode>>, Error = Simple<ArpToken<'a>>> + Clone {
+    ident()
+        .then(
+            just(ArpToken::Colon)
+            .ignore_then(ty().separated_by(just(ArpToken::Plus)).at_least(1))
+            .or_not())
+        .map(|(ident, bounds)| ChumskyNode::GenericParam(Box::new(ident), bounds.unwrap_or_default()))
+        .map_with_span(Spanned::new)
+        .separated_by(just(ArpToken::Comma))
+        .at_least(1)
+        .delimited_by(just(ArpToken::Lt), just(ArpToken::Gt))
+}
+
+/// Name of a declared function, `identity<T>` becomes a `GenericIdentifier` holding `GenericParam`s.
+pub(crate) fn generic_ident<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Error = Simple<ArpToken<'a>>> + Clone {
+    ident()
+        .then(generic_params().or_not())
+        .map_with_span(|(ident, params), span| match params {
+            Some(params) => Spanned::new(ChumskyNode::GenericIdentifier(Box::new(ident), params), span),
+            None => ident,
+        })
+}
+
+/// Name of a declared class, type parameters are attached to the last path segment.
+pub(crate) fn generic_ty<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Error = Simple<ArpToken<'a>>> + Clone {
+    ident()
+        .then_ignore(just(ArpToken::Dot))
+        .repeated()
+        .then(generic_ident())
+        .map(|(mut path, last)| {
+            path.push(last);
+            ChumskyNode::Type(path)
+        })
+        .map_with_span(Spanned::new)
+}
@@ -2,12 +2,13 @@ use arp_lexer::tokens::ArpToken;
 use arp_types::Spanned;
 use chumsky::prelude::*;
 
-use crate::{atom, statement, types::ChumskyNode};
+use crate::{atom, statement, types::{ChumskyNode, UnaryOp}};
 
 pub fn declaration_parser<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Error = Simple<ArpToken<'a>>> + Clone {
     declaration().then_ignore(end())
 }
 
+#[allow(clippy::result_large_err)]
 pub(crate) fn declaration<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Error = Simple<ArpToken<'a>>> + Clone {
     let decl = recursive(|decl| {
 
@@ -22,13 +23,25 @@ pub(crate) fn declaration<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode
             .map_with_span(Spanned::new);
         
     
-        let function_decl = just(ArpToken::Function)
-            .ignore_then(ident.clone())
+        let function_header = just(ArpToken::Function)
+            .ignore_then(atom::generic_ident())
             .then(
-                (ident.clone()
+                (ident.clone().or(atom::tuple_pattern())
                     .then_ignore(just(ArpToken::Colon))
                     .then(ty.clone()))
                     .map_with_span(|pair, s| Into::<Spanned<ChumskyNode>>::into((ChumskyNode::VarAndType(pair.0.into(), pair.1.into()), s)))
+                    // `sep: string = ", "`, defaults are literals.
+                    .then(just(ArpToken::Eq)
+                        .ignore_then(just(ArpToken::Minus).or_not().then(atom::atom()))
+                        .map_with_span(|(sign, literal), s| match sign {
+                            Some(_) => Spanned::new(ChumskyNode::UnaryExpr(UnaryOp::Negate, literal.into()), s),
+                            None => literal,
+                        })
+                        .or_not())
+                    .map_with_span(|(parameter, default), s| match default {
+                        Some(default) => Spanned::new(ChumskyNode::DefaultParam(parameter.into(), default.into()), s),
+                        None => parameter,
+                    })
                 .or(just(ArpToken::Mut).or_not().then_ignore(just(ArpToken::This)).map_with_span(|s, span| (ChumskyNode::MutThis(s.is_some()), span).into()))
                 .separated_by(just(ArpToken::Comma))
                 .allow_trailing()
@@ -36,13 +49,20 @@ pub(crate) fn declaration<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode
             .then(
                 just(ArpToken::ThinArrow)
                 .ignore_then(ty.clone())
-                .or_not())
+                .or_not());
+
+        let function_decl = function_header.clone()
             .then(statement.clone())
             .map(|(((ident, args), return_type), block)| ChumskyNode::FuncDecl(ident.into(), args, return_type.map(|r| r.into()), block.into()))
             .map_with_span(Spanned::new);
+
+        let function_signature = function_header
+            .then_ignore(just(ArpToken::SemiColon))
+            .map(|((ident, args), return_type)| ChumskyNode::FuncSignatureDecl(ident.into(), args, return_type.map(|r| r.into())))
+            .map_with_span(Spanned::new);
     
         let class_declaration = just(ArpToken::Class)
-            .ignore_then(ty.clone())
+            .ignore_then(atom::generic_ty())
             .then(
                 just(ArpToken::Colon)
                 .ignore_then(ty.clone().separated_by(just(ArpToken::Comma))).or_not()
@@ -58,13 +78,82 @@ pub(crate) fn declaration<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode
             .map(|((ident, extends), fields)| ChumskyNode::Structure(ident.into(), extends.unwrap_or_default(), fields))
             .map_with_span(Spanned::new);
     
+        let struct_declaration = just(ArpToken::Struct)
+            .ignore_then(atom::generic_ty())
+            .then(
+                (ident.clone()
+                    .then_ignore(just(ArpToken::Colon))
+                    .then(ty.clone()))
+                    .map_with_span(|pair, s| Into::<Spanned<ChumskyNode>>::into((ChumskyNode::VarAndType(pair.0.into(), pair.1.into()), s)))
+                .separated_by(just(ArpToken::Comma))
+                .allow_trailing()
+                .delimited_by(just(ArpToken::BraceOpen), just(ArpToken::BraceClose)))
+            .map(|(ident, fields)| ChumskyNode::StructDecl(ident.into(), fields))
+            .map_with_span(Spanned::new);
+
+        let union_declaration = just(ArpToken::Union)
+            .ignore_then(ty.clone())
+            .then(
+                ident.clone()
+                .then(
+                    (ident.clone()
+                        .then_ignore(just(ArpToken::Colon))
+                        .then(ty.clone()))
+                        .map_with_span(|pair, s| Into::<Spanned<ChumskyNode>>::into((ChumskyNode::VarAndType(pair.0.into(), pair.1.into()), s)))
+                    .separated_by(just(ArpToken::Comma))
+                    .allow_trailing()
+                    .delimited_by(just(ArpToken::BraceOpen), just(ArpToken::BraceClose))
+                    .or_not())
+                .map(|(ident, fields)| ChumskyNode::UnionVariant(ident.into(), fields.unwrap_or_default()))
+                .map_with_span(Spanned::new)
+                .separated_by(just(ArpToken::Comma))
+                .allow_trailing()
+                .delimited_by(just(ArpToken::BraceOpen), just(ArpToken::BraceClose)))
+            .map(|(ident, variants)| ChumskyNode::UnionDecl(ident.into(), variants))
+            .map_with_span(Spanned::new);
+    
+        let enum_declaration = just(ArpToken::Enum)
+            .ignore_then(ty.clone())
+            .then(
+                ident.clone()
+                .then(
+                    just(ArpToken::Eq)
+                    .ignore_then(just(ArpToken::Minus).or_not())
+                    .then(select! { ArpToken::Integer(i) => i })
+                    .map(|(sign, value)| if sign.is_some() { -value } else { value })
+                    .or_not())
+                .map(|(ident, value)| ChumskyNode::EnumVariant(ident.into(), value))
+                .map_with_span(Spanned::new)
+                .separated_by(just(ArpToken::Comma))
+                .allow_trailing()
+                .delimited_by(just(ArpToken::BraceOpen), just(ArpToken::BraceClose)))
+            .map(|(ident, variants)| ChumskyNode::EnumDecl(ident.into(), variants))
+            .map_with_span(Spanned::new);
+
+        let trait_declaration = just(ArpToken::Trait)
+            .ignore_then(atom::generic_ty())
+            .then(
+                function_signature
+                .or(function_decl.clone())
+                .repeated()
+                .delimited_by(just(ArpToken::BraceOpen), just(ArpToken::BraceClose)))
+            .map(|(ident, items)| ChumskyNode::TraitDecl(ident.into(), items))
+            .map_with_span(Spanned::new);
+
         let implementation_declaration = just(ArpToken::Implementation)
             .ignore_then(ty.clone())
+            .then(
+                just(ArpToken::For)
+                .ignore_then(ty.clone())
+                .or_not())
             .then(
                 function_decl.clone()
                 .repeated()
                 .delimited_by(just(ArpToken::BraceOpen), just(ArpToken::BraceClose)))
-            .map(|(ident, funcs)| ChumskyNode::ImplementationDecl(ident.into(), funcs))
+            .map(|((ident, for_ty), funcs)| match for_ty {
+                Some(for_ty) => ChumskyNode::TraitImplementationDecl(ident.into(), for_ty.into(), funcs),
+                None => ChumskyNode::ImplementationDecl(ident.into(), funcs),
+            })
             .map_with_span(Spanned::new);
     
         let import_declaration = just(ArpToken::From)
@@ -81,6 +170,10 @@ pub(crate) fn declaration<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode
         statement_decl
             .or(function_decl)
             .or(class_declaration)
+            .or(struct_declaration)
+            .or(union_declaration)
+            .or(enum_declaration)
+            .or(trait_declaration)
             .or(implementation_declaration)
             .or(import_declaration)
     });
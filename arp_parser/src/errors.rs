@@ -13,7 +13,7 @@ use thiserror::Error;
 pub enum ParserError<'a> {
 
     #[error("Simple parser error")]
-    SimpleError(Simple<ArpToken<'a>, Range<usize>>),
+    SimpleError(Box<Simple<ArpToken<'a>, Range<usize>>>),
 
     #[error("Unknown")]
     #[default]
@@ -1,4 +1,6 @@
-use arp_lexer::tokens::ArpToken;
+use std::ops::Range;
+
+use arp_lexer::tokens::{ArpToken, Float};
 use arp_types::Spanned;
 use chumsky::prelude::*;
 
@@ -8,6 +10,7 @@ pub fn expr_parser<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Erro
     expr().then_ignore(end())
 }
 
+#[allow(clippy::result_large_err)]
 pub(crate) fn expr<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Error = Simple<ArpToken<'a>>> + Clone {
     let expr = recursive(|expr| {
         
@@ -15,8 +18,16 @@ pub(crate) fn expr<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Erro
         let ty = atom::ty();
         let ident = atom::ident();
 
+        // `join(items, sep: ", ")`, named arguments can follow the positional ones.
+        let argument = ident.clone()
+            .then_ignore(just(ArpToken::Colon))
+            .then(expr.clone())
+            .map(|(name, value)| ChumskyNode::NamedArgument(Box::new(name), Box::new(value)))
+            .map_with_span(Spanned::new)
+            .or(expr.clone());
+
         let call = ident.clone().map(Box::new)
-            .then(expr.clone()
+            .then(argument.clone()
                 .separated_by(just(ArpToken::Comma))
                 .allow_trailing()
                 .delimited_by(just(ArpToken::ParenthesisOpen), just(ArpToken::ParenthesisClose)))
@@ -24,7 +35,7 @@ pub(crate) fn expr<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Erro
             .map_with_span(Spanned::new);
 
 
-        let construct = ty.map(Box::new)
+        let construct = ty.clone().map(Box::new)
             .then(((ident.clone().then_ignore(just(ArpToken::Colon))).or_not().then(expr.clone()))
                 .separated_by(just(ArpToken::Comma))
                 .allow_trailing()
@@ -33,6 +44,16 @@ pub(crate) fn expr<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Erro
             .map_with_span(Spanned::new);
 
 
+        // `new Random(42)` calls a constructor, overloads are resolved by the arguments.
+        let new = just(ArpToken::New)
+            .ignore_then(ty.clone().map(Box::new))
+            .then(argument
+                .separated_by(just(ArpToken::Comma))
+                .allow_trailing()
+                .delimited_by(just(ArpToken::ParenthesisOpen), just(ArpToken::ParenthesisClose)))
+            .map(|(ty, args)| ChumskyNode::NewExpr(ty, args))
+            .map_with_span(Spanned::new);
+
         let array = expr.clone()
             .separated_by(just(ArpToken::Comma))
             .allow_trailing()
@@ -40,25 +61,97 @@ pub(crate) fn expr<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Erro
             .map(ChumskyNode::ArrayExpr)
             .map_with_span(Spanned::new);
 
-        let grouping = expr
-            .delimited_by(just(ArpToken::ParenthesisOpen), just(ArpToken::ParenthesisClose));
+        let pattern = ty.clone().map(Box::new)
+            .then(((ident.clone().then_ignore(just(ArpToken::Colon))).or_not().then(ident.clone()))
+                .separated_by(just(ArpToken::Comma))
+                .allow_trailing()
+                .delimited_by(just(ArpToken::BraceOpen), just(ArpToken::BraceClose))
+                .or_not())
+            .map(|(ty, fields)| ChumskyNode::Pattern(ty, fields))
+            .map_with_span(Spanned::new);
+
+        let match_expr = just(ArpToken::Match)
+            .ignore_then(expr.clone().map(Box::new))
+            .then(pattern
+                .then_ignore(just(ArpToken::FatArrow))
+                .then(expr.clone())
+                .separated_by(just(ArpToken::Comma))
+                .allow_trailing()
+                .delimited_by(just(ArpToken::BraceOpen), just(ArpToken::BraceClose)))
+            .map(|(on, arms)| ChumskyNode::MatchExpr(on, arms))
+            .map_with_span(Spanned::new);
 
+        // `fn(a: int32, b: int32) => a + b`, the body is a single expression.
+        let lambda = just(ArpToken::Function)
+            .ignore_then(ident.clone()
+                .then_ignore(just(ArpToken::Colon))
+                .then(ty.clone())
+                .map_with_span(|(ident, ty), span| Spanned::new(ChumskyNode::VarAndType(Box::new(ident), Box::new(ty)), span))
+                .separated_by(just(ArpToken::Comma))
+                .allow_trailing()
+                .delimited_by(just(ArpToken::ParenthesisOpen), just(ArpToken::ParenthesisClose)))
+            .then_ignore(just(ArpToken::FatArrow))
+            .then(expr.clone().map(Box::new))
+            .map(|(params, body)| ChumskyNode::LambdaExpr(params, body))
+            .map_with_span(Spanned::new);
+
+        // `(a)` is a grouping, `(a, b)` a tuple.
+        let grouping = expr.clone()
+            .then(just(ArpToken::Comma)
+                .ignore_then(expr.clone().separated_by(just(ArpToken::Comma)).allow_trailing())
+                .or_not())
+            .delimited_by(just(ArpToken::ParenthesisOpen), just(ArpToken::ParenthesisClose))
+            .map_with_span(|(first, rest), span| match rest {
+                Some(rest) => Spanned::new(ChumskyNode::TupleExpr(std::iter::once(first).chain(rest).collect()), span),
+                None => first,
+            });
+
+        // Boxed to keep the nested parsers from overflowing the stack in debug builds.
         let atom = 
             atom::atom()
+            .or(match_expr)
+            .or(lambda)
+            .or(new)
             .or(grouping)
             .or(construct)
             .or(call)
             .or(array)
-            .or(ident);
+            .or(ident)
+            .boxed();
 
         let get = atom.clone()
             .then(
                 just(ArpToken::Dot)
-                .then(atom.clone())
+                .ignore_then(select! { ArpToken::Float(f) => f }.map_with_span(Postfix::TupleFields))
+                .or(just(ArpToken::Dot)
+                    .ignore_then(atom.clone())
+                    .map(Postfix::Get))
+                .or(just(ArpToken::Question).map_with_span(|_, span| Postfix::Try(span)))
+                .or(expr.clone()
+                    .delimited_by(just(ArpToken::BracketOpen), just(ArpToken::BracketClose))
+                    .map_with_span(Postfix::Index))
                 .repeated())
-            .foldl(|lhs, (_, rhs)| {
-                let new_span = lhs.concat(&rhs);
-                (ChumskyNode::GetExpr(Box::new(lhs), Box::new(rhs)), new_span).into()
+            .foldl(|lhs, rhs| match rhs {
+                Postfix::Get(rhs) => {
+                    let new_span = lhs.concat(&rhs);
+                    (ChumskyNode::GetExpr(Box::new(lhs), Box::new(rhs)), new_span).into()
+                },
+                Postfix::Try(question_span) => {
+                    let new_span = lhs.append_span(&question_span);
+                    (ChumskyNode::TryExpr(Box::new(lhs)), new_span).into()
+                },
+                Postfix::Index(index, bracket_span) => {
+                    let new_span = lhs.append_span(&bracket_span);
+                    (ChumskyNode::IndexExpr(Box::new(lhs), Box::new(index)), new_span).into()
+                },
+                Postfix::TupleFields(fields, span) => {
+                    let (outer, inner) = split_tuple_fields(fields, span);
+
+                    [outer, inner].into_iter().fold(lhs, |lhs, field| {
+                        let new_span = lhs.concat(&field);
+                        (ChumskyNode::GetExpr(Box::new(lhs), Box::new(field)), new_span).into()
+                    })
+                },
             });
 
         let unary = 
@@ -70,13 +163,45 @@ pub(crate) fn expr<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Erro
                 let new_span = rhs.append_span(&op_span);
                 (ChumskyNode::UnaryExpr(op, Box::new(rhs)), new_span).into()
             });
+
+        // `-x as float32` casts the negated value, casts bind tighter than binary operators.
+        let cast = unary
+            .then(just(ArpToken::As)
+                .ignore_then(ty.clone())
+                .repeated())
+            .foldl(|lhs, ty| {
+                let new_span = lhs.concat(&ty);
+                (ChumskyNode::CastExpr(Box::new(lhs), Box::new(ty)), new_span).into()
+            });
     
-        precedence_climber(unary)
+        precedence_climber(cast)
     });
 
     expr
 }
 
+/// What follows an atom: `.field`, `?`, `[index]` or `.0.1`.
+enum Postfix {
+    Get(Spanned<ChumskyNode>),
+    Try(Range<usize>),
+    Index(Spanned<ChumskyNode>, Range<usize>),
+    TupleFields(Float, Range<usize>),
+}
+
+/// `pair.0.1` is lexed with a float after the first dot, the digits on each side of it
+/// are fields of nested tuples.
+fn split_tuple_fields(fields: Float, span: Range<usize>) -> (Spanned<ChumskyNode>, Spanned<ChumskyNode>) {
+    let outer = fields.0.trunc() as i64;
+    let outer_len = outer.to_string().len();
+    let inner_len = span.len().saturating_sub(outer_len + 1);
+    let inner = ((fields.0 - fields.0.trunc()) * 10f64.powi(inner_len as i32)).round() as i64;
+
+    (
+        Spanned::new(ChumskyNode::LiteralInteger(outer), span.start..span.start + outer_len),
+        Spanned::new(ChumskyNode::LiteralInteger(inner), span.start + outer_len + 1..span.end),
+    )
+}
+
 fn precedence_climber<'a, U : Parser<ArpToken<'a>, Spanned<ChumskyNode>, Error = Simple<ArpToken<'a>>> + Clone>(unary : U) -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>, Error = Simple<ArpToken<'a>>> + Clone {
     let logic_or = unary.clone()
         .then(
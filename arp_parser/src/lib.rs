@@ -36,7 +36,7 @@ pub fn parse<'a>(source_len: usize, input: &'a [arp_types::Spanned<ArpToken<'a>>
             Ok(node)
         },
         Err(err) => {
-            Err(err.iter().map(|e| ParserError::SimpleError(e.clone())).collect())
+            Err(err.iter().map(|e| ParserError::SimpleError(Box::new(e.clone()))).collect())
         },
     }
 }
@@ -32,7 +32,7 @@ pub(crate) fn statement<'a>() -> impl Parser<ArpToken<'a>, Spanned<ChumskyNode>,
 
         let variable_decl = just(ArpToken::Let)
             .ignore_then(just(ArpToken::Mut).or_not())
-            .then(ident.clone())
+            .then(ident.clone().or(atom::tuple_pattern()))
             .then(just(ArpToken::Colon).ignore_then(ty.clone()).or_not())
             .then_ignore(just(ArpToken::Eq))
             .then(inline_expr.clone())
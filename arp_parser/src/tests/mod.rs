@@ -17,7 +17,7 @@ fn test_parse_expr<'a, S : Into<&'a str>>(path: S, input: S) -> Spanned<ChumskyN
     let chumsky_node = match expr().then_ignore(end()).parse(stream) {
         Ok(ok) => ok,
         Err(errors) => {
-            let errors = errors.iter().map(|e| ParserError::SimpleError(e.clone())).collect::<Vec<_>>();
+            let errors = errors.iter().map(|e| ParserError::SimpleError(Box::new(e.clone()))).collect::<Vec<_>>();
             let report = ParserError::build_report(&errors, &source);
 
             report.print(ariadne::sources([
@@ -41,7 +41,7 @@ fn test_parse_stmt<'a, S : Into<&'a str>>(path: S, input: S) -> Spanned<ChumskyN
     let chumsky_node = match crate::statement::statement().then_ignore(end()).parse(stream) {
         Ok(ok) => ok,
         Err(errors) => {
-            let errors = errors.iter().map(|e| ParserError::SimpleError(e.clone())).collect::<Vec<_>>();
+            let errors = errors.iter().map(|e| ParserError::SimpleError(Box::new(e.clone()))).collect::<Vec<_>>();
             let report = ParserError::build_report(&errors, &source);
 
             report.print(ariadne::sources([
@@ -66,7 +66,7 @@ fn test_parse_decl<'a, S : Into<&'a str>>(path: S, input: S) -> Spanned<ChumskyN
     let chumsky_node = match declaration_parser().then_ignore(end()).parse(stream) {
         Ok(ok) => ok,
         Err(errors) => {
-            let errors = errors.iter().map(|e| ParserError::SimpleError(e.clone())).collect::<Vec<_>>();
+            let errors = errors.iter().map(|e| ParserError::SimpleError(Box::new(e.clone()))).collect::<Vec<_>>();
             let report = ParserError::build_report(&errors, &source);
 
             report.print(ariadne::sources([
@@ -130,10 +130,84 @@ fn get() {
 
 
 
+#[test]
+fn match_expr() {
+    test_parse_expr("test/match", "match shape { Shape.Circle { r } => r, Shape.Rect { w: width, h } => width * h }");
+    test_parse_expr("test/match", "match shape { Shape.Empty => 0, _ => 1, }");
+    test_parse_expr("test/match", "match shape.get() { other => other }");
+    test_parse_expr("test/match", "match opt { Option<int32>.Some { value } => value, Option<int32>.None => 0 }");
+}
+
+#[test]
+fn try_expr() {
+    test_parse_expr("test/try", "value?");
+    test_parse_expr("test/try", "reader.read_line()?.trim()?");
+    test_parse_expr("test/try", "1 + parse(line)?");
+    test_parse_expr("test/construct", "Option<int32>.Some { value: 1 }");
+    test_parse_expr("test/construct", "Result<Option<int32>, string>.Err { error: \"oops\" }");
+    test_parse_expr("test/binary", "a < b");
+}
+
+#[test]
+fn array_expr() {
+    test_parse_expr("test/array", "[1, 2, 3]");
+    test_parse_expr("test/array", "[]");
+    test_parse_expr("test/index", "items[0]");
+    test_parse_expr("test/index", "grid[x][y + 1].len");
+    test_parse_expr("test/index", "this.items[i].name()");
+}
+
+#[test]
+fn tuple_expr() {
+    test_parse_expr("test/tuple", "(1, \"one\")");
+    test_parse_expr("test/tuple", "((1, 2), x + 1,)");
+    test_parse_expr("test/grouping", "(1 + 2) * 3");
+
+    match test_parse_expr("test/tuple", "pair.0.1").get_value() {
+        ChumskyNode::GetExpr(inner, field) => {
+            assert!(matches!(field.get_value(), ChumskyNode::LiteralInteger(1)));
+            assert!(matches!(inner.get_value(), ChumskyNode::GetExpr(_, field) if matches!(field.get_value(), ChumskyNode::LiteralInteger(0))));
+        },
+        node => panic!("Expected field access, found {node:?}"),
+    }
+}
+
+#[test]
+fn lambda_expr() {
+    test_parse_expr("test/lambda", "fn(x: int32) => x + 1");
+    test_parse_expr("test/lambda", "fn() => Console.WriteLine(\"hi\")");
+    test_parse_expr("test/lambda", "items.Sort(fn(a: int32, b: int32) => a - b)");
+
+    match test_parse_expr("test/lambda", "fn(a: int32, b: int32) => a * b + 1").get_value() {
+        ChumskyNode::LambdaExpr(params, body) => {
+            assert_eq!(params.len(), 2);
+            assert!(matches!(body.get_value(), ChumskyNode::BinaryExpr(..)));
+        },
+        node => panic!("Expected lambda, found {node:?}"),
+    }
+}
+
+#[test]
+fn cast_expr() {
+    test_parse_expr("test/cast", "x as float32");
+    test_parse_expr("test/cast", "shape as Square");
+    test_parse_expr("test/cast", "items.len as float64 / 2.0");
+
+    match test_parse_expr("test/cast", "-x as float32 + 1").get_value() {
+        ChumskyNode::BinaryExpr(lhs, _, _) => match lhs.get_value() {
+            ChumskyNode::CastExpr(value, _) => assert!(matches!(value.get_value(), ChumskyNode::UnaryExpr(..))),
+            node => panic!("Expected cast, found {node:?}"),
+        },
+        node => panic!("Expected binary expression, found {node:?}"),
+    }
+}
+
+
 #[test]
 fn statement() {
     test_parse_stmt("test/statement/ExpressionStmt", "1 + 1;");
     test_parse_stmt("test/statement/AssignmentStmt", "x.field = 1;");
+    test_parse_stmt("test/statement/AssignmentStmt", "items[0] = 1;");
     test_parse_stmt("test/statement/BlockStmt", "{ x = 1; 2 + 2; }");
     test_parse_stmt("test/statement/BlockStmt/Return", "{ 1 + 2 }");
     test_parse_stmt("test/statement/IfStmt/Else", "if x == 1 { y = 2; } else { 3; }");
@@ -157,8 +231,57 @@ fn declaration() {
     test_parse_decl("test/declaration/VariableDecl/BlockInside", "fn func() { let x = 1; }");
     test_parse_decl("test/declaration/ClassDecl", "class MyClass : BaseClass, IInterface { ident1: typ1, ident2: type2, }");
     test_parse_decl("test/declaration/ClassDecl", "class MyClass { ident1: typ1, ident2: type2, }");
+    test_parse_decl("test/declaration/StructDecl", "struct Point { x: int32, y: int32, }");
+    test_parse_decl("test/declaration/StructDecl", "struct Pair<K, V> { key: K, value: V }");
+    test_parse_decl("test/declaration/UnionDecl", "union Shape { Circle { r: float64 }, Rect { w: float64, h: float64 } }");
+    test_parse_decl("test/declaration/UnionDecl", "union Shape { Circle { r: float64, }, Empty, }");
+    test_parse_decl("test/declaration/EnumDecl", "enum Color { Red, Green = 5, Blue, }");
+    test_parse_decl("test/declaration/EnumDecl", "enum Offset { Back = -1, Forward = 1 }");
+
+    test_parse_decl("test/declaration/FuncDecl", "fn parse(line: string) -> Result<int32, string> { }");
+    test_parse_decl("test/declaration/FuncDecl", "fn first(items: Option<Option<int32>>) -> Option<int32> { }");
+    test_parse_decl("test/declaration/FuncDecl", "fn sum(items: [int32], grid: [[string]]) -> List<[int32]> { }");
+    test_parse_decl("test/declaration/FuncDecl", "fn swap((a, b): (int32, string)) -> (string, int32) { let (x, _) = (b, a); }");
+    test_parse_decl("test/declaration/FuncDecl", "fn filter(items: [int32], keep: fn(int32) -> bool, done: fn()) -> fn(int32, fn(int32)) -> [int32] { }");
     test_parse_decl("test/declaration/ImplementationDecl", "impl Namespace.MyClass { fn func(arg1: typ1, arg2: type2) -> return_type { } fn func(arg1: typ1, arg2: type2) { } } ");
+    test_parse_decl("test/declaration/GenericClassDecl", "class Box<T> { value: T }");
+    test_parse_decl("test/declaration/GenericClassDecl", "class Namespace.Pair<K, V: IComparable + IEquatable> { key: K, value: V, }");
+    test_parse_decl("test/declaration/GenericImplementationDecl", "impl Box<T> { fn get(this) -> T { return this.value; } }");
+    test_parse_decl("test/declaration/TraitDecl", "trait Printable { fn print(this) -> string; fn show(this) { } }");
+    test_parse_decl("test/declaration/TraitImplementationDecl", "impl Printable for Point { fn print(this) -> string { return \"point\"; } }");
+    test_parse_decl("test/declaration/GenericFuncDecl", "fn identity<T: Show>(value: T) -> T { return value; }");
+    test_parse_decl("test/declaration/DefaultParam", "fn pad(text: string, width: int32 = 8, fill: string = \" \", shift: float32 = -0.5) { }");
     test_parse_decl("test/declaration/ImportDecl", "from path.to.file import OuterClass, static_func");
     test_parse_decl("test/declaration/ImportDecl", "from extern path.to.file import OuterClass");
     test_parse_decl("test/declaration/ImportDecl", "from path.to.file import MyNamespace.MyClass");
 }
+
+#[test]
+fn new_expr() {
+    test_parse_expr("test/new", "new System.Text.StringBuilder()");
+    test_parse_expr("test/new", "new List<int32>(16,)");
+
+    match test_parse_expr("test/new", "new Random(42).Next(10)").get_value() {
+        ChumskyNode::GetExpr(lhs, _) => match lhs.get_value() {
+            ChumskyNode::NewExpr(_, args) => assert_eq!(args.len(), 1),
+            node => panic!("Expected new, found {node:?}"),
+        },
+        node => panic!("Expected get, found {node:?}"),
+    }
+}
+
+#[test]
+fn named_arguments() {
+    test_parse_expr("test/named", "new StringBuilder(capacity: 16)");
+
+    match test_parse_expr("test/named", "items.join(sep: \", \", 3)").get_value() {
+        ChumskyNode::GetExpr(_, call) => match call.get_value() {
+            ChumskyNode::CallExpr(_, args) => {
+                assert!(matches!(args[0].get_value(), ChumskyNode::NamedArgument(name, _) if name.get_value() == &ChumskyNode::Identifier("sep".into())));
+                assert!(matches!(args[1].get_value(), ChumskyNode::LiteralInteger(3)));
+            },
+            node => panic!("Expected call, found {node:?}"),
+        },
+        node => panic!("Expected get, found {node:?}"),
+    }
+}
@@ -1,6 +1,8 @@
 use arp_lexer::tokens::Float;
 use arp_types::Spanned;
 
+/// Destructured variant fields of a pattern: optional field name and the binding.
+pub type PatternFields = Vec<(Option<Spanned<ChumskyNode>>, Spanned<ChumskyNode>)>;
 
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -11,8 +13,14 @@ pub enum ChumskyNode {
     // Declarations
     ImportDecl(bool, Vec<Spanned<Self>>, Vec<Spanned<Self>>),
     ImplementationDecl(Box<Spanned<Self>>, Vec<Spanned<Self>>),
+    TraitImplementationDecl(Box<Spanned<Self>>, Box<Spanned<Self>>, Vec<Spanned<Self>>),
+    TraitDecl(Box<Spanned<Self>>, Vec<Spanned<Self>>),
     Structure(Box<Spanned<Self>>, Vec<Spanned<Self>>, Vec<Spanned<Self>>),
+    StructDecl(Box<Spanned<Self>>, Vec<Spanned<Self>>),
+    UnionDecl(Box<Spanned<Self>>, Vec<Spanned<Self>>),
+    EnumDecl(Box<Spanned<Self>>, Vec<Spanned<Self>>),
     FuncDecl(Box<Spanned<Self>>, Vec<Spanned<Self>>, Option<Box<Spanned<Self>>>, Box<Spanned<Self>>),
+    FuncSignatureDecl(Box<Spanned<Self>>, Vec<Spanned<Self>>, Option<Box<Spanned<Self>>>),
     VariableDecl(bool, Box<Spanned<Self>>, Option<Box<Spanned<Self>>>, Box<Spanned<Self>>),
     StatementDecl(Box<Spanned<Self>>),
 
@@ -34,6 +42,18 @@ pub enum ChumskyNode {
     CallExpr(Box<Spanned<Self>>, Vec<Spanned<Self>>),
     ConstructExpr(Box<Spanned<Self>>, Vec<(Option<Spanned<Self>>, Spanned<Self>)>),
     ArrayExpr(Vec<Spanned<Self>>),
+    IndexExpr(Box<Spanned<Self>>, Box<Spanned<Self>>),
+    TupleExpr(Vec<Spanned<Self>>),
+    MatchExpr(Box<Spanned<Self>>, Vec<(Spanned<Self>, Spanned<Self>)>),
+    /// Parameters as `VarAndType`s and the body, `fn(x: int32) => x + 1`.
+    LambdaExpr(Vec<Spanned<Self>>, Box<Spanned<Self>>),
+    TryExpr(Box<Spanned<Self>>),
+    /// `value as float32`, the value and the target type.
+    CastExpr(Box<Spanned<Self>>, Box<Spanned<Self>>),
+    /// `new Random(42)`, a constructor call with the constructed type and the arguments.
+    NewExpr(Box<Spanned<Self>>, Vec<Spanned<Self>>),
+    /// `count: 3` among the arguments of a call, the parameter name and the value.
+    NamedArgument(Box<Spanned<Self>>, Box<Spanned<Self>>),
 
 
     // Atoms
@@ -45,11 +65,24 @@ pub enum ChumskyNode {
     LiteralBool(bool),
     Identifier(Box<str>),
     Type(Vec<Spanned<Self>>),
+    ArrayType(Box<Spanned<Self>>),
+    TupleType(Vec<Spanned<Self>>),
+    /// Parameter types and the optional return type, `fn(int32) -> bool`.
+    FunctionType(Vec<Spanned<Self>>, Option<Box<Spanned<Self>>>),
+    TuplePattern(Vec<Spanned<Self>>),
+    GenericIdentifier(Box<Spanned<Self>>, Vec<Spanned<Self>>),
+    GenericParam(Box<Spanned<Self>>, Vec<Spanned<Self>>),
 
 
     // Utility
     VarAndType(Box<Spanned<Self>>, Box<Spanned<Self>>),
+    /// `sep: string = ", "`, a `VarAndType` parameter and its default value.
+    DefaultParam(Box<Spanned<Self>>, Box<Spanned<Self>>),
     MutThis(bool),
+    UnionVariant(Box<Spanned<Self>>, Vec<Spanned<Self>>),
+    /// Member of an enum with its explicit value, `Green = 5`.
+    EnumVariant(Box<Spanned<Self>>, Option<i64>),
+    Pattern(Box<Spanned<Self>>, Option<PatternFields>),
     
 
     Unknown
@@ -11,29 +11,110 @@ public class ArpTypeCollection
         {
             FullName = ty.FullName ?? throw new Exception("Can't determine type's full name."),
             ShortName = ty.Name,
+            GenericParams = ty.IsGenericTypeDefinition ? ty.GetGenericArguments().Select(arg => arg.Name).ToList() : [],
+            IsInterface = ty.IsInterface,
+            IsValueType = ty.IsValueType,
+            IsEnum = ty.IsEnum,
+            IsDelegate = typeof(Delegate).IsAssignableFrom(ty) && ty != typeof(Delegate) && ty != typeof(MulticastDelegate),
         };
 
-        foreach (var fld in ty.GetFields())
+        if (ty.IsEnum)
+        {
+            foreach (var member in ty.GetFields(System.Reflection.BindingFlags.Public | System.Reflection.BindingFlags.Static))
+            {
+                arpTypeInfo.EnumMembers.Add(new ArpEnumMember {
+                    Ident = member.Name,
+                    Value = Convert.ToInt64(member.GetRawConstantValue()),
+                });
+            }
+        }
+
+        foreach (var fld in ty.GetFields(System.Reflection.BindingFlags.Public | System.Reflection.BindingFlags.Instance))
         {
             arpTypeInfo.Fields.Add(new ArpTypedIdent {
                 Ident = fld.Name,
-                TyFullName = fld.FieldType.FullName ?? throw new Exception("Can't determine type's full name."),
+                TyFullName = TypeName(fld.FieldType),
+            });
+        }
+
+        // Members of enums are exported above.
+        foreach (var fld in ty.IsEnum ? Array.Empty<System.Reflection.FieldInfo>() : ty.GetFields(System.Reflection.BindingFlags.Public | System.Reflection.BindingFlags.Static))
+        {
+            arpTypeInfo.StaticFields.Add(new ArpStaticFieldInfo {
+                Ident = fld.Name,
+                TyFullName = TypeName(fld.FieldType),
+                IsReadOnly = fld.IsInitOnly || fld.IsLiteral,
+                Constant = fld.IsLiteral ? fld.GetRawConstantValue() : null,
+            });
+        }
+
+        // Indexers like `List.Item` are called through their accessors.
+        foreach (var prop in ty.GetProperties().Where(prop => prop.GetIndexParameters().Length == 0))
+        {
+            var accessor = prop.GetMethod ?? prop.SetMethod;
+
+            arpTypeInfo.Properties.Add(new ArpPropertyInfo {
+                Ident = prop.Name,
+                TyFullName = TypeName(prop.PropertyType),
+                IsStatic = accessor?.IsStatic ?? false,
+                CanRead = prop.GetMethod?.IsPublic ?? false,
+                CanWrite = prop.SetMethod?.IsPublic ?? false,
             });
         }
 
         foreach (var mtd in ty.GetMethods()){
             arpTypeInfo.Methods.Add(new ArpMethodInfo {
                 Ident = mtd.Name,
-                ReturnTyFullName = mtd.ReturnType.FullName ?? throw new Exception("Can't determine type's full name."),
+                ReturnTyFullName = TypeName(mtd.ReturnType),
                 Args = mtd.GetParameters().Select(par => new ArpTypedIdent {
                     Ident = par.Name ?? "",
-                    TyFullName = par.ParameterType.FullName ?? throw new Exception("Can't determine type's full name."),
+                    TyFullName = TypeName(par.ParameterType),
                 }).ToList(),
+                Defaults = mtd.GetParameters().Select(par => par.HasDefaultValue ? par.RawDefaultValue : null).ToList(),
+                IsParams = mtd.GetParameters().LastOrDefault()?.IsDefined(typeof(ParamArrayAttribute), false) ?? false,
+            });
+        }
+
+        foreach (var ctor in ty.GetConstructors()){
+            arpTypeInfo.Constructors.Add(new ArpMethodInfo {
+                Ident = ctor.Name,
+                ReturnTyFullName = "System.Void",
+                Args = ctor.GetParameters().Select(par => new ArpTypedIdent {
+                    Ident = par.Name ?? "",
+                    TyFullName = TypeName(par.ParameterType),
+                }).ToList(),
+                Defaults = ctor.GetParameters().Select(par => par.HasDefaultValue ? par.RawDefaultValue : null).ToList(),
+                IsParams = ctor.GetParameters().LastOrDefault()?.IsDefined(typeof(ParamArrayAttribute), false) ?? false,
             });
         }
 
         collection.Add(arpTypeInfo);
     }
+
+    // Generic parameters are written as `!0` (type) or `!!0` (method),
+    // constructed generics as `System.Collections.Generic.List<!0>` and arrays as `System.String[]`.
+    private static string TypeName(Type ty)
+    {
+        if (ty.IsGenericParameter)
+        {
+            var prefix = ty.DeclaringMethod is null ? "!" : "!!";
+            return prefix + ty.GenericParameterPosition;
+        }
+
+        if (ty.IsSZArray)
+        {
+            return TypeName(ty.GetElementType() ?? throw new Exception("Can't determine array's element type.")) + "[]";
+        }
+
+        if (ty.IsConstructedGenericType)
+        {
+            var definition = ty.GetGenericTypeDefinition().FullName ?? throw new Exception("Can't determine type's full name.");
+            var args = ty.GetGenericArguments().Select(TypeName);
+            return definition.Split('`')[0] + "<" + string.Join(", ", args) + ">";
+        }
+
+        return ty.FullName ?? throw new Exception("Can't determine type's full name.");
+    }
 }
 
 public class ArpTypeInfo
@@ -41,7 +122,16 @@ public class ArpTypeInfo
     
     [JsonInclude] public required string FullName;
     [JsonInclude] public string? ShortName;
+    [JsonInclude] public List<string> GenericParams = [];
+    [JsonInclude] public bool IsInterface;
+    [JsonInclude] public bool IsValueType;
+    [JsonInclude] public bool IsEnum;
+    [JsonInclude] public bool IsDelegate;
+    [JsonInclude] public List<ArpEnumMember> EnumMembers = [];
+    [JsonInclude] public List<ArpMethodInfo> Constructors = [];
     [JsonInclude] public List<ArpTypedIdent> Fields = [];
+    [JsonInclude] public List<ArpStaticFieldInfo> StaticFields = [];
+    [JsonInclude] public List<ArpPropertyInfo> Properties = [];
     [JsonInclude] public List<ArpMethodInfo> Methods = [];
 
 }
@@ -52,10 +142,35 @@ public class ArpTypedIdent
     [JsonInclude] public required string TyFullName;
 }
 
+public class ArpStaticFieldInfo
+{
+    [JsonInclude] public required string Ident;
+    [JsonInclude] public required string TyFullName;
+    [JsonInclude] public bool IsReadOnly;
+    [JsonInclude] public object? Constant;
+}
+
+public class ArpPropertyInfo
+{
+    [JsonInclude] public required string Ident;
+    [JsonInclude] public required string TyFullName;
+    [JsonInclude] public bool IsStatic;
+    [JsonInclude] public bool CanRead;
+    [JsonInclude] public bool CanWrite;
+}
+
+public class ArpEnumMember
+{
+    [JsonInclude] public required string Ident;
+    [JsonInclude] public long Value;
+}
 
 public class ArpMethodInfo
 {
     [JsonInclude] public required string Ident;
     [JsonInclude] public List<ArpTypedIdent> Args = [];
+    // Default values of the optional parameters, `null` for required ones.
+    [JsonInclude] public List<object?> Defaults = [];
+    [JsonInclude] public bool IsParams;
     [JsonInclude] public required string ReturnTyFullName;
 }
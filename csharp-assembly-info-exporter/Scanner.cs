@@ -11,7 +11,10 @@ public static class Scanner
 
         var asm = Assembly.LoadFile(Path.GetFullPath(path));
 
-        foreach (var ty in asm.GetExportedTypes())
+        // Facades like System.Runtime.dll forward most of their types to System.Private.CoreLib.
+        var forwarded = asm.GetForwardedTypes().Where(ty => ty.IsPublic);
+
+        foreach (var ty in asm.GetExportedTypes().Concat(forwarded))
         {
             try
             {